@@ -0,0 +1,125 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Compares the parallel verification of the `std` feature against the
+//! sequential one of `alloc`, along with the barycentric evaluation of the
+//! public inputs done by the prover:
+//!
+//! ```text
+//! cargo bench --bench verify --features bench
+//! cargo bench --bench verify --no-default-features --features alloc,bench
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dusk_plonk::prelude::*;
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[cfg(feature = "std")]
+const PATH: &str = "parallel";
+#[cfg(not(feature = "std"))]
+const PATH: &str = "sequential";
+
+/// Circuit of `DEGREE` gates, the first `PUBLIC` of them with a public input
+#[derive(Debug, Default, Clone, Copy)]
+struct PublicInputsCircuit<const DEGREE: usize, const PUBLIC: usize>;
+
+impl<const DEGREE: usize, const PUBLIC: usize> Circuit
+    for PublicInputsCircuit<DEGREE, PUBLIC>
+{
+    fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+        for i in 0..PUBLIC {
+            let pi = BlsScalar::from(i as u64 + 1);
+            composer.append_public(pi);
+        }
+
+        while composer.constraints() < DEGREE {
+            let a = composer.append_witness(BlsScalar::from(2));
+            let b = composer.append_witness(BlsScalar::from(3));
+            composer.gate_add(Constraint::new().left(1).right(1).a(a).b(b));
+        }
+
+        Ok(())
+    }
+}
+
+fn verify<const DEGREE: usize, const PUBLIC: usize>(
+    c: &mut Criterion,
+    pp: &PublicParameters,
+) {
+    let label = b"dusk-network";
+    let (prover, verifier) =
+        Compiler::compile::<PublicInputsCircuit<DEGREE, PUBLIC>>(pp, label)
+            .expect("failed to compile circuit");
+
+    let mut rng = StdRng::seed_from_u64(0xbe7c);
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &PublicInputsCircuit::<DEGREE, PUBLIC>)
+        .expect("failed to prove");
+
+    let power = (DEGREE as f64).log2() as usize;
+    let description = format!(
+        "Verify {} 2^{} gates, {} public inputs",
+        PATH, power, PUBLIC
+    );
+
+    c.bench_function(description.as_str(), |b| {
+        b.iter(|| verifier.verify(black_box(&proof), black_box(&public_inputs)))
+    });
+}
+
+/// Barycentric evaluation of the public inputs polynomial, as done by the
+/// prover when computing the linearization polynomial
+fn barycentric(c: &mut Criterion, power: usize, non_zero: usize) {
+    let mut rng = StdRng::seed_from_u64(0xba7c);
+
+    let mut evaluations = vec![BlsScalar::zero(); 1 << power];
+    evaluations
+        .iter_mut()
+        .take(non_zero)
+        .for_each(|eval| *eval = BlsScalar::random(&mut rng));
+    let point = BlsScalar::random(&mut rng);
+
+    let description = format!(
+        "Prover barycentric {} 2^{} evaluations, {} non-zero",
+        PATH, power, non_zero
+    );
+
+    c.bench_function(description.as_str(), |b| {
+        b.iter(|| Proof::barycentric_eval(black_box(&evaluations), &point))
+    });
+}
+
+fn verify_benchmark(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0x5e7);
+    let pp = PublicParameters::setup(1 << 14, &mut rng)
+        .expect("failed to generate pp");
+
+    verify::<{ 1 << 5 }, 1>(c, &pp);
+    verify::<{ 1 << 9 }, 1>(c, &pp);
+    verify::<{ 1 << 9 }, 64>(c, &pp);
+    verify::<{ 1 << 13 }, 1>(c, &pp);
+    verify::<{ 1 << 13 }, 64>(c, &pp);
+    verify::<{ 1 << 13 }, 1024>(c, &pp);
+}
+
+fn barycentric_benchmark(c: &mut Criterion) {
+    for power in [5usize, 9, 10, 13, 17] {
+        for non_zero in [1, 16, 64, 256, 1 << power] {
+            if non_zero <= 1 << power {
+                barycentric(c, power, non_zero);
+            }
+        }
+    }
+}
+
+criterion_group! {
+    name = verify_bench;
+    config = Criterion::default().sample_size(10);
+    targets = verify_benchmark, barycentric_benchmark
+}
+criterion_main!(verify_bench);
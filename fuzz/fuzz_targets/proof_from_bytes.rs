@@ -0,0 +1,34 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![no_main]
+
+use dusk_bytes::{DeserializableSlice, Serializable};
+use dusk_plonk::prelude::Proof;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // slices of any length are rejected or decoded, never panic
+    let proof = Proof::from_slice(data);
+
+    let mut framed = data;
+    let _ = Proof::read_framed(&mut framed);
+
+    let Ok(bytes) = <[u8; Proof::SIZE]>::try_from(data) else {
+        assert!(proof.is_err());
+        return;
+    };
+
+    // both decoders accept the same encodings
+    let checked = Proof::from_bytes_checked(&bytes);
+    assert_eq!(proof.is_ok(), checked.is_ok());
+
+    // and only the canonical ones
+    if let Ok(proof) = proof {
+        assert_eq!(proof.to_bytes(), bytes);
+        assert_eq!(checked.ok(), Some(proof));
+    }
+});
@@ -22,10 +22,13 @@ pub(crate) use kzg10::Commitment;
 pub(crate) use kzg10::AggregateProof;
 
 #[cfg(feature = "alloc")]
-pub(crate) use kzg10::{CommitKey, OpeningKey};
+pub(crate) use kzg10::Proof as OpeningProof;
 
 #[cfg(feature = "alloc")]
-pub use kzg10::PublicParameters;
+pub(crate) use kzg10::CommitKey;
+
+#[cfg(feature = "alloc")]
+pub use kzg10::{OpeningKey, PublicParameters};
 
 #[cfg(all(feature = "alloc", feature = "rkyv-impl"))]
 pub use kzg10::{
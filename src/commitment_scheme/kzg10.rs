@@ -29,3 +29,5 @@ pub(crate) mod commitment;
 pub(crate) mod proof;
 
 pub(crate) use commitment::Commitment;
+#[cfg(feature = "alloc")]
+pub(crate) use proof::Proof;
@@ -7,6 +7,7 @@
 //! Module containing the representation of a Commitment to a Polynomial.
 use dusk_bls12_381::{G1Affine, G1Projective};
 use dusk_bytes::{DeserializableSlice, Serializable};
+use subtle::{Choice, ConstantTimeEq};
 
 #[cfg(feature = "rkyv-impl")]
 use bytecheck::CheckBytes;
@@ -43,6 +44,12 @@ impl From<G1Projective> for Commitment {
     }
 }
 
+impl ConstantTimeEq for Commitment {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
 impl Serializable<{ G1Affine::SIZE }> for Commitment {
     type Error = dusk_bytes::Error;
 
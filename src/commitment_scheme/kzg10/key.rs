@@ -9,7 +9,10 @@
 //! Opening keys.
 use super::{proof::Proof, Commitment};
 use crate::{
-    error::Error, fft::Polynomial, transcript::TranscriptProtocol, util,
+    error::Error,
+    fft::Polynomial,
+    transcript::{TranscriptBackend, TranscriptProtocol},
+    util,
 };
 use alloc::vec::Vec;
 use dusk_bls12_381::{
@@ -18,6 +21,8 @@ use dusk_bls12_381::{
 };
 use dusk_bytes::{DeserializableSlice, Serializable};
 use merlin::Transcript;
+#[cfg(feature = "std")]
+use rayon::prelude::*;
 
 #[cfg(feature = "rkyv-impl")]
 use bytecheck::CheckBytes;
@@ -186,6 +191,26 @@ impl CommitKey {
         )))
     }
 
+    /// Commits to each of the given [`Polynomial`]s, returning the
+    /// corresponding [`Commitment`]s in the same order.
+    ///
+    /// The commitments are independent, so they are computed in parallel when
+    /// the `std` feature is enabled.
+    pub(crate) fn commit_all(
+        &self,
+        polynomials: &[&Polynomial],
+    ) -> Result<Vec<Commitment>, Error> {
+        #[cfg(not(feature = "std"))]
+        let polynomials_iter = polynomials.iter();
+
+        #[cfg(feature = "std")]
+        let polynomials_iter = polynomials.par_iter();
+
+        polynomials_iter
+            .map(|polynomial| self.commit(polynomial))
+            .collect()
+    }
+
     /// Computes a single witness for multiple polynomials at the same point, by
     /// taking a random linear combination of the individual witnesses.
     /// We apply the same optimization mentioned in when computing each witness;
@@ -274,9 +299,19 @@ impl OpeningKey {
         }
     }
 
+    /// The generator `[1]_2` of G2 and `[x]_2`, the generator times the
+    /// secret of the setup, in this order.
+    ///
+    /// These are the points the opening proofs are paired against, so the
+    /// pairing check of an opening of `C` to `v` at `z` with witness `W` can
+    /// be performed with another library as
+    /// `e(C - v·[1]_1 + z·W, [1]_2) = e(W, [x]_2)`.
+    pub fn g2_elements(&self) -> (G2Affine, G2Affine) {
+        (self.h, self.x_h)
+    }
+
     /// Checks whether a batch of polynomials evaluated at different points,
     /// returned their specified value.
-    #[allow(dead_code)]
     pub(crate) fn batch_check(
         &self,
         points: &[BlsScalar],
@@ -320,31 +355,46 @@ impl OpeningKey {
         };
         Ok(())
     }
-}
 
-#[cfg(feature = "std")]
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::commitment_scheme::{AggregateProof, PublicParameters};
-    use crate::fft::Polynomial;
-    use dusk_bls12_381::BlsScalar;
-    use dusk_bytes::Serializable;
-    use merlin::Transcript;
-    use rand_core::OsRng;
+    /// Checks whether a batch of polynomials evaluated at different points,
+    /// returned their specified value, identifying the first failing proof.
+    ///
+    /// The batch is first checked at once with [`Self::batch_check`]. Only if
+    /// that fails, every proof is checked on its own and the index of the
+    /// first invalid one is returned with [`Error::InvalidBatchItem`].
+    pub(crate) fn batch_check_indexed(
+        &self,
+        points: &[BlsScalar],
+        proofs: &[Proof],
+        transcript: &mut Transcript,
+    ) -> Result<(), Error> {
+        let err = match self.batch_check(points, proofs, transcript) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+
+        match points
+            .iter()
+            .zip(proofs)
+            .position(|(point, proof)| !self.check(point, proof))
+        {
+            Some(index) => Err(Error::InvalidBatchItem { index }),
+            None => Err(err),
+        }
+    }
 
-    // Checks that a polynomial `p` was evaluated at a point `z` and returned
-    // the value specified `v`. ie. v = p(z).
-    fn check(op_key: &OpeningKey, point: BlsScalar, proof: Proof) -> bool {
+    /// Checks that a polynomial `p` was evaluated at a point `z` and returned
+    /// the value specified `v`. ie. v = p(z).
+    pub(crate) fn check(&self, point: &BlsScalar, proof: &Proof) -> bool {
         let inner_a: G1Affine = (proof.commitment_to_polynomial.0
-            - (op_key.g * proof.evaluated_point))
+            - (self.g * proof.evaluated_point))
             .into();
 
-        let inner_b: G2Affine = (op_key.x_h - (op_key.h * point)).into();
+        let inner_b: G2Affine = (self.x_h - (self.h * point)).into();
         let prepared_inner_b = G2Prepared::from(-inner_b);
 
         let pairing = dusk_bls12_381::multi_miller_loop(&[
-            (&inner_a, &op_key.prepared_h),
+            (&inner_a, &self.prepared_h),
             (&proof.commitment_to_witness.0, &prepared_inner_b),
         ])
         .final_exponentiation();
@@ -352,6 +402,57 @@ mod test {
         pairing == dusk_bls12_381::Gt::identity()
     }
 
+    /// Checks that `e(left_i, [x]_2) · e(right_i, [1]_2)` is the identity for
+    /// every pair of points, using a single multi-pairing.
+    ///
+    /// Every point is appended to the transcript before deriving the
+    /// challenge whose powers are used to combine the pairs.
+    pub(crate) fn batch_check_pairing_points(
+        &self,
+        points: &[(G1Affine, G1Affine)],
+        transcript: &mut Transcript,
+    ) -> Result<(), Error> {
+        if points.is_empty() {
+            return Ok(());
+        }
+
+        for (left, right) in points {
+            transcript.append_commitment(b"left", &Commitment(*left));
+            transcript.append_commitment(b"right", &Commitment(*right));
+        }
+
+        let challenge = transcript.challenge_scalar(b"batch");
+        let powers = util::powers_of(&challenge, points.len() - 1);
+
+        let (left, right): (Vec<G1Affine>, Vec<G1Affine>) =
+            points.iter().copied().unzip();
+        let left = G1Affine::from(msm_variable_base(&left, &powers));
+        let right = G1Affine::from(msm_variable_base(&right, &powers));
+
+        let pairing = dusk_bls12_381::multi_miller_loop(&[
+            (&left, &self.prepared_x_h),
+            (&right, &self.prepared_h),
+        ])
+        .final_exponentiation();
+
+        if pairing != dusk_bls12_381::Gt::identity() {
+            return Err(Error::PairingCheckFailure);
+        };
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commitment_scheme::{AggregateProof, PublicParameters};
+    use crate::fft::Polynomial;
+    use dusk_bls12_381::BlsScalar;
+    use dusk_bytes::Serializable;
+    use merlin::Transcript;
+    use rand_core::OsRng;
+
     // Creates an opening proof that a polynomial `p` was correctly evaluated at
     // p(z) and produced the value `v`. ie v = p(z).
     // Returns an error if the polynomials degree is too large.
@@ -437,7 +538,7 @@ mod test {
 
         let proof = open_single(&ck, &poly, &value, &point)?;
 
-        let ok = check(&opening_key, point, proof);
+        let ok = opening_key.check(&point, &proof);
         assert!(ok);
         Ok(())
     }
@@ -453,13 +554,13 @@ mod test {
         let poly_a = Polynomial::rand(degree, &mut OsRng);
         let value_a = poly_a.evaluate(&point_a);
         let proof_a = open_single(&ck, &poly_a, &value_a, &point_a)?;
-        assert!(check(&vk, point_a, proof_a));
+        assert!(vk.check(&point_a, &proof_a));
 
         // Compute secret polynomial b
         let poly_b = Polynomial::rand(degree, &mut OsRng);
         let value_b = poly_b.evaluate(&point_b);
         let proof_b = open_single(&ck, &poly_b, &value_b, &point_b)?;
-        assert!(check(&vk, point_b, proof_b));
+        assert!(vk.check(&point_b, &proof_b));
 
         vk.batch_check(
             &[point_a, point_b],
@@ -468,6 +569,34 @@ mod test {
         )
     }
     #[test]
+    fn test_batch_check_indexed() -> Result<(), Error> {
+        let degree = 25;
+        let (ck, vk) = setup_test(degree)?;
+
+        let points: Vec<BlsScalar> = (10..13u64).map(BlsScalar::from).collect();
+        let mut proofs = points
+            .iter()
+            .map(|point| {
+                let poly = Polynomial::rand(degree, &mut OsRng);
+                let value = poly.evaluate(point);
+                open_single(&ck, &poly, &value, point)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        vk.batch_check_indexed(&points, &proofs, &mut Transcript::new(b""))?;
+
+        proofs[1].evaluated_point += BlsScalar::one();
+        assert_eq!(
+            vk.batch_check(&points, &proofs, &mut Transcript::new(b"")),
+            Err(Error::PairingCheckFailure)
+        );
+        assert_eq!(
+            vk.batch_check_indexed(&points, &proofs, &mut Transcript::new(b"")),
+            Err(Error::InvalidBatchItem { index: 1 })
+        );
+        Ok(())
+    }
+    #[test]
     fn test_aggregate_witness() -> Result<(), Error> {
         let max_degree = 27;
         let (ck, opening_key) = setup_test(max_degree)?;
@@ -499,7 +628,7 @@ mod test {
             let transcript = &mut Transcript::new(b"agg_flatten");
             let v_challenge = transcript.challenge_scalar(b"v_challenge");
             let flattened_proof = aggregated_proof.flatten(&v_challenge);
-            check(&opening_key, point, flattened_proof)
+            opening_key.check(&point, &flattened_proof)
         };
 
         assert!(ok);
@@ -565,6 +694,45 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_g2_elements() -> Result<(), Error> {
+        let degree = 25;
+        let (ck, opening_key) = setup_test(degree)?;
+        let point = BlsScalar::from(10);
+
+        let poly = Polynomial::rand(degree, &mut OsRng);
+        let value = poly.evaluate(&point);
+        let proof = open_single(&ck, &poly, &value, &point)?;
+        assert!(opening_key.check(&point, &proof));
+
+        // perform the pairing of the opening with the exported points
+        let (h, x_h) = opening_key.g2_elements();
+        let pairs = |value: BlsScalar| {
+            let witness = proof.commitment_to_witness.0;
+            let left: G1Affine = (proof.commitment_to_polynomial.0
+                - opening_key.g * value
+                + witness * point)
+                .into();
+
+            dusk_bls12_381::multi_miller_loop(&[
+                (&left, &G2Prepared::from(h)),
+                (&-witness, &G2Prepared::from(x_h)),
+            ])
+            .final_exponentiation()
+        };
+        assert_eq!(pairs(value), dusk_bls12_381::Gt::identity());
+        assert_ne!(
+            pairs(value + BlsScalar::one()),
+            dusk_bls12_381::Gt::identity()
+        );
+
+        // the points are the ones serialized with the key
+        let bytes = opening_key.to_bytes();
+        assert_eq!(&bytes[G1Affine::SIZE..][..G2Affine::SIZE], &h.to_bytes());
+        assert_eq!(&bytes[G1Affine::SIZE + G2Affine::SIZE..], &x_h.to_bytes());
+        Ok(())
+    }
+
     #[test]
     fn opening_key_dusk_bytes() -> Result<(), Error> {
         let (_, opening_key) = setup_test(7)?;
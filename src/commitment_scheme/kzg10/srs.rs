@@ -9,11 +9,18 @@
 use super::key::{CommitKey, OpeningKey};
 use crate::{error::Error, util};
 use alloc::vec::Vec;
-use dusk_bls12_381::{BlsScalar, G1Affine, G1Projective, G2Affine};
+use dusk_bls12_381::{
+    multiscalar_mul::msm_variable_base, BlsScalar, G1Affine, G1Projective,
+    G2Affine, G2Prepared,
+};
 use dusk_bytes::{DeserializableSlice, Serializable};
 use ff::Field;
+use merlin::Transcript;
 use rand_core::{CryptoRng, RngCore};
 
+#[cfg(feature = "std")]
+use rayon::prelude::*;
+
 #[cfg(feature = "rkyv-impl")]
 use bytecheck::CheckBytes;
 #[cfg(feature = "rkyv-impl")]
@@ -48,7 +55,7 @@ impl PublicParameters {
     /// because adding the blinding factors requires some extra elements
     /// for the SRS: +1 per each wire (we have 4 wires), plus +2 for the
     /// permutation polynomial
-    const ADDED_BLINDING_DEGREE: usize = 6;
+    pub(crate) const ADDED_BLINDING_DEGREE: usize = 6;
 
     /// Setup generates the public parameters using a random number generator.
     /// This method will in most cases be used for testing and exploration.
@@ -96,6 +103,110 @@ impl PublicParameters {
         })
     }
 
+    /// Generate the public parameters deterministically from a `seed`, so
+    /// tests and examples get the same parameters on every machine.
+    ///
+    /// # Warning
+    ///
+    /// **Never use these parameters in production.** The secret `x` of the
+    /// setup, the toxic waste, is derived from the seed alone, so anyone who
+    /// knows the seed can forge proofs for any circuit compiled with these
+    /// parameters. Use the output of a trusted setup ceremony instead, with
+    /// [`PublicParameters::from_powers_of_tau`].
+    pub fn insecure_setup(
+        max_degree: usize,
+        seed: u64,
+    ) -> Result<PublicParameters, Error> {
+        let mut transcript = Transcript::new(b"dusk-plonk-insecure-setup");
+        transcript.append_u64(b"seed", seed);
+
+        Self::setup(max_degree, &mut SeededRng(transcript))
+    }
+
+    /// Load the public parameters from the output of a powers-of-tau
+    /// ceremony, to commit to polynomials up to `max_degree`.
+    ///
+    /// `g1_powers` is the list of the G1 powers `[tau^i]_1` and `g2_powers`
+    /// the list of the G2 powers `[tau^i]_2`, in ascending order from `i = 0`
+    /// and in the compressed encoding of the points. Only the first G1 powers
+    /// required for the degree, and the first two G2 powers, are loaded.
+    ///
+    /// Every loaded point is decompressed and checked to be in the prime
+    /// order subgroup, in parallel when the `std` feature is enabled. Every
+    /// G1 power is then checked to be `tau` times the previous one, with the
+    /// `tau` of the G2 powers, with a single pairing check over a random
+    /// linear combination of the G1 powers. The random coefficients are
+    /// derived from the loaded powers, so the check is deterministic.
+    ///
+    /// Returns [`Error::InsufficientPowersOfTau`] if the ceremony doesn't
+    /// provide enough G1 powers for the degree.
+    pub fn from_powers_of_tau(
+        g1_powers: &[u8],
+        g2_powers: &[u8],
+        max_degree: usize,
+    ) -> Result<PublicParameters, Error> {
+        // Cannot commit to constants
+        if max_degree < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+
+        let needed = max_degree + Self::ADDED_BLINDING_DEGREE + 1;
+        let available = g1_powers.len() / G1Affine::SIZE;
+        if available < needed {
+            return Err(Error::InsufficientPowersOfTau { needed, available });
+        }
+
+        if g2_powers.len() < 2 * G2Affine::SIZE {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let g1_powers = &g1_powers[..needed * G1Affine::SIZE];
+
+        #[cfg(not(feature = "std"))]
+        let g1_chunks = g1_powers.chunks_exact(G1Affine::SIZE);
+
+        #[cfg(feature = "std")]
+        let g1_chunks = g1_powers.par_chunks_exact(G1Affine::SIZE);
+
+        let powers_of_g = g1_chunks
+            .map(G1Affine::from_slice)
+            .collect::<Result<Vec<G1Affine>, dusk_bytes::Error>>()?;
+
+        let mut g2_powers = g2_powers;
+        let h = G2Affine::from_reader(&mut g2_powers)?;
+        let x_h = G2Affine::from_reader(&mut g2_powers)?;
+
+        // e(Σ r_i · [tau^i]_1, [tau]_2) = e(Σ r_i · [tau^(i+1)]_1, [1]_2)
+        let mut transcript = Transcript::new(b"dusk-plonk-powers-of-tau");
+        transcript.append_message(b"g1_powers", g1_powers);
+        transcript.append_message(b"h", &h.to_bytes());
+        transcript.append_message(b"x_h", &x_h.to_bytes());
+        let mut rng = SeededRng(transcript);
+
+        let r: Vec<BlsScalar> =
+            (1..needed).map(|_| BlsScalar::random(&mut rng)).collect();
+        let powers =
+            G1Affine::from(msm_variable_base(&powers_of_g[..needed - 1], &r));
+        let shifted = G1Affine::from(msm_variable_base(&powers_of_g[1..], &r));
+
+        let pairing = dusk_bls12_381::multi_miller_loop(&[
+            (&shifted, &G2Prepared::from(-h)),
+            (&powers, &G2Prepared::from(x_h)),
+        ])
+        .final_exponentiation();
+
+        if pairing != dusk_bls12_381::Gt::identity() {
+            return Err(Error::PairingCheckFailure);
+        }
+
+        let g = powers_of_g[0];
+
+        Ok(PublicParameters {
+            commit_key: CommitKey { powers_of_g },
+            opening_key: OpeningKey::new(g, h, x_h),
+        })
+    }
+
     /// Serialize the [`PublicParameters`] into bytes.
     ///
     /// This operation is designed to store the raw representation of the
@@ -179,12 +290,18 @@ impl PublicParameters {
     /// Returns the [`CommitKey`] and [`OpeningKey`] used to generate and verify
     /// proofs.
     ///
-    /// Returns an error if the truncated degree is larger than the public
-    /// parameters configured degree.
+    /// Returns [`Error::InsufficientSRS`] if the truncated degree, along with
+    /// the blinding, is larger than the public parameters configured degree.
     pub(crate) fn trim(
         &self,
         truncated_degree: usize,
     ) -> Result<(CommitKey, OpeningKey), Error> {
+        let needed = truncated_degree + Self::ADDED_BLINDING_DEGREE;
+        let available = self.max_degree();
+        if needed > available {
+            return Err(Error::InsufficientSRS { needed, available });
+        }
+
         let truncated_prover_key = self
             .commit_key
             .truncate(truncated_degree + Self::ADDED_BLINDING_DEGREE)?;
@@ -199,6 +316,40 @@ impl PublicParameters {
     }
 }
 
+/// Generator drawing its bytes from a transcript, for the secrets of
+/// [`PublicParameters::insecure_setup`] and the random scalars checking the
+/// powers of [`PublicParameters::from_powers_of_tau`].
+///
+/// The output is as unpredictable as the transcript for whoever doesn't know
+/// its inputs. The seed of an insecure setup is public by design, so its
+/// secrets are only fit for tests, while the ceremony output is fixed before
+/// the scalars checking it are derived.
+struct SeededRng(Transcript);
+
+impl RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.challenge_bytes(b"secret", dest);
+    }
+
+    fn try_fill_bytes(
+        &mut self,
+        dest: &mut [u8],
+    ) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for SeededRng {}
+
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod test {
@@ -233,6 +384,106 @@ mod test {
         assert_eq!(got_pp.opening_key.x_h, pp.opening_key.x_h);
     }
 
+    #[test]
+    fn public_parameters_insecure_setup() {
+        let pp = PublicParameters::insecure_setup(1 << 4, 0xdead).unwrap();
+        let same = PublicParameters::insecure_setup(1 << 4, 0xdead).unwrap();
+        let other = PublicParameters::insecure_setup(1 << 4, 0xbeef).unwrap();
+
+        assert_eq!(pp.max_degree(), (1 << 4) + 6);
+        assert_eq!(pp.to_var_bytes(), same.to_var_bytes());
+        assert_ne!(pp.to_var_bytes(), other.to_var_bytes());
+
+        assert_eq!(
+            PublicParameters::insecure_setup(0, 0xdead).err(),
+            Some(Error::DegreeIsZero)
+        );
+    }
+
+    #[test]
+    fn public_parameters_from_powers_of_tau() {
+        let max_degree = 1 << 4;
+        let pp = PublicParameters::setup(max_degree, &mut OsRng).unwrap();
+
+        let g1_powers = pp.commit_key.to_var_bytes();
+        let mut g2_powers = pp.opening_key.h.to_bytes().to_vec();
+        g2_powers.extend(pp.opening_key.x_h.to_bytes());
+
+        let loaded =
+            PublicParameters::from_powers_of_tau(&g1_powers, &g2_powers, 8)
+                .unwrap();
+        assert_eq!(loaded.max_degree(), 8 + 6);
+        assert_eq!(
+            loaded.commit_key.powers_of_g[..],
+            pp.commit_key.powers_of_g[..=8 + 6]
+        );
+        assert_eq!(loaded.opening_key.to_bytes(), pp.opening_key.to_bytes());
+
+        // the whole ceremony is loaded for the degree of the setup
+        let loaded = PublicParameters::from_powers_of_tau(
+            &g1_powers, &g2_powers, max_degree,
+        )
+        .unwrap();
+        assert_eq!(loaded.commit_key, pp.commit_key);
+
+        assert!(matches!(
+            PublicParameters::from_powers_of_tau(
+                &g1_powers,
+                &g2_powers,
+                max_degree + 1
+            ),
+            Err(Error::InsufficientPowersOfTau {
+                needed,
+                available,
+            }) if needed == max_degree + 8 && available == max_degree + 7
+        ));
+        assert_eq!(
+            PublicParameters::from_powers_of_tau(
+                &g1_powers,
+                &g2_powers[..G2Affine::SIZE],
+                8
+            )
+            .err(),
+            Some(Error::NotEnoughBytes)
+        );
+
+        // a point out of the curve is rejected
+        let mut corrupted = g1_powers.clone();
+        corrupted[G1Affine::SIZE + 1] ^= 1;
+        assert!(PublicParameters::from_powers_of_tau(
+            &corrupted, &g2_powers, 8
+        )
+        .is_err());
+
+        // a single G1 power of another tau is rejected
+        let mut tampered = g1_powers.clone();
+        let power = G1Affine::from(
+            G1Projective::from(pp.commit_key.powers_of_g[5])
+                + G1Affine::generator(),
+        );
+        tampered[5 * G1Affine::SIZE..6 * G1Affine::SIZE]
+            .copy_from_slice(&power.to_bytes());
+        assert_eq!(
+            PublicParameters::from_powers_of_tau(&tampered, &g2_powers, 8)
+                .err(),
+            Some(Error::PairingCheckFailure)
+        );
+
+        // powers of another tau are rejected
+        let other = PublicParameters::setup(max_degree, &mut OsRng).unwrap();
+        let mut other_g2_powers = other.opening_key.h.to_bytes().to_vec();
+        other_g2_powers.extend(other.opening_key.x_h.to_bytes());
+        assert_eq!(
+            PublicParameters::from_powers_of_tau(
+                &g1_powers,
+                &other_g2_powers,
+                8
+            )
+            .err(),
+            Some(Error::PairingCheckFailure)
+        );
+    }
+
     #[test]
     fn public_parameters_bytes_unchecked() {
         let pp = PublicParameters::setup(1 << 7, &mut OsRng).unwrap();
@@ -4,21 +4,28 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
 
 use crate::commitment_scheme::{CommitKey, OpeningKey, PublicParameters};
 use crate::error::Error;
 use crate::fft::{EvaluationDomain, Evaluations, Polynomial};
 use crate::proof_system::preprocess::Polynomials;
+use crate::proof_system::proof::QUOTIENT_CHUNKS;
+use crate::proof_system::quotient_poly::QuotientLayout;
 use crate::proof_system::{widget, ProverKey};
 
-use crate::prelude::{Circuit, Composer};
+use crate::prelude::{Circuit, Composer, CustomGate};
 
+mod aggregator;
 mod prover;
 mod verifier;
+mod verifier_ref;
 
+pub use aggregator::MultiProofAggregator;
 pub use prover::Prover;
 pub use verifier::Verifier;
+pub use verifier_ref::VerifierKeyRef;
 
 /// Generate the arguments to prove and verify a circuit
 pub struct Compiler;
@@ -37,7 +44,14 @@ impl Compiler {
         let mut composer = Composer::initialized();
         C::default().circuit(&mut composer)?;
 
-        Self::compile_with_composer(pp, label, &composer)
+        Self::compile_with_custom_gates(
+            pp,
+            label,
+            &composer,
+            C::CUSTOM_GATES,
+            Prover::HIDING_DEGREE,
+            QUOTIENT_CHUNKS,
+        )
     }
 
     /// Create a new arguments set from a given circuit instance
@@ -54,7 +68,79 @@ impl Compiler {
         let mut composer = Composer::initialized();
         circuit.circuit(&mut composer)?;
 
-        Self::compile_with_composer(pp, label, &composer)
+        Self::compile_with_custom_gates(
+            pp,
+            label,
+            &composer,
+            C::CUSTOM_GATES,
+            Prover::HIDING_DEGREE,
+            QUOTIENT_CHUNKS,
+        )
+    }
+
+    /// Create a new arguments set from a given circuit instance, blinding
+    /// the wire polynomials of the proofs with `hiding_degree + 1` random
+    /// scalars.
+    ///
+    /// Use the default implementation of the circuit. Every degree of
+    /// blinding over the default of [`Prover::HIDING_DEGREE`] requires four
+    /// extra powers in the public parameters, and a lower hiding degree is
+    /// raised to the default. The hiding degree has to be lower than the
+    /// size of the circuit, rounded to the next power of two.
+    pub fn compile_with_blinding<C>(
+        pp: &PublicParameters,
+        label: &[u8],
+        hiding_degree: usize,
+    ) -> Result<(Prover, Verifier), Error>
+    where
+        C: Circuit,
+    {
+        let mut composer = Composer::initialized();
+        C::default().circuit(&mut composer)?;
+
+        Self::compile_with_custom_gates(
+            pp,
+            label,
+            &composer,
+            C::CUSTOM_GATES,
+            hiding_degree,
+            QUOTIENT_CHUNKS,
+        )
+    }
+
+    /// Create a new arguments set from a given circuit instance, splitting
+    /// the quotient polynomial of the proofs into `quotient_chunks` chunks.
+    ///
+    /// Use the default implementation of the circuit. The quotient is split
+    /// into four chunks by default, and circuits with custom gates of a
+    /// higher degree can split it into up to one chunk per degree of their
+    /// gate identities, and at most 16. Every extra chunk adds a commitment
+    /// to the proofs, and lowers the degree of the public parameters needed
+    /// to prove the circuit.
+    ///
+    /// Proofs with more than four chunks have to be serialized with
+    /// [`Proof::to_var_bytes`].
+    ///
+    /// [`Proof::to_var_bytes`]: crate::prelude::Proof::to_var_bytes
+    pub fn compile_with_quotient_chunks<C>(
+        pp: &PublicParameters,
+        label: &[u8],
+        quotient_chunks: usize,
+    ) -> Result<(Prover, Verifier), Error>
+    where
+        C: Circuit,
+    {
+        let mut composer = Composer::initialized();
+        C::default().circuit(&mut composer)?;
+
+        Self::compile_with_custom_gates(
+            pp,
+            label,
+            &composer,
+            C::CUSTOM_GATES,
+            Prover::HIDING_DEGREE,
+            quotient_chunks,
+        )
     }
 
     /// Generates a [Prover] and [Verifier] from a buffer created by
@@ -64,34 +150,121 @@ impl Compiler {
         label: &[u8],
         compressed: &[u8],
     ) -> Result<(Prover, Verifier), Error> {
-        let composer = Composer::from_bytes(compressed)?;
+        let composer = Composer::from_compressed(compressed)?;
 
-        Self::compile_with_composer(pp, label, &composer)
+        Self::compile_with_custom_gates(
+            pp,
+            label,
+            &composer,
+            &[],
+            Prover::HIDING_DEGREE,
+            QUOTIENT_CHUNKS,
+        )
+    }
+
+    /// Generates a [Prover] and [Verifier] from the constraint system of the
+    /// circuit `C` built by `composer`, such as one reloaded with
+    /// [`Composer::from_bytes`].
+    ///
+    /// The custom gates enabled by the composer are the ones of
+    /// [`Circuit::CUSTOM_GATES`].
+    pub fn compile_with_composer<C>(
+        pp: &PublicParameters,
+        label: &[u8],
+        composer: &Composer,
+    ) -> Result<(Prover, Verifier), Error>
+    where
+        C: Circuit,
+    {
+        Self::compile_with_custom_gates(
+            pp,
+            label,
+            composer,
+            C::CUSTOM_GATES,
+            Prover::HIDING_DEGREE,
+            QUOTIENT_CHUNKS,
+        )
     }
 
     /// Create a new arguments set from a given circuit instance
     ///
     /// Use the default implementation of the circuit
-    fn compile_with_composer(
+    fn compile_with_custom_gates(
         pp: &PublicParameters,
         label: &[u8],
         composer: &Composer,
+        custom_gates: &[CustomGate],
+        hiding_degree: usize,
+        quotient_chunks: usize,
     ) -> Result<(Prover, Verifier), Error> {
-        let n = (composer.constraints() + 6).next_power_of_two();
-
-        let (commit, opening) = pp.trim(n)?;
-
-        let (prover, verifier) =
-            Self::preprocess(label, commit, opening, composer)?;
+        if let Some((id, _)) = composer
+            .custom_selectors
+            .iter()
+            .find(|(id, _)| *id >= custom_gates.len())
+        {
+            return Err(Error::UnregisteredCustomGate { id: *id });
+        }
+
+        EvaluationDomain::check_circuit_size(composer.constraints())?;
+        Prover::check_hiding_degree(
+            hiding_degree,
+            composer.constraints().next_power_of_two(),
+        )?;
+
+        let layout =
+            QuotientLayout::new(custom_gates).with_chunks(quotient_chunks)?;
+        let (commit, opening) = pp.trim(Self::commit_degree(
+            composer.constraints(),
+            &layout,
+            hiding_degree,
+        ))?;
+
+        let (prover, verifier) = Self::preprocess(
+            label,
+            commit,
+            opening,
+            composer,
+            custom_gates,
+            hiding_degree,
+            layout,
+        )?;
 
         Ok((prover, verifier))
     }
 
+    /// Degree the commit key has to support, before the blinding added by
+    /// [`PublicParameters::trim`], to prove a circuit of `constraints` gates
+    /// with the given quotient layout and hiding degree
+    ///
+    /// A hiding degree lower than [`Prover::HIDING_DEGREE`] is raised to it,
+    /// as the prover does when it's created, so the key is sized for the
+    /// blinding the proofs are actually computed with.
+    pub(crate) fn commit_degree(
+        constraints: usize,
+        layout: &QuotientLayout,
+        hiding_degree: usize,
+    ) -> usize {
+        let n = (constraints + 6).next_power_of_two();
+
+        // the last quotient chunk has a degree of a multiple of `n`, and every
+        // extra degree of blinding of the wires raises the degree of the
+        // last chunk by the degree of the gate identities, counted from the
+        // default hiding degree the prover applies at least
+        let hiding_degree = hiding_degree.max(Prover::HIDING_DEGREE);
+        let extra_degree = (layout.degree() * hiding_degree)
+            .saturating_sub(CustomGate::DEFAULT_DEGREE * Prover::HIDING_DEGREE);
+
+        layout.last_chunk_factor() * n + extra_degree
+    }
+
     fn preprocess(
         label: &[u8],
         commit_key: CommitKey,
         opening_key: OpeningKey,
         prover: &Composer,
+        custom_gates: &[CustomGate],
+        hiding_degree: usize,
+        layout: QuotientLayout,
     ) -> Result<(Prover, Verifier), Error> {
         let mut perm = prover.perm.clone();
 
@@ -257,126 +430,164 @@ impl Compiler {
             s_sigma_4: s_sigma_4_poly,
         };
 
-        // The polynomial needs an evaluation domain of 4n.
-        // Plus, adding the blinding factors translates to
-        // the polynomial not fitting in 4n, so now we need
-        // 8n, the next power of 2
-        let domain_8n = EvaluationDomain::new(8 * domain.size())?;
+        // The quotient polynomial needs an evaluation domain of 4n for the
+        // fixed widgets. Plus, adding the blinding factors translates to the
+        // polynomial not fitting in 4n, so now we need 8n, the next power of
+        // 2. Custom gates of a higher degree need a larger coset.
+        let coset_domain =
+            EvaluationDomain::new(layout.coset_factor() * domain.size())?;
 
-        let q_m_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_m),
-            domain_8n,
+        let q_m_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_m),
+            coset_domain,
         );
-        let q_l_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_l),
-            domain_8n,
+        let q_l_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_l),
+            coset_domain,
         );
-        let q_r_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_r),
-            domain_8n,
+        let q_r_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_r),
+            coset_domain,
         );
-        let q_o_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_o),
-            domain_8n,
+        let q_o_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_o),
+            coset_domain,
         );
-        let q_c_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_c),
-            domain_8n,
+        let q_c_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_c),
+            coset_domain,
         );
-        let q_f_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_f),
-            domain_8n,
+        let q_f_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_f),
+            coset_domain,
         );
-        let q_arith_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_arith),
-            domain_8n,
+        let q_arith_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_arith),
+            coset_domain,
         );
-        let q_range_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_range),
-            domain_8n,
+        let q_range_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_range),
+            coset_domain,
         );
-        let q_logic_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_logic),
-            domain_8n,
+        let q_logic_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_logic),
+            coset_domain,
         );
-        let q_fixed_group_add_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_fixed_group_add),
-            domain_8n,
+        let q_fixed_group_add_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_fixed_group_add),
+            coset_domain,
         );
-        let q_variable_group_add_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.q_variable_group_add),
-            domain_8n,
+        let q_variable_group_add_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.q_variable_group_add),
+            coset_domain,
         );
 
-        let s_sigma_1_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.s_sigma_1),
-            domain_8n,
+        let s_sigma_1_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.s_sigma_1),
+            coset_domain,
         );
-        let s_sigma_2_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.s_sigma_2),
-            domain_8n,
+        let s_sigma_2_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.s_sigma_2),
+            coset_domain,
         );
-        let s_sigma_3_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.s_sigma_3),
-            domain_8n,
+        let s_sigma_3_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.s_sigma_3),
+            coset_domain,
         );
-        let s_sigma_4_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&selectors.s_sigma_4),
-            domain_8n,
+        let s_sigma_4_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&selectors.s_sigma_4),
+            coset_domain,
         );
 
-        let linear_eval_8n = Evaluations::from_vec_and_domain(
-            domain_8n.coset_fft(&[BlsScalar::zero(), BlsScalar::one()]),
-            domain_8n,
+        let linear_eval_coset = Evaluations::from_vec_and_domain(
+            coset_domain.coset_fft(&[BlsScalar::zero(), BlsScalar::one()]),
+            coset_domain,
         );
 
         let arithmetic_prover_key = widget::arithmetic::ProverKey {
-            q_m: (selectors.q_m, q_m_eval_8n),
-            q_l: (selectors.q_l.clone(), q_l_eval_8n.clone()),
-            q_r: (selectors.q_r.clone(), q_r_eval_8n.clone()),
-            q_o: (selectors.q_o, q_o_eval_8n),
-            q_f: (selectors.q_f, q_f_eval_8n),
-            q_c: (selectors.q_c.clone(), q_c_eval_8n.clone()),
-            q_arith: (selectors.q_arith, q_arith_eval_8n),
+            q_m: (selectors.q_m, q_m_eval_coset),
+            q_l: (selectors.q_l.clone(), q_l_eval_coset.clone()),
+            q_r: (selectors.q_r.clone(), q_r_eval_coset.clone()),
+            q_o: (selectors.q_o, q_o_eval_coset),
+            q_f: (selectors.q_f, q_f_eval_coset),
+            q_c: (selectors.q_c.clone(), q_c_eval_coset.clone()),
+            q_arith: (selectors.q_arith, q_arith_eval_coset),
         };
 
         let range_prover_key = widget::range::ProverKey {
-            q_range: (selectors.q_range, q_range_eval_8n),
+            q_range: (selectors.q_range, q_range_eval_coset),
         };
 
         let logic_prover_key = widget::logic::ProverKey {
-            q_c: (selectors.q_c.clone(), q_c_eval_8n.clone()),
-            q_logic: (selectors.q_logic, q_logic_eval_8n),
+            q_c: (selectors.q_c.clone(), q_c_eval_coset.clone()),
+            q_logic: (selectors.q_logic, q_logic_eval_coset),
         };
 
         let ecc_prover_key = widget::ecc::scalar_mul::fixed_base::ProverKey {
-            q_l: (selectors.q_l, q_l_eval_8n),
-            q_r: (selectors.q_r, q_r_eval_8n),
-            q_c: (selectors.q_c, q_c_eval_8n),
+            q_l: (selectors.q_l, q_l_eval_coset),
+            q_r: (selectors.q_r, q_r_eval_coset),
+            q_c: (selectors.q_c, q_c_eval_coset),
             q_fixed_group_add: (
                 selectors.q_fixed_group_add,
-                q_fixed_group_add_eval_8n,
+                q_fixed_group_add_eval_coset,
             ),
         };
 
         let permutation_prover_key = widget::permutation::ProverKey {
-            s_sigma_1: (selectors.s_sigma_1, s_sigma_1_eval_8n),
-            s_sigma_2: (selectors.s_sigma_2, s_sigma_2_eval_8n),
-            s_sigma_3: (selectors.s_sigma_3, s_sigma_3_eval_8n),
-            s_sigma_4: (selectors.s_sigma_4, s_sigma_4_eval_8n),
-            linear_evaluations: linear_eval_8n,
+            s_sigma_1: (selectors.s_sigma_1, s_sigma_1_eval_coset),
+            s_sigma_2: (selectors.s_sigma_2, s_sigma_2_eval_coset),
+            s_sigma_3: (selectors.s_sigma_3, s_sigma_3_eval_coset),
+            s_sigma_4: (selectors.s_sigma_4, s_sigma_4_eval_coset),
+            linear_evaluations: linear_eval_coset,
         };
 
         let curve_addition_prover_key =
             widget::ecc::curve_addition::ProverKey {
                 q_variable_group_add: (
                     selectors.q_variable_group_add,
-                    q_variable_group_add_eval_8n,
+                    q_variable_group_add_eval_coset,
                 ),
             };
 
-        let v_h_coset_8n =
-            domain_8n.compute_vanishing_poly_over_coset(domain.size() as u64);
+        // custom gates get a selector column each, enabled on the gates
+        // appended with `Composer::append_registered_gate`
+        let mut custom_prover_keys = Vec::with_capacity(custom_gates.len());
+        let mut custom_verifier_keys = Vec::with_capacity(custom_gates.len());
+
+        for (id, gate) in custom_gates.iter().enumerate() {
+            let mut q_custom = vec![BlsScalar::zero(); size];
+
+            prover
+                .custom_selectors
+                .iter()
+                .filter(|(custom_id, _)| *custom_id == id)
+                .for_each(|(_, i)| q_custom[*i] = BlsScalar::one());
+
+            let q_custom_poly = domain.ifft(&q_custom);
+            let q_custom_poly =
+                Polynomial::from_coefficients_vec(q_custom_poly);
+
+            let q_custom_comm =
+                commit_key.commit(&q_custom_poly).unwrap_or_default();
+
+            let q_custom_eval_coset = Evaluations::from_vec_and_domain(
+                coset_domain.coset_fft(&q_custom_poly),
+                coset_domain,
+            );
+
+            custom_prover_keys.push(widget::custom::ProverKey {
+                gate: *gate,
+                q_custom: (q_custom_poly, q_custom_eval_coset),
+            });
+
+            custom_verifier_keys.push(widget::custom::VerifierKey {
+                gate: *gate,
+                q_custom: q_custom_comm,
+            });
+        }
+
+        let v_h_coset = coset_domain
+            .compute_vanishing_poly_over_coset(domain.size() as u64);
 
         let prover_key = ProverKey {
             n: domain.size(),
@@ -386,7 +597,7 @@ impl Compiler {
             permutation: permutation_prover_key,
             variable_base: curve_addition_prover_key,
             fixed_base: ecc_prover_key,
-            v_h_coset_8n,
+            v_h_coset,
         };
 
         let public_input_indexes = prover.public_input_indexes();
@@ -398,8 +609,11 @@ impl Compiler {
             prover_key,
             commit_key,
             verifier_key,
+            (custom_prover_keys, &custom_verifier_keys),
             size,
             constraints,
+            hiding_degree,
+            layout,
         );
 
         let verifier = Verifier::new(
@@ -407,9 +621,11 @@ impl Compiler {
             verifier_key,
             opening_key,
             public_input_indexes,
+            custom_verifier_keys,
             size,
             constraints,
-        );
+            layout.chunks(),
+        )?;
 
         Ok((prover, verifier))
     }
@@ -0,0 +1,140 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+use merlin::Transcript;
+
+use crate::commitment_scheme::{OpeningKey, OpeningProof};
+use crate::error::Error;
+use crate::proof_system::Proof;
+use crate::transcript::{TranscriptBackend, TranscriptProtocol};
+
+use super::Verifier;
+
+/// Aggregator of generated proofs, possibly of different circuits, to verify
+/// all of them with a single pairing check.
+///
+/// Every proof is reduced to its two KZG openings, at the evaluation
+/// challenge and at its shift, and the openings of all the proofs are
+/// combined with the powers of a challenge derived from a transcript seeded
+/// with every one of them. The verifiers of the aggregated proofs must share
+/// the opening key, i.e. be compiled from the same public parameters.
+#[derive(Debug, Clone)]
+pub struct MultiProofAggregator {
+    opening_key: OpeningKey,
+    points: Vec<BlsScalar>,
+    openings: Vec<OpeningProof>,
+}
+
+impl MultiProofAggregator {
+    /// Create an empty aggregator for proofs checked with the opening key of
+    /// the given verifier
+    pub fn new(verifier: &Verifier) -> Self {
+        Self {
+            opening_key: verifier.opening_key().clone(),
+            points: Vec::new(),
+            openings: Vec::new(),
+        }
+    }
+
+    /// Number of aggregated proofs
+    pub fn len(&self) -> usize {
+        self.openings.len() / 2
+    }
+
+    /// Whether no proof was aggregated
+    pub fn is_empty(&self) -> bool {
+        self.openings.is_empty()
+    }
+
+    /// Reduce a generated proof to its openings and add them to the
+    /// aggregation.
+    ///
+    /// The proof is only checked by [`Self::verify`], but it is rejected
+    /// early if the public inputs don't match the verifier or if the verifier
+    /// doesn't share the opening key of the aggregator.
+    pub fn push(
+        &mut self,
+        verifier: &Verifier,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<(), Error> {
+        let opening_key = verifier.opening_key();
+        if opening_key.g != self.opening_key.g
+            || opening_key.h != self.opening_key.h
+            || opening_key.x_h != self.opening_key.x_h
+        {
+            return Err(Error::InconsistentOpeningKey);
+        }
+
+        for (point, opening) in
+            verifier.compute_openings(proof, public_inputs)?
+        {
+            self.points.push(point);
+            self.openings.push(opening);
+        }
+
+        Ok(())
+    }
+
+    /// Verify all the aggregated proofs at once.
+    ///
+    /// The aggregation is rejected if any of the proofs is invalid, without
+    /// indicating which one. An empty aggregation is valid.
+    pub fn verify(&self) -> Result<(), Error> {
+        if self.openings.is_empty() {
+            return Ok(());
+        }
+
+        self.opening_key
+            .batch_check(&self.points, &self.openings, &mut self.transcript())
+            .map_err(|_| Error::ProofVerificationError)
+    }
+
+    /// Verify all the aggregated proofs at once, like [`Self::verify`], but
+    /// identify the first invalid proof if the aggregation is rejected.
+    ///
+    /// If the aggregated check fails, the openings are checked one by one,
+    /// which is considerably slower, and the position of the first invalid
+    /// proof in the aggregation is returned with [`Error::InvalidBatchItem`].
+    pub fn verify_indexed(&self) -> Result<(), Error> {
+        if self.openings.is_empty() {
+            return Ok(());
+        }
+
+        self.opening_key
+            .batch_check_indexed(
+                &self.points,
+                &self.openings,
+                &mut self.transcript(),
+            )
+            .map_err(|err| match err {
+                // every proof is reduced to two openings
+                Error::InvalidBatchItem { index } => {
+                    Error::InvalidBatchItem { index: index / 2 }
+                }
+                _ => Error::ProofVerificationError,
+            })
+    }
+
+    /// Transcript seeded with all the aggregated openings
+    fn transcript(&self) -> Transcript {
+        // The challenge combining the openings has to depend on all of them
+        let mut transcript = Transcript::new(b"multi_proof_aggregation");
+        for (point, opening) in self.points.iter().zip(self.openings.iter()) {
+            transcript.append_scalar(b"point", point);
+            transcript.append_scalar(b"eval", &opening.evaluated_point);
+            transcript
+                .append_commitment(b"poly", &opening.commitment_to_polynomial);
+            transcript
+                .append_commitment(b"witness", &opening.commitment_to_witness);
+        }
+
+        transcript
+    }
+}
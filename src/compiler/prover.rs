@@ -4,6 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::ops;
 
@@ -13,16 +14,25 @@ use ff::Field;
 use merlin::Transcript;
 use rand_core::{CryptoRng, RngCore};
 
-use crate::commitment_scheme::CommitKey;
+use crate::commitment_scheme::{CommitKey, Commitment, PublicParameters};
 use crate::compiler::prover::linearization_poly::ProofEvaluations;
 use crate::error::Error;
 use crate::fft::{EvaluationDomain, Polynomial};
+use crate::proof_system::proof::{
+    alloc::compute_vanishing_evaluation, Proof, MAX_QUOTIENT_CHUNKS,
+    QUOTIENT_CHUNKS, QUOTIENT_LABELS,
+};
+use crate::proof_system::quotient_poly::QuotientLayout;
+use crate::proof_system::widget::custom;
 use crate::proof_system::{
-    linearization_poly, proof::Proof, quotient_poly, ProverKey, VerifierKey,
+    linearization_poly, quotient_poly, ProverKey, VerifierKey,
+};
+use crate::runtime::{Runtime, RuntimeEvent, RuntimeListener};
+use crate::transcript::{
+    ContextTranscript, TranscriptBackend, TranscriptProtocol,
 };
-use crate::transcript::TranscriptProtocol;
 
-use super::{Circuit, Composer};
+use super::{Circuit, Compiler, Composer};
 
 /// Turbo Prover with processed keys
 #[derive(Clone)]
@@ -31,9 +41,14 @@ pub struct Prover {
     pub(crate) prover_key: ProverKey,
     pub(crate) commit_key: CommitKey,
     pub(crate) verifier_key: VerifierKey,
+    pub(crate) custom_prover_keys: Vec<custom::ProverKey>,
+    custom_verifier_keys: Vec<custom::VerifierKey>,
     pub(crate) transcript: Transcript,
     pub(crate) size: usize,
     pub(crate) constraints: usize,
+    hiding_degree: usize,
+    quotient_layout: QuotientLayout,
+    listeners: Vec<Box<dyn RuntimeListener>>,
 }
 
 impl ops::Deref for Prover {
@@ -45,28 +60,96 @@ impl ops::Deref for Prover {
 }
 
 impl Prover {
+    /// Default hiding degree of the wire polynomials
+    pub const HIDING_DEGREE: usize = 1;
+
+    /// Tag of the hiding degree appended to the serialized prover, which
+    /// bytes from before its introduction lack
+    const HIDING_DEGREE_TAG: u16 = 1;
+
     pub(crate) fn new(
         label: Vec<u8>,
         prover_key: ProverKey,
         commit_key: CommitKey,
         verifier_key: VerifierKey,
+        (custom_prover_keys, custom_verifier_keys): (
+            Vec<custom::ProverKey>,
+            &[custom::VerifierKey],
+        ),
         size: usize,
         constraints: usize,
+        hiding_degree: usize,
+        quotient_layout: QuotientLayout,
     ) -> Self {
-        let transcript =
-            Transcript::base(label.as_slice(), &verifier_key, constraints);
+        let transcript = Transcript::base(
+            &label,
+            &verifier_key,
+            custom_verifier_keys,
+            constraints,
+        );
+
+        let hiding_degree = hiding_degree.max(Self::HIDING_DEGREE);
 
         Self {
             label,
             prover_key,
             commit_key,
             verifier_key,
+            custom_prover_keys,
+            custom_verifier_keys: custom_verifier_keys.to_vec(),
             transcript,
             size,
             constraints,
+            hiding_degree,
+            quotient_layout,
+            listeners: Vec::new(),
         }
     }
 
+    /// Register a listener to be notified of every [`RuntimeEvent`] emitted
+    /// while the circuit is built and checked by the prover.
+    ///
+    /// The listener is cloned into the composer of every proof. Listeners
+    /// aren't serialized with the prover.
+    pub fn add_listener<L>(&mut self, listener: L)
+    where
+        L: 'static + RuntimeListener + Clone,
+    {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Register a callback to be called on every [`RuntimeEvent`] emitted
+    /// while the circuit is built and checked by the prover.
+    ///
+    /// This is a shorthand for [`Self::add_listener`] with a function.
+    pub fn on_event(&mut self, callback: fn(&RuntimeEvent)) {
+        self.add_listener(callback);
+    }
+
+    /// Hiding degree of the wire polynomials, blinded with
+    /// `hiding_degree + 1` random scalars
+    pub const fn hiding_degree(&self) -> usize {
+        self.hiding_degree
+    }
+
+    /// Check that the wire polynomials of a circuit of `size` gates can be
+    /// blinded with `hiding_degree + 1` random scalars
+    pub(crate) fn check_hiding_degree(
+        hiding_degree: usize,
+        size: usize,
+    ) -> Result<(), Error> {
+        // a blinding scalar is subtracted from each of the lowest
+        // `hiding_degree + 1` coefficients of the wire polynomials
+        if hiding_degree >= size {
+            return Err(Error::HidingDegreeTooLarge {
+                hiding_degree,
+                max: size.saturating_sub(1),
+            });
+        }
+
+        Ok(())
+    }
+
     /// adds blinding scalars to a witness vector
     ///
     /// appends:
@@ -107,8 +190,13 @@ impl Prover {
         let commit_key_len = commit_key.len();
         let verifier_key_len = verifier_key.len();
 
-        let size =
-            48 + label_len + prover_key_len + commit_key_len + verifier_key_len;
+        let size = 48
+            + label_len
+            + prover_key_len
+            + commit_key_len
+            + verifier_key_len
+            + u16::SIZE
+            + u64::SIZE;
 
         (size, prover_key, commit_key, verifier_key)
     }
@@ -119,6 +207,9 @@ impl Prover {
     }
 
     /// Serialize the prover into bytes
+    ///
+    /// Custom gates are not serialized, so the prover of a circuit that
+    /// registers any can't be restored from its bytes.
     pub fn to_bytes(&self) -> Vec<u8> {
         let (size, prover_key, commit_key, verifier_key) =
             self.prepare_serialize();
@@ -130,6 +221,7 @@ impl Prover {
         let verifier_key_len = verifier_key.len() as u64;
         let size = self.size as u64;
         let constraints = self.constraints as u64;
+        let hiding_degree = self.hiding_degree as u64;
 
         bytes.extend(label_len.to_be_bytes());
         bytes.extend(prover_key_len.to_be_bytes());
@@ -143,11 +235,17 @@ impl Prover {
         bytes.extend(commit_key);
         bytes.extend(verifier_key);
 
+        bytes.extend(Self::HIDING_DEGREE_TAG.to_be_bytes());
+        bytes.extend(hiding_degree.to_be_bytes());
+
         bytes
     }
 
     /// Attempt to deserialize the prover from bytes generated via
     /// [`Self::to_bytes`]
+    ///
+    /// Bytes serialized before the hiding degree was configurable are
+    /// restored with the default [`Self::HIDING_DEGREE`].
     pub fn try_from_bytes<B>(bytes: B) -> Result<Self, Error>
     where
         B: AsRef<[u8]>,
@@ -186,9 +284,12 @@ impl Prover {
         let constraints = u64::from_be_bytes(constraints) as usize;
         bytes = &bytes[8..];
 
-        if bytes.len()
-            < label_len + prover_key_len + commit_key_len + verifier_key_len
-        {
+        let len = label_len
+            .checked_add(prover_key_len)
+            .and_then(|len| len.checked_add(commit_key_len))
+            .and_then(|len| len.checked_add(verifier_key_len))
+            .ok_or(Error::NotEnoughBytes)?;
+        if bytes.len() < len {
             return Err(Error::NotEnoughBytes);
         }
 
@@ -202,6 +303,30 @@ impl Prover {
         bytes = &bytes[commit_key_len..];
 
         let verifier_key = &bytes[..verifier_key_len];
+        bytes = &bytes[verifier_key_len..];
+
+        let hiding_degree = match bytes.len() {
+            0 => Self::HIDING_DEGREE,
+            _ if bytes.len() < u16::SIZE + u64::SIZE => {
+                return Err(Error::NotEnoughBytes)
+            }
+            _ => {
+                let tag =
+                    <[u8; 2]>::try_from(&bytes[..2]).expect("checked len");
+                let tag = u16::from_be_bytes(tag);
+                if tag != Self::HIDING_DEGREE_TAG {
+                    return Err(Error::IncompatibleKeyVersion {
+                        found: tag,
+                        expected: Self::HIDING_DEGREE_TAG,
+                    });
+                }
+
+                let hiding_degree =
+                    <[u8; 8]>::try_from(&bytes[2..10]).expect("checked len");
+                u64::from_be_bytes(hiding_degree) as usize
+            }
+        };
+        Self::check_hiding_degree(hiding_degree, size)?;
 
         let label = label.to_vec();
         let prover_key = ProverKey::from_slice(prover_key)?;
@@ -216,12 +341,27 @@ impl Prover {
             prover_key,
             commit_key,
             verifier_key,
+            (Vec::new(), &[]),
             size,
             constraints,
+            hiding_degree,
+            QuotientLayout::new(core::iter::empty()),
         ))
     }
 
     /// Prove the circuit
+    ///
+    /// The `rng` is the only source of randomness of the prover, used for the
+    /// blinding scalars, so proving the same circuit twice with identically
+    /// seeded generators produces the same proof. The scalars are drawn
+    /// outside of the parallel work of the `std` feature, so the proof
+    /// doesn't depend on the number of threads either.
+    ///
+    /// A proof can't be re-randomized after the fact: every challenge is
+    /// derived from the blinded wire commitments, so fresh blinding changes
+    /// the quotient, permutation and opening polynomials, which can only be
+    /// computed from the witnesses. Unlinkable proofs of the same statement
+    /// are obtained by proving it again with a fresh `rng`.
     pub fn prove<C, R>(
         &self,
         rng: &mut R,
@@ -231,14 +371,177 @@ impl Prover {
         C: Circuit,
         R: RngCore + CryptoRng,
     {
-        let prover = Composer::prove(self.constraints, circuit)?;
+        self.prove_with(rng, circuit, &mut self.transcript.clone(), None, false)
+    }
+
+    /// Prove the circuit, binding the proof to the domain separator `dst`.
+    ///
+    /// The separator is appended to the transcript before anything else, so
+    /// the proof is only accepted by [`Verifier::verify_domain_separated`]
+    /// with the same separator. This prevents proofs of applications sharing
+    /// the same circuit from being replayed across them.
+    ///
+    /// [`Verifier::verify_domain_separated`]:
+    /// crate::prelude::Verifier::verify_domain_separated
+    pub fn prove_domain_separated<C, R>(
+        &self,
+        rng: &mut R,
+        circuit: &C,
+        dst: &[u8],
+    ) -> Result<(Proof, Vec<BlsScalar>), Error>
+    where
+        C: Circuit,
+        R: RngCore + CryptoRng,
+    {
+        self.prove_with(
+            rng,
+            circuit,
+            &mut self.transcript.clone(),
+            Some(dst),
+            false,
+        )
+    }
+
+    /// Prove the circuit, deriving the challenges with the transcript
+    /// backend `T` instead of the default Merlin transcript.
+    ///
+    /// The proof is only accepted by [`Verifier::verify_with_transcript`]
+    /// with the same backend.
+    ///
+    /// [`Verifier::verify_with_transcript`]:
+    /// crate::prelude::Verifier::verify_with_transcript
+    pub fn prove_with_transcript<T, C, R>(
+        &self,
+        rng: &mut R,
+        circuit: &C,
+    ) -> Result<(Proof, Vec<BlsScalar>), Error>
+    where
+        T: TranscriptBackend,
+        C: Circuit,
+        R: RngCore + CryptoRng,
+    {
+        let mut transcript = T::base(
+            &self.label,
+            &self.verifier_key,
+            &self.custom_verifier_keys,
+            self.constraints,
+        );
+
+        self.prove_with(rng, circuit, &mut transcript, None, false)
+    }
+
+    /// Prove the circuit within a larger protocol, continuing the
+    /// `transcript` the caller seeded with the context of the protocol.
+    ///
+    /// The label of the circuit, its description, the public inputs and the
+    /// rounds of the proof are appended to the `transcript`, so the proof is
+    /// only accepted by [`Verifier::verify_in_context`] with a transcript
+    /// seeded in the same way. See [`ContextTranscript`] for the state it's
+    /// expected in.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if nothing was appended to the `transcript`.
+    ///
+    /// [`Verifier::verify_in_context`]:
+    /// crate::prelude::Verifier::verify_in_context
+    pub fn prove_in_context<C, R>(
+        &self,
+        rng: &mut R,
+        circuit: &C,
+        transcript: &mut ContextTranscript,
+    ) -> Result<(Proof, Vec<BlsScalar>), Error>
+    where
+        C: Circuit,
+        R: RngCore + CryptoRng,
+    {
+        debug_assert!(
+            transcript.is_seeded(),
+            "the transcript has to be seeded with the context of the protocol"
+        );
+
+        transcript.append_circuit_in_context(
+            &self.label,
+            &self.verifier_key,
+            &self.custom_verifier_keys,
+            self.constraints,
+        );
+
+        let (proof, public_inputs) =
+            self.prove_with(rng, circuit, transcript, None, false)?;
+
+        // the verifier draws the batching challenge of the pairing check,
+        // which the prover doesn't need, out of the last round of the proof,
+        // so do the same to leave the transcript in the same state
+        transcript
+            .opening_challenge(&proof.w_z_chall_comm, &proof.w_z_chall_w_comm);
+
+        Ok((proof, public_inputs))
+    }
+
+    /// Prove the circuit, checking first that every gate is satisfied by the
+    /// witnesses.
+    ///
+    /// An unsatisfied circuit is rejected with
+    /// [`Error::UnsatisfiedConstraint`] before computing any polynomial or
+    /// commitment, instead of producing a proof that fails to verify. See
+    /// [`Composer::check_satisfied`] for the identities that are checked.
+    pub fn prove_checked<C, R>(
+        &self,
+        rng: &mut R,
+        circuit: &C,
+    ) -> Result<(Proof, Vec<BlsScalar>), Error>
+    where
+        C: Circuit,
+        R: RngCore + CryptoRng,
+    {
+        self.prove_with(rng, circuit, &mut self.transcript.clone(), None, true)
+    }
+
+    fn prove_with<T, C, R>(
+        &self,
+        rng: &mut R,
+        circuit: &C,
+        transcript: &mut T,
+        dst: Option<&[u8]>,
+        checked: bool,
+    ) -> Result<(Proof, Vec<BlsScalar>), Error>
+    where
+        T: TranscriptProtocol,
+        C: Circuit,
+        R: RngCore + CryptoRng,
+    {
+        // fail before building the circuit if the commit key is too small
+        // for its polynomials
+        let needed = Compiler::commit_degree(
+            self.constraints,
+            &self.quotient_layout,
+            self.hiding_degree,
+        ) + PublicParameters::ADDED_BLINDING_DEGREE;
+        let available = self.commit_key.max_degree();
+        if needed > available {
+            return Err(Error::InsufficientSRS { needed, available });
+        }
+
+        let mut runtime = Runtime::new();
+        self.listeners
+            .iter()
+            .for_each(|listener| runtime.add_listener(listener.clone()));
+
+        let mut prover =
+            Composer::prove_with_runtime(self.constraints, circuit, runtime)?;
+        if checked {
+            prover.check_satisfied()?;
+        }
 
         let constraints = self.constraints;
         let size = self.size;
 
         let domain = EvaluationDomain::new(constraints)?;
 
-        let mut transcript = self.transcript.clone();
+        if let Some(dst) = dst {
+            transcript.application_domain_sep(dst);
+        }
 
         let public_inputs = prover.public_inputs();
         let public_input_indexes = prover.public_input_indexes();
@@ -270,17 +573,22 @@ impl Prover {
                 d_scalars[i] = prover[constraint.d];
             });
 
-        let a_poly = Self::blind_poly(rng, &a_scalars, 1, &domain);
-        let b_poly = Self::blind_poly(rng, &b_scalars, 1, &domain);
-        let c_poly = Self::blind_poly(rng, &c_scalars, 1, &domain);
-        let d_poly = Self::blind_poly(rng, &d_scalars, 1, &domain);
+        let a_poly =
+            Self::blind_poly(rng, &a_scalars, self.hiding_degree, &domain);
+        let b_poly =
+            Self::blind_poly(rng, &b_scalars, self.hiding_degree, &domain);
+        let c_poly =
+            Self::blind_poly(rng, &c_scalars, self.hiding_degree, &domain);
+        let d_poly =
+            Self::blind_poly(rng, &d_scalars, self.hiding_degree, &domain);
 
         // commit to wire polynomials
         // ([a(x)]_1, [b(x)]_1, [c(x)]_1, [d(x)]_1)
-        let a_comm = self.commit_key.commit(&a_poly)?;
-        let b_comm = self.commit_key.commit(&b_poly)?;
-        let c_comm = self.commit_key.commit(&c_poly)?;
-        let d_comm = self.commit_key.commit(&d_poly)?;
+        let wire_comms = self
+            .commit_key
+            .commit_all(&[&a_poly, &b_poly, &c_poly, &d_poly])?;
+        let (a_comm, b_comm, c_comm, d_comm) =
+            (wire_comms[0], wire_comms[1], wire_comms[2], wire_comms[3]);
 
         // Add wire polynomial commitments to transcript
         transcript.append_commitment(b"a_comm", &a_comm);
@@ -325,6 +633,12 @@ impl Prover {
             transcript.challenge_scalar(b"fixed base separation challenge");
         let var_base_sep_challenge =
             transcript.challenge_scalar(b"variable base separation challenge");
+        let custom_sep_challenge = match self.custom_prover_keys.is_empty() {
+            true => BlsScalar::zero(),
+            false => {
+                transcript.challenge_scalar(b"custom separation challenge")
+            }
+        };
 
         // compute public inputs polynomial
         let pi_poly = domain.ifft(&dense_public_inputs);
@@ -341,63 +655,65 @@ impl Prover {
             fixed_base_sep_challenge,
             var_base_sep_challenge,
         );
+        let layout = &self.quotient_layout;
         let t_poly = quotient_poly::compute(
             &domain,
+            layout,
             &self.prover_key,
             &z_poly,
             wires,
             &pi_poly,
+            (&self.custom_prover_keys, &custom_sep_challenge),
             args,
         )?;
 
-        // split quotient polynomial into 4 degree `n` polynomials
-        let domain_size = domain.size();
-
-        let mut t_low_vec = t_poly[0..domain_size].to_vec();
-        let mut t_mid_vec = t_poly[domain_size..2 * domain_size].to_vec();
-        let mut t_high_vec = t_poly[2 * domain_size..3 * domain_size].to_vec();
-        let mut t_fourth_vec = t_poly[3 * domain_size..].to_vec();
-
-        // select 3 blinding factors for the quotient splitted polynomials
-        let b_12 = BlsScalar::random(&mut *rng);
-        let b_13 = BlsScalar::random(&mut *rng);
-        let b_14 = BlsScalar::random(&mut *rng);
-
-        // t_low'(X) + b_12*X^n
-        t_low_vec.push(b_12);
-
-        // t_mid'(X) - b_12 + b_13*X^n
-        t_mid_vec[0] -= b_12;
-        t_mid_vec.push(b_13);
-
-        // t_high'(X) - b_13 + b_14*X^n
-        t_high_vec[0] -= b_13;
-        t_high_vec.push(b_14);
-
-        // t_fourth'(X) - b_14
-        t_fourth_vec[0] -= b_14;
+        // split quotient polynomial into polynomials of the chunk degree, a
+        // multiple of `n`
+        let chunk_size = layout.chunk_factor() * domain.size();
+
+        let chunks = layout.chunks();
+        let mut t_vecs: Vec<Vec<BlsScalar>> = (0..chunks)
+            .map(|i| {
+                let end = match i + 1 == chunks {
+                    true => t_poly.len(),
+                    false => (i + 1) * chunk_size,
+                };
+                t_poly[i * chunk_size..end].to_vec()
+            })
+            .collect();
+
+        // select the blinding factors for the quotient splitted polynomials
+        let blinders: Vec<BlsScalar> =
+            (1..chunks).map(|_| BlsScalar::random(&mut *rng)).collect();
+
+        // t_i'(X) - b_(i-1) + b_i*X^m, for the chunk size m
+        for (i, b) in blinders.iter().enumerate() {
+            t_vecs[i].push(*b);
+            t_vecs[i + 1][0] -= b;
+        }
 
-        let t_low_poly = Polynomial::from_coefficients_vec(t_low_vec);
-        let t_mid_poly = Polynomial::from_coefficients_vec(t_mid_vec);
-        let t_high_poly = Polynomial::from_coefficients_vec(t_high_vec);
-        let t_fourth_poly = Polynomial::from_coefficients_vec(t_fourth_vec);
+        let t_polys: Vec<Polynomial> = t_vecs
+            .into_iter()
+            .map(Polynomial::from_coefficients_vec)
+            .collect();
 
         // commit to split quotient polynomial
-        let t_low_comm = self.commit_key.commit(&t_low_poly)?;
-        let t_mid_comm = self.commit_key.commit(&t_mid_poly)?;
-        let t_high_comm = self.commit_key.commit(&t_high_poly)?;
-        let t_fourth_comm = self.commit_key.commit(&t_fourth_poly)?;
+        let t_comms = self
+            .commit_key
+            .commit_all(&t_polys.iter().collect::<Vec<_>>())?;
 
         // add quotient polynomial commitments to transcript
-        transcript.append_commitment(b"t_low_comm", &t_low_comm);
-        transcript.append_commitment(b"t_mid_comm", &t_mid_comm);
-        transcript.append_commitment(b"t_high_comm", &t_high_comm);
-        transcript.append_commitment(b"t_fourth_comm", &t_fourth_comm);
+        for (label, t_comm) in QUOTIENT_LABELS.iter().zip(t_comms.iter()) {
+            transcript.append_commitment(label.as_bytes(), t_comm);
+        }
 
         // round 4
         // compute evaluation challenge 'z'
         let z_challenge = transcript.challenge_scalar(b"z_challenge");
 
+        // the verifier rejects a challenge in the domain
+        compute_vanishing_evaluation(&domain, &z_challenge)?;
+
         // compute opening evaluations
         let a_eval = a_poly.evaluate(&z_challenge);
         let b_eval = b_poly.evaluate(&z_challenge);
@@ -498,11 +814,10 @@ impl Prover {
             &z_poly,
             &evaluations,
             &domain,
-            &t_low_poly,
-            &t_mid_poly,
-            &t_high_poly,
-            &t_fourth_poly,
+            &self.quotient_layout,
+            &t_polys,
             &public_inputs,
+            (&self.custom_prover_keys, &custom_sep_challenge),
         );
 
         // compute the opening proof polynomial 'W_z(X)'
@@ -520,7 +835,6 @@ impl Prover {
             &z_challenge,
             &v_challenge,
         );
-        let w_z_chall_comm = self.commit_key.commit(&aggregate_witness)?;
 
         // compute the shifted challenge 'v_w'
         let v_w_challenge = transcript.challenge_scalar(b"v_w_challenge");
@@ -531,8 +845,22 @@ impl Prover {
             &(z_challenge * domain.group_gen),
             &v_w_challenge,
         );
-        let w_z_chall_w_comm =
-            self.commit_key.commit(&shifted_aggregate_witness)?;
+
+        // commit to both opening proof polynomials
+        let witness_comms = self
+            .commit_key
+            .commit_all(&[&aggregate_witness, &shifted_aggregate_witness])?;
+        let (w_z_chall_comm, w_z_chall_w_comm) =
+            (witness_comms[0], witness_comms[1]);
+
+        // the chunks past the fourth are left as the identity if the
+        // quotient isn't split into more
+        let mut t_extra_comms =
+            <[Commitment; MAX_QUOTIENT_CHUNKS - QUOTIENT_CHUNKS]>::default();
+        t_extra_comms
+            .iter_mut()
+            .zip(&t_comms[QUOTIENT_CHUNKS..])
+            .for_each(|(extra, t_comm)| *extra = *t_comm);
 
         let proof = Proof {
             a_comm,
@@ -542,10 +870,12 @@ impl Prover {
 
             z_comm,
 
-            t_low_comm,
-            t_mid_comm,
-            t_high_comm,
-            t_fourth_comm,
+            t_low_comm: t_comms[0],
+            t_mid_comm: t_comms[1],
+            t_high_comm: t_comms[2],
+            t_fourth_comm: t_comms[3],
+            t_extra_comms,
+            extra_chunks: (t_comms.len() - QUOTIENT_CHUNKS) as u8,
 
             w_z_chall_comm,
             w_z_chall_w_comm,
@@ -5,22 +5,38 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use alloc::vec::Vec;
+use core::fmt;
+use core::num::NonZeroUsize;
 
 use dusk_bls12_381::BlsScalar;
+#[cfg(all(feature = "std", feature = "serde"))]
+use dusk_bls12_381::G1Affine;
 use dusk_bytes::{DeserializableSlice, Serializable};
 use merlin::Transcript;
 
-use crate::commitment_scheme::OpeningKey;
+use crate::commitment_scheme::{OpeningKey, OpeningProof};
 use crate::error::Error;
-use crate::proof_system::{Proof, VerifierKey};
-use crate::transcript::TranscriptProtocol;
+use crate::fft::EvaluationDomain;
+use crate::proof_system::proof::{MAX_QUOTIENT_CHUNKS, QUOTIENT_CHUNKS};
+#[cfg(all(feature = "std", feature = "serde"))]
+use crate::proof_system::trace::VerifyTrace;
+use crate::proof_system::widget::custom;
+use crate::proof_system::{
+    Challenges, ConstantPublicInputs, CustomGate, LinearizationContext, Proof,
+    PublicInputs, VerificationReport, VerifierKey,
+};
+use crate::transcript::{
+    ContextTranscript, ResumableTranscript, TranscriptBackend,
+    TranscriptProtocol,
+};
 
-use super::Composer;
+use super::VerifierKeyRef;
 
 /// Verify proofs of a given circuit
 pub struct Verifier {
     label: Vec<u8>,
     verifier_key: VerifierKey,
+    linearization: LinearizationContext,
     opening_key: OpeningKey,
     public_input_indexes: Vec<usize>,
     transcript: Transcript,
@@ -29,26 +45,53 @@ pub struct Verifier {
 }
 
 impl Verifier {
+    /// Magic prefix of the versioned serialization of the verifier
+    pub const MAGIC: [u8; 4] = *b"PLNK";
+
+    /// Version of the layout of the verifier serialization, increased on
+    /// every incompatible change of the verifier key
+    pub const VERSION: u16 = 1;
+
+    /// Size in bytes of the header of the versioned serialization
+    pub const HEADER_SIZE: usize = Self::MAGIC.len() + u16::SIZE;
+
     pub(crate) fn new(
         label: Vec<u8>,
         verifier_key: VerifierKey,
         opening_key: OpeningKey,
         public_input_indexes: Vec<usize>,
+        custom_gates: Vec<custom::VerifierKey>,
         size: usize,
         constraints: usize,
-    ) -> Self {
+        quotient_chunks: usize,
+    ) -> Result<Self, Error> {
+        // the public inputs are expanded over the domain when verifying
+        if let Some(index) = public_input_indexes.iter().find(|i| **i >= size) {
+            return Err(Error::PublicInputOutOfDomain {
+                index: *index,
+                size,
+            });
+        }
+
         let transcript =
-            Transcript::base(label.as_slice(), &verifier_key, constraints);
+            Transcript::base(&label, &verifier_key, &custom_gates, constraints);
+
+        let linearization = LinearizationContext::new(
+            &verifier_key,
+            custom_gates,
+            quotient_chunks,
+        )?;
 
-        Self {
+        Ok(Self {
             label,
             verifier_key,
+            linearization,
             opening_key,
             public_input_indexes,
             transcript,
             size,
             constraints,
-        }
+        })
     }
 
     fn prepare_serialize(
@@ -61,12 +104,14 @@ impl Verifier {
         let verifier_key_len = verifier_key.len();
         let opening_key_len = opening_key.len();
         let public_input_indexes_len = self.public_input_indexes.len() * 8;
+        let custom_gates_len = self.custom_gates_serialized_size();
 
         let size = 48
             + label_len
             + verifier_key_len
             + opening_key_len
-            + public_input_indexes_len;
+            + public_input_indexes_len
+            + custom_gates_len;
 
         (size, verifier_key, opening_key)
     }
@@ -76,7 +121,81 @@ impl Verifier {
         self.prepare_serialize().0
     }
 
+    /// Serialized size in bytes of the custom gates and the number of
+    /// quotient chunks, appended after the public inputs
+    fn custom_gates_serialized_size(&self) -> usize {
+        2 * u64::SIZE
+            + self.linearization.custom_gates().len()
+                * custom::VerifierKey::SIZE
+    }
+
+    /// Append the number of custom gates, their verifier keys and the number
+    /// of quotient chunks to `bytes`
+    fn custom_gates_to_bytes(&self, bytes: &mut Vec<u8>) {
+        let custom_gates = self.linearization.custom_gates();
+        let chunks = self.linearization.quotient_layout().chunks() as u64;
+
+        bytes.extend((custom_gates.len() as u64).to_be_bytes());
+        custom_gates
+            .iter()
+            .for_each(|gate| bytes.extend(gate.to_bytes()));
+        bytes.extend(chunks.to_be_bytes());
+    }
+
+    /// Split the verifier keys of the custom gates and the number of
+    /// quotient chunks off the `bytes` remaining after the public inputs of
+    /// a serialized verifier.
+    ///
+    /// Verifiers serialized before custom gates were supported end with
+    /// their public inputs, and have no custom gates. Verifiers serialized
+    /// before the number of quotient chunks was configurable end with their
+    /// custom gates, and split the quotient into four chunks.
+    pub(crate) fn custom_gates_from_bytes(
+        bytes: &[u8],
+    ) -> Result<(&[u8], usize), Error> {
+        if bytes.is_empty() {
+            return Ok((bytes, QUOTIENT_CHUNKS));
+        }
+        if bytes.len() < u64::SIZE {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let len = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let len = usize::try_from(u64::from_be_bytes(len))
+            .ok()
+            .and_then(|len| len.checked_mul(custom::VerifierKey::SIZE))
+            .ok_or(Error::NotEnoughBytes)?;
+
+        let bytes = &bytes[8..];
+        if bytes.len() < len {
+            return Err(Error::NotEnoughBytes);
+        }
+        let (custom_gates, bytes) = bytes.split_at(len);
+
+        let chunks = match bytes.len() {
+            0 => QUOTIENT_CHUNKS,
+            _ if bytes.len() < u64::SIZE => return Err(Error::NotEnoughBytes),
+            _ => {
+                let chunks =
+                    <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+                usize::try_from(u64::from_be_bytes(chunks)).map_err(|_| {
+                    Error::UnsupportedQuotientChunks {
+                        chunks: usize::MAX,
+                        max: MAX_QUOTIENT_CHUNKS,
+                    }
+                })?
+            }
+        };
+
+        Ok((custom_gates, chunks))
+    }
+
     /// Serialize the verifier into bytes
+    ///
+    /// The identities of the custom gates are functions of the circuit, so
+    /// only their degrees and selector commitments are serialized. The
+    /// verifier of a circuit that registers any is restored with
+    /// [`Self::try_from_bytes_with_custom_gates`].
     pub fn to_bytes(&self) -> Vec<u8> {
         let (size, verifier_key, opening_key) = self.prepare_serialize();
         let mut bytes = Vec::with_capacity(size);
@@ -105,33 +224,173 @@ impl Verifier {
             .map(u64::to_be_bytes)
             .for_each(|i| bytes.extend(i));
 
+        self.custom_gates_to_bytes(&mut bytes);
+
         bytes
     }
 
     /// Attempt to deserialize the prover from bytes generated via
     /// [`Self::to_bytes`]
+    ///
+    /// The verifier of a circuit that registers custom gates is rejected with
+    /// [`Error::UnregisteredCustomGate`], and has to be restored with
+    /// [`Self::try_from_bytes_with_custom_gates`].
     pub fn try_from_bytes<B>(bytes: B) -> Result<Self, Error>
     where
         B: AsRef<[u8]>,
     {
-        let mut bytes = bytes.as_ref();
+        Self::try_from_bytes_with_custom_gates(bytes, &[])
+    }
+
+    /// Attempt to deserialize the verifier of a circuit registering the
+    /// `custom_gates`, such as [`Circuit::CUSTOM_GATES`], from bytes
+    /// generated via [`Self::to_bytes`]
+    ///
+    /// The custom gates have to match the ones serialized with the verifier,
+    /// in number and degree.
+    ///
+    /// [`Circuit::CUSTOM_GATES`]: crate::prelude::Circuit::CUSTOM_GATES
+    pub fn try_from_bytes_with_custom_gates<B>(
+        bytes: B,
+        custom_gates: &[CustomGate],
+    ) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        VerifierKeyRef::try_from_bytes(bytes.as_ref())?
+            .to_verifier_with_custom_gates(custom_gates)
+    }
+
+    /// Serialize the verifier into bytes, prefixed with [`Self::MAGIC`] and
+    /// [`Self::VERSION`] so keys of an incompatible layout are detected when
+    /// they are loaded with [`Self::try_from_versioned_bytes`].
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(Self::HEADER_SIZE + self.serialized_size());
+
+        bytes.extend(Self::MAGIC);
+        bytes.extend(Self::VERSION.to_be_bytes());
+        bytes.extend(self.to_bytes());
+
+        bytes
+    }
 
-        if bytes.len() < 48 {
+    /// Attempt to deserialize the verifier from bytes generated via
+    /// [`Self::to_versioned_bytes`], checking their header first
+    pub fn try_from_versioned_bytes<B>(bytes: B) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        Self::try_from_versioned_bytes_with_custom_gates(bytes, &[])
+    }
+
+    /// Attempt to deserialize the verifier of a circuit registering the
+    /// `custom_gates` from bytes generated via [`Self::to_versioned_bytes`],
+    /// checking their header first
+    pub fn try_from_versioned_bytes_with_custom_gates<B>(
+        bytes: B,
+        custom_gates: &[CustomGate],
+    ) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        let bytes = bytes.as_ref();
+
+        if bytes.len() < Self::HEADER_SIZE {
             return Err(Error::NotEnoughBytes);
         }
 
-        let label_len = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let label_len = u64::from_be_bytes(label_len) as usize;
-        bytes = &bytes[8..];
+        if bytes[..Self::MAGIC.len()] != Self::MAGIC {
+            return Err(Error::InvalidKeyHeader);
+        }
+
+        let found =
+            <[u8; 2]>::try_from(&bytes[Self::MAGIC.len()..Self::HEADER_SIZE])
+                .expect("checked len");
+        let found = u16::from_be_bytes(found);
+        if found != Self::VERSION {
+            return Err(Error::IncompatibleKeyVersion {
+                found,
+                expected: Self::VERSION,
+            });
+        }
+
+        Self::try_from_bytes_with_custom_gates(
+            &bytes[Self::HEADER_SIZE..],
+            custom_gates,
+        )
+    }
+
+    /// Serialize the verifier into a compact representation, suited for
+    /// clients that only verify proofs.
+    ///
+    /// Unlike [`Self::to_bytes`], only the distinct commitments of the
+    /// verifier key are stored, and the sizes that can be derived from the
+    /// number of constraints are omitted. As with [`Self::to_bytes`], only
+    /// the degrees and selector commitments of the custom gates are stored.
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let size = 24
+            + self.label.len()
+            + VerifierKey::COMPACT_SIZE
+            + OpeningKey::SIZE
+            + self.public_input_indexes.len() * 8
+            + self.custom_gates_serialized_size();
+        let mut bytes = Vec::with_capacity(size);
+
+        let constraints = self.constraints as u64;
+        let label_len = self.label.len() as u64;
+        let public_input_indexes_len = self.public_input_indexes.len() as u64;
+
+        bytes.extend(constraints.to_be_bytes());
+        bytes.extend(label_len.to_be_bytes());
+        bytes.extend(public_input_indexes_len.to_be_bytes());
+
+        bytes.extend(self.label.as_slice());
+        bytes.extend(self.verifier_key.to_compact_bytes());
+        bytes.extend(self.opening_key.to_bytes());
+
+        self.public_input_indexes
+            .iter()
+            .map(|i| *i as u64)
+            .map(u64::to_be_bytes)
+            .for_each(|i| bytes.extend(i));
 
-        let verifier_key_len =
+        self.custom_gates_to_bytes(&mut bytes);
+
+        bytes
+    }
+
+    /// Attempt to deserialize the verifier from bytes generated via
+    /// [`Self::to_compact_bytes`]
+    pub fn try_from_compact_bytes<B>(bytes: B) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        Self::try_from_compact_bytes_with_custom_gates(bytes, &[])
+    }
+
+    /// Attempt to deserialize the verifier of a circuit registering the
+    /// `custom_gates` from bytes generated via [`Self::to_compact_bytes`]
+    pub fn try_from_compact_bytes_with_custom_gates<B>(
+        bytes: B,
+        custom_gates: &[CustomGate],
+    ) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        let mut bytes = bytes.as_ref();
+
+        if bytes.len() < 24 {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let constraints =
             <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let verifier_key_len = u64::from_be_bytes(verifier_key_len) as usize;
+        let constraints = u64::from_be_bytes(constraints) as usize;
         bytes = &bytes[8..];
 
-        let opening_key_len =
-            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let opening_key_len = u64::from_be_bytes(opening_key_len) as usize;
+        let label_len = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let label_len = u64::from_be_bytes(label_len) as usize;
         bytes = &bytes[8..];
 
         let public_input_indexes_len =
@@ -140,37 +399,47 @@ impl Verifier {
             u64::from_be_bytes(public_input_indexes_len) as usize;
         bytes = &bytes[8..];
 
-        let size = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let size = u64::from_be_bytes(size) as usize;
-        bytes = &bytes[8..];
-
-        let constraints =
-            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let constraints = u64::from_be_bytes(constraints) as usize;
-        bytes = &bytes[8..];
-
-        if bytes.len()
-            < label_len
-                + verifier_key_len
-                + opening_key_len
-                + public_input_indexes_len * 8
-        {
+        let public_input_indexes_len = public_input_indexes_len
+            .checked_mul(8)
+            .ok_or(Error::NotEnoughBytes)?;
+        let len = label_len
+            .checked_add(VerifierKey::COMPACT_SIZE + OpeningKey::SIZE)
+            .and_then(|len| len.checked_add(public_input_indexes_len))
+            .ok_or(Error::NotEnoughBytes)?;
+        if bytes.len() < len {
             return Err(Error::NotEnoughBytes);
         }
 
+        let size = constraints.checked_next_power_of_two().ok_or(
+            Error::CircuitTooLarge {
+                requested: constraints,
+                max_supported: EvaluationDomain::MAX_SIZE,
+            },
+        )?;
+
         let label = &bytes[..label_len];
         bytes = &bytes[label_len..];
 
-        let verifier_key = &bytes[..verifier_key_len];
-        bytes = &bytes[verifier_key_len..];
+        let verifier_key = <[u8; VerifierKey::COMPACT_SIZE]>::try_from(
+            &bytes[..VerifierKey::COMPACT_SIZE],
+        )
+        .expect("checked len");
+        bytes = &bytes[VerifierKey::COMPACT_SIZE..];
 
-        let opening_key = &bytes[..opening_key_len];
-        bytes = &bytes[opening_key_len..];
+        let opening_key = &bytes[..OpeningKey::SIZE];
+        bytes = &bytes[OpeningKey::SIZE..];
 
-        let public_input_indexes = &bytes[..public_input_indexes_len * 8];
+        let public_input_indexes = &bytes[..public_input_indexes_len];
+        bytes = &bytes[public_input_indexes_len..];
+
+        let (custom_gates_bytes, quotient_chunks) =
+            Self::custom_gates_from_bytes(bytes)?;
+        let custom_gates =
+            custom::verifier_keys_from_bytes(custom_gates_bytes, custom_gates)?;
 
         let label = label.to_vec();
-        let verifier_key = VerifierKey::from_slice(verifier_key)?;
+        let verifier_key =
+            VerifierKey::from_compact_bytes(constraints, &verifier_key)?;
         let opening_key = OpeningKey::from_slice(opening_key)?;
         let public_input_indexes = public_input_indexes
             .chunks_exact(8)
@@ -179,21 +448,56 @@ impl Verifier {
             .map(|n| n as usize)
             .collect();
 
-        Ok(Self::new(
+        Self::new(
             label,
             verifier_key,
             opening_key,
             public_input_indexes,
+            custom_gates,
             size,
             constraints,
-        ))
+            quotient_chunks,
+        )
+    }
+
+    /// Bound the number of commitments of the verifier key combined at once
+    /// when computing the linearization commitment of a proof.
+    ///
+    /// The commitment is accumulated over multi-scalar multiplications of at
+    /// most `batch` points, trading some speed for a lower peak memory on
+    /// constrained verifiers of circuits with many custom gates. Only the
+    /// scratch memory of the multi-scalar multiplications is bounded: the
+    /// commitments themselves are held by the verifier along with its key.
+    /// By default, a single multi-scalar multiplication is performed. The
+    /// batch size isn't serialized with the verifier.
+    pub fn with_msm_batch_size(mut self, batch: NonZeroUsize) -> Self {
+        self.linearization.set_msm_batch(Some(batch));
+        self
     }
 
-    /// Verify a generated proof
+    /// Verify a generated proof.
+    ///
+    /// The challenges are derived from a fresh transcript labelled with the
+    /// label of the circuit, to which the circuit description and the public
+    /// inputs are appended before the rounds of the proof. To verify a proof
+    /// within a larger protocol, use [`Self::verify_in_context`] instead.
     pub fn verify(
         &self,
         proof: &Proof,
         public_inputs: &[BlsScalar],
+    ) -> Result<(), Error> {
+        self.verify_with_report(proof, public_inputs).result()
+    }
+
+    /// Verify a generated proof with the public inputs placed at the
+    /// positions of their gates.
+    ///
+    /// Every public input declared by the circuit has to be provided, and no
+    /// other.
+    pub fn verify_public_inputs(
+        &self,
+        proof: &Proof,
+        public_inputs: &PublicInputs,
     ) -> Result<(), Error> {
         if public_inputs.len() != self.public_input_indexes.len() {
             return Err(Error::InconsistentPublicInputsLen {
@@ -202,23 +506,552 @@ impl Verifier {
             });
         }
 
-        let mut transcript = self.transcript.clone();
+        if let Some(index) = self
+            .public_input_indexes
+            .iter()
+            .find(|index| public_inputs.get(**index).is_none())
+        {
+            return Err(Error::PublicInputNotFound { index: *index });
+        }
+
+        let mut transcript = self.seed_public_inputs(public_inputs, None);
 
-        public_inputs
+        proof
+            .verify_with_report(
+                &self.verifier_key,
+                &self.linearization,
+                &mut transcript,
+                &self.opening_key,
+                public_inputs,
+                None,
+            )
+            .result()
+    }
+
+    /// Size of the evaluation domain of the circuit
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of constraints of the circuit, before padding to the domain
+    pub const fn constraints(&self) -> usize {
+        self.constraints
+    }
+
+    /// Number of public inputs expected by the circuit
+    pub fn public_inputs_len(&self) -> usize {
+        self.public_input_indexes.len()
+    }
+
+    /// Number of custom gates registered by the circuit
+    pub fn custom_gates_len(&self) -> usize {
+        self.linearization.custom_gates().len()
+    }
+
+    /// Gate indexes of the public inputs declared by the circuit, in
+    /// ascending order
+    pub fn public_input_indexes(&self) -> &[usize] {
+        &self.public_input_indexes
+    }
+
+    /// Place the public inputs, in the order returned by the prover, at the
+    /// positions of their gates
+    pub fn public_inputs(
+        &self,
+        public_inputs: &[BlsScalar],
+    ) -> Result<PublicInputs, Error> {
+        if public_inputs.len() != self.public_input_indexes.len() {
+            return Err(Error::InconsistentPublicInputsLen {
+                expected: self.public_input_indexes.len(),
+                provided: public_inputs.len(),
+            });
+        }
+
+        Ok(self
+            .public_input_indexes
             .iter()
-            .for_each(|pi| transcript.append_scalar(b"pi", pi));
+            .copied()
+            .zip(public_inputs.iter().copied())
+            .collect())
+    }
 
-        let dense_public_inputs = Composer::dense_public_inputs(
-            &self.public_input_indexes,
-            public_inputs,
-            self.size,
+    /// Precompute the evaluation of the public inputs whose values are fixed
+    /// by the circuit definition, to verify proofs with
+    /// [`Verifier::verify_with_constants`].
+    ///
+    /// Every constant has to be placed at the gate of a public input
+    /// declared by the circuit. The constants are bound to the
+    /// [`Verifier::circuit_hash`], so they're only accepted by the verifiers
+    /// of the same circuit.
+    pub fn constant_public_inputs(
+        &self,
+        constants: PublicInputs,
+    ) -> Result<ConstantPublicInputs, Error> {
+        let mut declared = self.public_input_indexes.clone();
+        declared.sort_unstable();
+
+        if let Some(index) = constants
+            .indexes()
+            .find(|index| declared.binary_search(index).is_err())
+        {
+            return Err(Error::UndeclaredPublicInput { index });
+        }
+
+        Ok(ConstantPublicInputs::new(
+            constants,
+            self.linearization.domain(),
+            self.circuit_hash(),
+        ))
+    }
+
+    /// Verify a generated proof, given the public inputs fixed by the circuit
+    /// apart from the ones that vary across proofs.
+    ///
+    /// The public inputs that vary are given in ascending order of their
+    /// gate index, skipping the positions of the constants. The constants
+    /// are evaluated with their precomputed roots, while the proof is
+    /// verified for the whole set of public inputs, exactly as
+    /// [`Verifier::verify`] does.
+    pub fn verify_with_constants(
+        &self,
+        proof: &Proof,
+        constants: &ConstantPublicInputs,
+        public_inputs: &[BlsScalar],
+    ) -> Result<(), Error> {
+        // the positions of the constants were checked against the circuit
+        // they're bound to
+        if constants.circuit_hash() != &self.circuit_hash() {
+            return Err(Error::InconsistentConstantPublicInputs);
+        }
+
+        let variable_indexes: Vec<usize> = self
+            .public_input_indexes
+            .iter()
+            .copied()
+            .filter(|index| constants.inputs().get(*index).is_none())
+            .collect();
+
+        if public_inputs.len() != variable_indexes.len() {
+            return Err(Error::InconsistentPublicInputsLen {
+                expected: variable_indexes.len(),
+                provided: public_inputs.len(),
+            });
+        }
+
+        let variable: PublicInputs = variable_indexes
+            .into_iter()
+            .zip(public_inputs.iter().copied())
+            .collect();
+
+        // the transcript binds every public input, in order of their gates
+        let mut all = variable.clone();
+        constants.inputs().iter().for_each(|(index, value)| {
+            all.insert(index, *value);
+        });
+        let mut transcript = self.seed_public_inputs(&all, None);
+
+        proof
+            .verify_with_report(
+                &self.verifier_key,
+                &self.linearization,
+                &mut transcript,
+                &self.opening_key,
+                &variable,
+                Some(constants),
+            )
+            .result()
+    }
+
+    /// Verify a proof generated with [`Prover::prove_domain_separated`] for
+    /// the domain separator `dst`.
+    ///
+    /// [`Prover::prove_domain_separated`]:
+    /// crate::prelude::Prover::prove_domain_separated
+    pub fn verify_domain_separated(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+        dst: &[u8],
+    ) -> Result<(), Error> {
+        let (mut transcript, public_inputs) =
+            self.prepare_public_inputs(public_inputs, Some(dst))?;
+
+        proof
+            .verify_with_report(
+                &self.verifier_key,
+                &self.linearization,
+                &mut transcript,
+                &self.opening_key,
+                &public_inputs,
+                None,
+            )
+            .result()
+    }
+
+    /// Verify a generated proof, returning a [`VerificationReport`] with the
+    /// challenges and evaluations computed by the verifier and the sub-check
+    /// that failed, if any.
+    ///
+    /// Since the verifier is linearized, the quotient polynomial identity is
+    /// not checked on its own: a proof that doesn't satisfy it is reported as
+    /// a [`VerificationFailure::Pairing`].
+    ///
+    /// [`VerificationFailure::Pairing`]:
+    /// crate::prelude::VerificationFailure::Pairing
+    pub fn verify_with_report(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> VerificationReport {
+        let (mut transcript, public_inputs) =
+            match self.prepare_public_inputs(public_inputs, None) {
+                Ok(prepared) => prepared,
+                Err(err) => return VerificationReport::setup_failure(err),
+            };
+
+        proof.verify_with_report(
+            &self.verifier_key,
+            &self.linearization,
+            &mut transcript,
+            &self.opening_key,
+            &public_inputs,
+            None,
+        )
+    }
+
+    /// Derive the Fiat-Shamir challenges of a proof for the given public
+    /// inputs, exactly as [`Verifier::verify`] does, without verifying the
+    /// proof.
+    pub fn derive_challenges(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<Challenges, Error> {
+        let (mut transcript, _) =
+            self.prepare_public_inputs(public_inputs, None)?;
+
+        Ok(proof.derive_challenges(
+            &mut transcript,
+            !self.linearization.custom_gates().is_empty(),
+        ))
+    }
+
+    /// Points the opening witnesses of a proof are opened at, the evaluation
+    /// challenge `z` and its shift `z·ω` by the generator of the domain, for
+    /// the given public inputs.
+    ///
+    /// Along with [`Proof::opening_witnesses`], this allows an external
+    /// batcher to assemble the pairing check of the openings without
+    /// verifying the proof.
+    pub fn opening_points(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<(BlsScalar, BlsScalar), Error> {
+        let z = self.derive_challenges(proof, public_inputs)?.z;
+
+        Ok((z, z * self.linearization.domain().group_gen))
+    }
+
+    /// Fingerprint of the circuit of the verifier, to identify it without
+    /// comparing the serialized keys.
+    ///
+    /// The hash covers the circuit size and the commitments of the verifier
+    /// key and of the custom gates, so it's the same for every verifier of a
+    /// circuit regardless of its label or of how it was deserialized.
+    pub fn circuit_hash(&self) -> [u8; 32] {
+        self.verifier_key
+            .circuit_hash(self.linearization.custom_gates())
+    }
+
+    /// Replay the transcript of a proof for the given public inputs up to
+    /// the quotient commitments, returning it right before the evaluation
+    /// challenge `z` is drawn, without verifying the proof.
+    ///
+    /// The [`ResumableTranscript`] yields the same challenges as the default
+    /// transcript, so its state can be exported for a recursive verifier to
+    /// resume the remaining rounds of the verification.
+    pub fn evaluation_transcript(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<ResumableTranscript, Error> {
+        let public_inputs = self.public_inputs(public_inputs)?;
+
+        let transcript = ResumableTranscript::base(
+            &self.label,
+            &self.verifier_key,
+            self.linearization.custom_gates(),
+            self.constraints,
+        );
+        let mut transcript =
+            Self::append_public_inputs(transcript, &public_inputs, None);
+
+        proof.derive_quotient_challenges(
+            &mut transcript,
+            !self.linearization.custom_gates().is_empty(),
+        );
+
+        Ok(transcript)
+    }
+
+    /// Verify a batch of generated proofs of this circuit at once.
+    ///
+    /// The pairing checks of all the proofs are aggregated into a single
+    /// multi-pairing, which is considerably faster than verifying each proof
+    /// on its own. The batch is rejected if any of the proofs is invalid,
+    /// without indicating which one. An empty batch is valid.
+    pub fn batch_verify(
+        &self,
+        proofs: &[Proof],
+        public_inputs: &[&[BlsScalar]],
+    ) -> Result<(), Error> {
+        if public_inputs.len() != proofs.len() {
+            return Err(Error::InconsistentBatchLen {
+                expected: proofs.len(),
+                provided: public_inputs.len(),
+            });
+        }
+
+        let mut transcripts = Vec::with_capacity(proofs.len());
+        let mut positional = Vec::with_capacity(proofs.len());
+        for public_inputs in public_inputs {
+            let (transcript, public_inputs) =
+                self.prepare_public_inputs(public_inputs, None)?;
+            transcripts.push(transcript);
+            positional.push(public_inputs);
+        }
+
+        let verifier_keys =
+            vec![(&self.verifier_key, &self.linearization); proofs.len()];
+        let positional: Vec<&PublicInputs> = positional.iter().collect();
+
+        Proof::batch_verify(
+            proofs,
+            &verifier_keys,
+            &mut transcripts,
+            &self.opening_key,
+            &positional,
+        )
+    }
+
+    /// Verify a proof generated with [`Prover::prove_in_context`] within a
+    /// larger protocol, continuing the `transcript` the caller seeded with
+    /// the context of the protocol.
+    ///
+    /// The `transcript` has to be in the same state as the one the proof was
+    /// generated with, as described by [`ContextTranscript`]. The label of
+    /// the circuit, its description, the public inputs and the rounds of the
+    /// proof are appended to it, so the protocol can continue from it once
+    /// the proof is verified.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if nothing was appended to the `transcript`.
+    ///
+    /// [`Prover::prove_in_context`]:
+    /// crate::prelude::Prover::prove_in_context
+    pub fn verify_in_context(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+        transcript: &mut ContextTranscript,
+    ) -> Result<(), Error> {
+        debug_assert!(
+            transcript.is_seeded(),
+            "the transcript has to be seeded with the context of the protocol"
         );
 
-        proof.verify(
+        let public_inputs = self.public_inputs(public_inputs)?;
+
+        transcript.append_circuit_in_context(
+            &self.label,
+            &self.verifier_key,
+            self.linearization.custom_gates(),
+            self.constraints,
+        );
+        public_inputs
+            .values()
+            .for_each(|pi| transcript.append_scalar(b"pi", pi));
+
+        proof
+            .verify_with_report(
+                &self.verifier_key,
+                &self.linearization,
+                transcript,
+                &self.opening_key,
+                &public_inputs,
+                None,
+            )
+            .result()
+    }
+
+    /// Verify a proof generated with [`Prover::prove_with_transcript`],
+    /// deriving the challenges with the same transcript backend `T`.
+    ///
+    /// [`Prover::prove_with_transcript`]:
+    /// crate::prelude::Prover::prove_with_transcript
+    pub fn verify_with_transcript<T: TranscriptBackend>(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<(), Error> {
+        let public_inputs = self.public_inputs(public_inputs)?;
+        let mut transcript = self.transcript_with::<T>(&public_inputs);
+
+        proof
+            .verify_with_report(
+                &self.verifier_key,
+                &self.linearization,
+                &mut transcript,
+                &self.opening_key,
+                &public_inputs,
+                None,
+            )
+            .result()
+    }
+
+    /// Verify a generated proof with the slow reference verifier, which
+    /// checks the polynomial identity of the circuit without the
+    /// linearization of [`Verifier::verify`] and each KZG opening with a
+    /// pairing of its own.
+    ///
+    /// It is only meant to test the verifier against, and accepts exactly
+    /// the proofs [`Verifier::verify`] accepts.
+    #[cfg(feature = "test-reference")]
+    pub fn verify_reference(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<(), Error> {
+        let (mut transcript, public_inputs) =
+            self.prepare_public_inputs(public_inputs, None)?;
+
+        proof.verify_reference(
             &self.verifier_key,
+            &self.linearization,
             &mut transcript,
             &self.opening_key,
-            &dense_public_inputs,
+            &public_inputs,
+        )
+    }
+
+    /// Reduce a generated proof to its KZG openings, without checking them.
+    pub(crate) fn compute_openings(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<[(BlsScalar, OpeningProof); 2], Error> {
+        let (mut transcript, public_inputs) =
+            self.prepare_public_inputs(public_inputs, None)?;
+
+        proof.compute_openings(
+            &self.verifier_key,
+            &self.linearization,
+            &mut transcript,
+            &public_inputs,
+        )
+    }
+
+    /// Seed a fresh transcript of the backend `T` with the circuit
+    /// description and the public inputs.
+    pub(crate) fn transcript_with<T: TranscriptBackend>(
+        &self,
+        public_inputs: &PublicInputs,
+    ) -> T {
+        let transcript = T::base(
+            &self.label,
+            &self.verifier_key,
+            self.linearization.custom_gates(),
+            self.constraints,
+        );
+
+        Self::append_public_inputs(transcript, public_inputs, None)
+    }
+
+    /// Reduce a generated proof to the points of its pairing check, deriving
+    /// the challenges from the given seeded transcript.
+    #[cfg(all(feature = "std", feature = "serde"))]
+    pub(crate) fn pairing_points<T: TranscriptBackend>(
+        &self,
+        proof: &Proof,
+        transcript: &mut T,
+        public_inputs: &PublicInputs,
+    ) -> Result<(VerificationReport, G1Affine, G1Affine), Error> {
+        proof.compute_pairing_points(
+            &self.verifier_key,
+            &self.linearization,
+            transcript,
+            &self.opening_key,
+            public_inputs,
+            None,
+            &mut VerifyTrace::new("trace"),
+        )
+    }
+
+    /// Opening key used to check the proofs of this verifier.
+    ///
+    /// Its [`OpeningKey::g2_elements`] are the G2 points of the pairing
+    /// check, to perform it outside of the verifier along with
+    /// [`Verifier::opening_points`] and [`Proof::opening_witnesses`].
+    pub const fn opening_key(&self) -> &OpeningKey {
+        &self.opening_key
+    }
+
+    /// Check the public inputs against the circuit description, placing them
+    /// at the positions of their gates, and append them to a fresh
+    /// transcript, after the domain separator if any.
+    fn prepare_public_inputs(
+        &self,
+        public_inputs: &[BlsScalar],
+        dst: Option<&[u8]>,
+    ) -> Result<(Transcript, PublicInputs), Error> {
+        let public_inputs = self.public_inputs(public_inputs)?;
+        let transcript = self.seed_public_inputs(&public_inputs, dst);
+
+        Ok((transcript, public_inputs))
+    }
+
+    /// Append the public inputs to a fresh transcript, after the domain
+    /// separator if any.
+    fn seed_public_inputs(
+        &self,
+        public_inputs: &PublicInputs,
+        dst: Option<&[u8]>,
+    ) -> Transcript {
+        Self::append_public_inputs(self.transcript.clone(), public_inputs, dst)
+    }
+
+    /// Append the public inputs to the given transcript, after the domain
+    /// separator if any.
+    pub(crate) fn append_public_inputs<T: TranscriptProtocol>(
+        mut transcript: T,
+        public_inputs: &PublicInputs,
+        dst: Option<&[u8]>,
+    ) -> T {
+        if let Some(dst) = dst {
+            transcript.application_domain_sep(dst);
+        }
+
+        public_inputs
+            .values()
+            .for_each(|pi| transcript.append_scalar(b"pi", pi));
+
+        transcript
+    }
+}
+
+impl fmt::Display for Verifier {
+    /// Summarize the dimensions of the circuit, without the commitments of
+    /// its keys
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Verifier {{ size: {}, constraints: {}, public inputs: {}, custom gates: {} }}",
+            self.size(),
+            self.constraints(),
+            self.public_inputs_len(),
+            self.custom_gates_len()
         )
     }
 }
@@ -0,0 +1,233 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::DeserializableSlice;
+use merlin::Transcript;
+
+use crate::commitment_scheme::OpeningKey;
+use crate::error::Error;
+use crate::proof_system::widget::custom;
+use crate::proof_system::{
+    CustomGate, LinearizationContext, Proof, PublicInputs, VerifierKey,
+};
+use crate::transcript::TranscriptProtocol;
+
+use super::Verifier;
+
+/// Borrowed view over a verifier serialized with [`Verifier::to_bytes`], such
+/// as a memory-mapped key file.
+///
+/// Only the layout of the bytes is checked when the view is created. The
+/// commitments of the keys are deserialized, and checked to be in the
+/// prime-order subgroup, when a proof is verified with
+/// [`Proof::verify_with_ref`], so services holding many circuit keys don't
+/// need to keep an owned copy of them resident.
+///
+/// [`Proof::verify_with_ref`]: crate::prelude::Proof::verify_with_ref
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierKeyRef<'a> {
+    label: &'a [u8],
+    verifier_key: &'a [u8],
+    opening_key: &'a [u8],
+    public_input_indexes: &'a [u8],
+    custom_gates: &'a [u8],
+    quotient_chunks: usize,
+    size: usize,
+    constraints: usize,
+}
+
+impl<'a> VerifierKeyRef<'a> {
+    /// Create a view over bytes generated via [`Verifier::to_bytes`],
+    /// checking only their layout
+    pub fn try_from_bytes(mut bytes: &'a [u8]) -> Result<Self, Error> {
+        if bytes.len() < 48 {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let label_len = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let label_len = u64::from_be_bytes(label_len) as usize;
+        bytes = &bytes[8..];
+
+        let verifier_key_len =
+            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let verifier_key_len = u64::from_be_bytes(verifier_key_len) as usize;
+        bytes = &bytes[8..];
+
+        let opening_key_len =
+            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let opening_key_len = u64::from_be_bytes(opening_key_len) as usize;
+        bytes = &bytes[8..];
+
+        let public_input_indexes_len =
+            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let public_input_indexes_len =
+            u64::from_be_bytes(public_input_indexes_len) as usize;
+        bytes = &bytes[8..];
+
+        let size = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let size = u64::from_be_bytes(size) as usize;
+        bytes = &bytes[8..];
+
+        let constraints =
+            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let constraints = u64::from_be_bytes(constraints) as usize;
+        bytes = &bytes[8..];
+
+        let public_input_indexes_len = public_input_indexes_len
+            .checked_mul(8)
+            .ok_or(Error::NotEnoughBytes)?;
+        let len = label_len
+            .checked_add(verifier_key_len)
+            .and_then(|len| len.checked_add(opening_key_len))
+            .and_then(|len| len.checked_add(public_input_indexes_len))
+            .ok_or(Error::NotEnoughBytes)?;
+        if bytes.len() < len {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let label = &bytes[..label_len];
+        bytes = &bytes[label_len..];
+
+        let verifier_key = &bytes[..verifier_key_len];
+        bytes = &bytes[verifier_key_len..];
+
+        let opening_key = &bytes[..opening_key_len];
+        bytes = &bytes[opening_key_len..];
+
+        let public_input_indexes = &bytes[..public_input_indexes_len];
+        bytes = &bytes[public_input_indexes_len..];
+
+        let (custom_gates, quotient_chunks) =
+            Verifier::custom_gates_from_bytes(bytes)?;
+
+        Ok(Self {
+            label,
+            verifier_key,
+            opening_key,
+            public_input_indexes,
+            custom_gates,
+            quotient_chunks,
+            size,
+            constraints,
+        })
+    }
+
+    /// Number of public inputs expected by the circuit
+    pub const fn public_inputs_len(&self) -> usize {
+        self.public_input_indexes.len() / 8
+    }
+
+    /// Number of custom gates registered by the circuit
+    pub const fn custom_gates_len(&self) -> usize {
+        self.custom_gates.len() / custom::VerifierKey::SIZE
+    }
+
+    /// Gate positions of the public inputs, read from the referenced bytes
+    fn public_input_indexes(&self) -> impl Iterator<Item = usize> + 'a {
+        self.public_input_indexes
+            .chunks_exact(8)
+            .map(|c| <[u8; 8]>::try_from(c).expect("checked len"))
+            .map(u64::from_be_bytes)
+            .map(|n| n as usize)
+    }
+
+    /// Verify a proof against the referenced verifier, reading its fields
+    /// from the referenced bytes as they're needed.
+    ///
+    /// The public inputs are matched against their positions before any
+    /// commitment is deserialized, and only the commitments of the keys are
+    /// deserialized, while the label and the positions are read in place.
+    pub(crate) fn verify(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<(), Error> {
+        if public_inputs.len() != self.public_inputs_len() {
+            return Err(Error::InconsistentPublicInputsLen {
+                expected: self.public_inputs_len(),
+                provided: public_inputs.len(),
+            });
+        }
+
+        let size = self.size;
+        let public_inputs: PublicInputs = self
+            .public_input_indexes()
+            .zip(public_inputs.iter().copied())
+            .map(|(index, pi)| match index < size {
+                true => Ok((index, pi)),
+                false => Err(Error::PublicInputOutOfDomain { index, size }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let custom_gates =
+            custom::verifier_keys_from_bytes(self.custom_gates, &[])?;
+        let verifier_key = VerifierKey::from_slice(self.verifier_key)?;
+        let opening_key = OpeningKey::from_slice(self.opening_key)?;
+
+        let transcript = Transcript::base(
+            self.label,
+            &verifier_key,
+            &custom_gates,
+            self.constraints,
+        );
+        let mut transcript =
+            Verifier::append_public_inputs(transcript, &public_inputs, None);
+
+        let linearization = LinearizationContext::new(
+            &verifier_key,
+            custom_gates,
+            self.quotient_chunks,
+        )?;
+
+        proof
+            .verify_with_report(
+                &verifier_key,
+                &linearization,
+                &mut transcript,
+                &opening_key,
+                &public_inputs,
+                None,
+            )
+            .result()
+    }
+
+    /// Deserialize the referenced bytes into an owned [`Verifier`], checking
+    /// every commitment of its keys
+    ///
+    /// The verifier of a circuit that registers custom gates is rejected with
+    /// [`Error::UnregisteredCustomGate`], and has to be restored with
+    /// [`Self::to_verifier_with_custom_gates`].
+    pub fn to_verifier(&self) -> Result<Verifier, Error> {
+        self.to_verifier_with_custom_gates(&[])
+    }
+
+    /// Deserialize the referenced bytes into an owned [`Verifier`] of a
+    /// circuit registering the `custom_gates`, checking every commitment of
+    /// its keys
+    pub fn to_verifier_with_custom_gates(
+        &self,
+        custom_gates: &[CustomGate],
+    ) -> Result<Verifier, Error> {
+        let custom_gates =
+            custom::verifier_keys_from_bytes(self.custom_gates, custom_gates)?;
+        let label = self.label.to_vec();
+        let verifier_key = VerifierKey::from_slice(self.verifier_key)?;
+        let opening_key = OpeningKey::from_slice(self.opening_key)?;
+        let public_input_indexes = self.public_input_indexes().collect();
+
+        Verifier::new(
+            label,
+            verifier_key,
+            opening_key,
+            public_input_indexes,
+            custom_gates,
+            self.size,
+            self.constraints,
+            self.quotient_chunks,
+        )
+    }
+}
@@ -6,29 +6,39 @@
 
 //! PLONK turbo composer definitions
 
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::{cmp, ops};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 use dusk_bls12_381::BlsScalar;
 use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar};
 
 use crate::bit_iterator::BitIterator8;
 use crate::error::Error;
-use crate::runtime::{Runtime, RuntimeEvent};
+use crate::proof_system::quotient_poly::QuotientLayout;
+use crate::runtime::{Runtime, RuntimeEvent, RuntimeListener};
 
+mod checkpoint;
 mod circuit;
 mod compress;
 mod constraint_system;
 mod gate;
+mod serialize;
+mod stats;
 
 pub(crate) mod permutation;
 
+pub use checkpoint::Checkpoint;
 pub use circuit::Circuit;
-pub use constraint_system::{Constraint, Witness, WitnessPoint};
+pub use constraint_system::{
+    Constraint, Selector, WiredWitness, Witness, WitnessPoint,
+};
 pub use gate::Gate;
+pub(crate) use gate::GateFamily;
+pub use stats::CircuitStats;
 
-pub(crate) use constraint_system::{Selector, WireData, WiredWitness};
+pub(crate) use constraint_system::WireData;
 pub(crate) use permutation::Permutation;
 
 /// Construct and prove circuits
@@ -43,6 +53,9 @@ pub struct Composer {
     /// Witness values
     pub(crate) witnesses: Vec<BlsScalar>,
 
+    /// Gates enabling a registered custom gate, as `(custom gate, gate index)`
+    pub(crate) custom_selectors: Vec<(usize, usize)>,
+
     /// Permutation argument.
     pub(crate) perm: Permutation,
 
@@ -76,13 +89,283 @@ impl Composer {
     /// Identity point representation inside the constraint system
     pub const IDENTITY: WitnessPoint = WitnessPoint::new(Self::ZERO, Self::ONE);
 
+    /// Highest bit length supported by [`Self::range_gate`], so the double
+    /// of a witness proved to an odd width can't wrap around the modulus
+    pub const MAX_RANGE_BITS: usize = 252;
+
     /// Constraints count
     pub fn constraints(&self) -> usize {
         self.constraints.len()
     }
 
+    /// Size of the evaluation domain the circuit is proven at, its number of
+    /// constraints rounded up to the next power of two as done by
+    /// `EvaluationDomain::new`
+    pub fn padded_size(&self) -> usize {
+        self.constraints().next_power_of_two()
+    }
+
+    /// Number of rows of the evaluation domain left unused by the circuit
+    ///
+    /// With the `debug` feature, a warning is printed along with the CDF
+    /// output when the padding takes more than a quarter of the domain.
+    pub fn padding_overhead(&self) -> usize {
+        self.padded_size() - self.constraints()
+    }
+
+    /// Approximate peak of the bytes allocated to prove the circuit, so a
+    /// scheduler can decide whether a proving job fits in memory before
+    /// starting it.
+    ///
+    /// The estimate is the number of polynomials held at once while proving,
+    /// times their size over the [`padded size`](Self::padded_size) `n` of
+    /// the circuit or over the coset of `8n` the quotient is evaluated at,
+    /// times the size of a [`BlsScalar`]. It assumes that:
+    ///
+    /// - the prover key holds every selector, sigma and custom gate polynomial
+    ///   both in coefficients and over the coset, along with the linear
+    ///   evaluations of the permutation and the vanishing polynomial;
+    /// - the peak is reached while computing the quotient polynomial, when the
+    ///   wires and the permutation polynomial are held over the coset along
+    ///   with both terms of the quotient, its evaluations and its coefficients;
+    /// - the custom gates are of the default degree, as gates of a higher
+    ///   degree enlarge the coset.
+    ///
+    /// The commit key, the witnesses of the composer, the blinding
+    /// coefficients and the overhead of the allocator aren't accounted for.
+    pub fn estimate_proving_memory(&self) -> usize {
+        // selectors and sigmas of the prover key, in coefficients and over
+        // the coset
+        const KEY_POLYNOMIALS: usize = 19;
+        // linear evaluations of the permutation and vanishing polynomial
+        const KEY_COSET_EVALUATIONS: usize = 2;
+        // wires, permutation polynomial, both quotient terms, the quotient
+        // evaluations and coefficients
+        const QUOTIENT_COSET_EVALUATIONS: usize = 9;
+        // wire scalars, blinded wires, permutation and public inputs
+        const PROVER_POLYNOMIALS: usize = 10;
+
+        let n = self.padded_size();
+        let coset = QuotientLayout::new(core::iter::empty()).coset_factor() * n;
+
+        let custom_gates = self
+            .custom_selectors
+            .iter()
+            .map(|(id, _)| id + 1)
+            .max()
+            .unwrap_or(0);
+
+        let key = (KEY_POLYNOMIALS + custom_gates) * (n + coset)
+            + KEY_COSET_EVALUATIONS * coset;
+        let prover =
+            QUOTIENT_COSET_EVALUATIONS * coset + PROVER_POLYNOMIALS * n;
+
+        (key + prover) * core::mem::size_of::<BlsScalar>()
+    }
+
+    /// Capture the current gates and witnesses of the constraint system, to
+    /// discard everything appended after it with [`Self::rollback`].
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            gates: self.constraints.len(),
+            witnesses: self.witnesses.len(),
+            #[cfg(feature = "debug")]
+            debugger: self.runtime.debugger().len(),
+        }
+    }
+
+    /// Discard the gates and witnesses appended after the `checkpoint`, along
+    /// with their public inputs, custom gates and wires in the permutation.
+    ///
+    /// Witnesses appended before the checkpoint stay allocated, but lose the
+    /// wires of the discarded gates. Runtime listeners aren't notified, so
+    /// they keep the events of the discarded gates and witnesses. Rolling
+    /// back to a checkpoint taken after the current state has no effect.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        let Checkpoint {
+            gates, witnesses, ..
+        } = checkpoint;
+
+        self.constraints.truncate(gates);
+        self.witnesses.truncate(witnesses);
+        self.public_inputs.retain(|index, _| *index < gates);
+        self.custom_selectors.retain(|(_, index)| *index < gates);
+        self.perm.truncate(witnesses, gates);
+
+        #[cfg(feature = "debug")]
+        self.runtime.truncate_debugger(checkpoint.debugger);
+    }
+
+    /// Remove the gates that can't fail and the repeated arithmetic gates
+    /// from the constraint system, shrinking the circuit before proving it,
+    /// and return the number of removed gates.
+    ///
+    /// A gate can't fail if its identity vanishes for any witness: its range,
+    /// logic and curve addition selectors are zero, and so are either
+    /// `q_arith` or every arithmetic coefficient, `q_c` included. A gate
+    /// repeating the selectors and wires of a previous arithmetic gate
+    /// enforces the same identity twice. Gates with a public input or a
+    /// custom gate, and gates holding the shifted wires read by the gate
+    /// before them, are always kept.
+    ///
+    /// The remaining gates keep their order, along with their public inputs
+    /// and custom gates, and every witness stays bound by copy constraints
+    /// to the remaining wires holding it. The pass depends only on the
+    /// structure of the circuit, so calling it at the end of
+    /// [`Circuit::circuit`] yields the same circuit for the compiler and the
+    /// prover. Runtime listeners aren't notified of the removed gates.
+    pub fn optimize(&mut self) -> usize {
+        let custom: HashSet<usize> = self
+            .custom_selectors
+            .iter()
+            .map(|(_, index)| *index)
+            .collect();
+
+        let mut seen = HashSet::new();
+        let keep: Vec<bool> = self
+            .constraints
+            .iter()
+            .enumerate()
+            .map(|(index, gate)| {
+                let shifted = index > 0
+                    && (self.constraints[index - 1].reads_next()
+                        || custom.contains(&(index - 1)));
+                if shifted
+                    || custom.contains(&index)
+                    || self.public_inputs.contains_key(&index)
+                {
+                    return true;
+                }
+
+                if gate.is_trivial() {
+                    return false;
+                }
+
+                gate.reads_next()
+                    || seen.insert((
+                        [
+                            gate.q_m,
+                            gate.q_l,
+                            gate.q_r,
+                            gate.q_o,
+                            gate.q_f,
+                            gate.q_c,
+                            gate.q_arith,
+                        ],
+                        [gate.a, gate.b, gate.c, gate.d],
+                    ))
+            })
+            .collect();
+
+        let mut len = 0;
+        let gates: Vec<Option<usize>> = keep
+            .iter()
+            .map(|keep| {
+                keep.then(|| {
+                    len += 1;
+                    len - 1
+                })
+            })
+            .collect();
+
+        let removed = self.constraints.len() - len;
+        if removed == 0 {
+            return 0;
+        }
+
+        let mut index = 0;
+        self.constraints.retain(|_| {
+            index += 1;
+            keep[index - 1]
+        });
+
+        self.public_inputs = self
+            .public_inputs
+            .drain()
+            .filter_map(|(index, pi)| gates[index].map(|index| (index, pi)))
+            .collect();
+        self.custom_selectors
+            .iter_mut()
+            .for_each(|(_, index)| *index = gates[*index].expect("kept"));
+        self.perm.remap_gates(&gates);
+
+        #[cfg(feature = "debug")]
+        self.runtime.retain_debugger(&keep);
+
+        removed
+    }
+
+    /// Gates of the constraint system, in insertion order
+    pub fn gates(&self) -> &[Gate] {
+        &self.constraints
+    }
+
+    /// Sigma permutation of the copy constraints over the padded circuit, as
+    /// one mapping per wire column in the order left, right, output and
+    /// fourth.
+    ///
+    /// The wire of column `i` at row `j` is addressed as `i·n + j`, for the
+    /// [`padded size`](Self::padded_size) `n`, and is mapped to the next wire
+    /// holding the same witness, cycling back to the first one. Wires holding
+    /// a witness that isn't shared are mapped to themselves.
+    pub fn sigma_permutation(&self) -> [Vec<usize>; 4] {
+        let n = self.padded_size();
+
+        self.perm.compute_sigma_permutations(n).map(|sigma| {
+            sigma
+                .into_iter()
+                .map(|wire| match wire {
+                    WireData::Left(j) => j,
+                    WireData::Right(j) => n + j,
+                    WireData::Output(j) => 2 * n + j,
+                    WireData::Fourth(j) => 3 * n + j,
+                })
+                .collect()
+        })
+    }
+
+    /// Report the gate counts by type and the wiring of the constraint
+    /// system, to assess the cost of a circuit before proving it
+    pub fn stats(&self) -> CircuitStats {
+        CircuitStats::from_composer(self)
+    }
+
+    /// Witnesses appended to the constraint system along with their assigned
+    /// values, in insertion order
+    pub fn witness_assignments(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (Witness, BlsScalar)> + '_ {
+        self.witnesses
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (Witness::new(i), *v))
+    }
+
+    /// Serialize the constraint system: its gates, number of witnesses wired
+    /// to the gates, positions of the public inputs and the custom gates
+    /// enabled by the gates.
+    ///
+    /// The values of the witnesses and public inputs aren't serialized, nor
+    /// are the runtime listeners. The reloaded composer compiles to the same
+    /// prover and verifier with [`Compiler::compile_with_composer`], skipping
+    /// the construction of the circuit.
+    ///
+    /// [`Compiler::compile_with_composer`]:
+    /// crate::prelude::Compiler::compile_with_composer
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serialize::SerializedCircuit::from_composer(self)
+    }
+
+    /// Deserialize a constraint system serialized with [`Self::to_bytes`].
+    ///
+    /// The witnesses and public inputs are allocated with a zero value, and
+    /// the permutation is rebuilt from the wires of the gates.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        serialize::SerializedCircuit::from_bytes(bytes)
+    }
+
     /// Create a [`Composer`] instance from a compressed circuit
-    pub(crate) fn from_bytes(compressed: &[u8]) -> Result<Self, Error> {
+    pub(crate) fn from_compressed(compressed: &[u8]) -> Result<Self, Error> {
         compress::CompressedCircuit::from_bytes(compressed)
     }
 
@@ -103,42 +386,8 @@ impl Composer {
     fn append_custom_gate_internal(&mut self, constraint: Constraint) {
         let n = self.constraints.len();
 
-        let a = constraint.witness(WiredWitness::A);
-        let b = constraint.witness(WiredWitness::B);
-        let c = constraint.witness(WiredWitness::C);
-        let d = constraint.witness(WiredWitness::D);
-
-        let q_m = *constraint.coeff(Selector::Multiplication);
-        let q_l = *constraint.coeff(Selector::Left);
-        let q_r = *constraint.coeff(Selector::Right);
-        let q_o = *constraint.coeff(Selector::Output);
-        let q_f = *constraint.coeff(Selector::Fourth);
-        let q_c = *constraint.coeff(Selector::Constant);
-
-        let q_arith = *constraint.coeff(Selector::Arithmetic);
-        let q_range = *constraint.coeff(Selector::Range);
-        let q_logic = *constraint.coeff(Selector::Logic);
-        let q_fixed_group_add = *constraint.coeff(Selector::GroupAddFixedBase);
-        let q_variable_group_add =
-            *constraint.coeff(Selector::GroupAddVariableBase);
-
-        let gate = Gate {
-            q_m,
-            q_l,
-            q_r,
-            q_o,
-            q_f,
-            q_c,
-            q_arith,
-            q_range,
-            q_logic,
-            q_fixed_group_add,
-            q_variable_group_add,
-            a,
-            b,
-            c,
-            d,
-        };
+        let gate = Gate::from(&constraint);
+        let Gate { a, b, c, d, .. } = gate;
 
         self.constraints.push(gate);
 
@@ -156,10 +405,61 @@ impl Composer {
         &mut self.runtime
     }
 
+    /// Register a listener to be notified of every [`RuntimeEvent`] emitted
+    /// by the composer from now on.
+    ///
+    /// Listeners are notified in the order they were registered, after the
+    /// debugger if the `debug` feature is enabled.
+    pub fn add_listener<L>(&mut self, listener: L)
+    where
+        L: 'static + RuntimeListener + Clone,
+    {
+        self.runtime.add_listener(Box::new(listener));
+    }
+
+    /// Register a callback to be called on every [`RuntimeEvent`] emitted
+    /// by the composer from now on.
+    ///
+    /// This is a shorthand for [`Self::add_listener`] with a function.
+    pub fn on_event(&mut self, callback: fn(&RuntimeEvent)) {
+        self.add_listener(callback);
+    }
+
+    /// Encode the witnesses and constraints appended so far into an
+    /// in-memory CDF buffer.
+    ///
+    /// This is the same circuit description written to the path set in the
+    /// `CDF_OUTPUT` environment variable, and can be decoded with
+    /// `dusk_cdf::CircuitDescription::from_reader`.
+    #[cfg(feature = "debug")]
+    pub fn cdf_to_vec(&self) -> std::io::Result<Vec<u8>> {
+        self.runtime.debugger().write_to_vec()
+    }
+
+    /// Indexes of the gates in the dependency cone of the gate `constraint`,
+    /// in ascending order, to isolate the gates involved in an unsatisfied
+    /// constraint while developing a gadget.
+    ///
+    /// The cone follows the wiring backwards, from each witness of a gate to
+    /// the first gate wiring it, along with the gates chained by the shifted
+    /// wires of range, logic and curve addition gates. It's empty if there
+    /// is no such gate.
+    #[cfg(feature = "debug")]
+    pub fn failing_cone(&self, constraint: usize) -> Vec<usize> {
+        self.runtime.debugger().failing_cone(constraint)
+    }
+
     /// Initialize the constraint system with the constants for 0 and 1 and
     /// append two dummy gates
     pub fn initialized() -> Self {
+        Self::initialized_with(Runtime::new())
+    }
+
+    /// Initialize the constraint system like [`Self::initialized`], emitting
+    /// the events to the given runtime
+    pub(crate) fn initialized_with(runtime: Runtime) -> Self {
         let mut slf = Self::uninitialized();
+        slf.runtime = runtime;
 
         let zero = slf.append_witness(0);
         let one = slf.append_witness(1);
@@ -180,6 +480,7 @@ impl Composer {
             constraints: Vec::new(),
             public_inputs: HashMap::new(),
             witnesses: Vec::new(),
+            custom_selectors: Vec::new(),
             perm: Permutation::new(),
             runtime: Runtime::new(),
         }
@@ -247,6 +548,107 @@ impl Composer {
         self.append_custom_gate_internal(constraint)
     }
 
+    /// Append a new width-4 gate/constraint that enables the selector of the
+    /// custom gate `id`, its index in [`Circuit::CUSTOM_GATES`].
+    ///
+    /// The identity of the custom gate is enforced on top of the selectors
+    /// of the constraint, which are usually all left unset.
+    pub fn append_registered_gate(
+        &mut self,
+        id: usize,
+        constraint: Constraint,
+    ) {
+        self.custom_selectors.push((id, self.constraints.len()));
+
+        self.append_custom_gate(constraint)
+    }
+
+    /// Append the witnesses and gates of `other` to the constraint system,
+    /// to compose circuits built by independent composers.
+    ///
+    /// The witnesses of `other` are appended with new indexes, except for the
+    /// pairs `(other, self)` of `wire_mapping`, which identify a witness of
+    /// `other` with one of `self` so the permutation wires them together.
+    /// The constants [`Self::ZERO`] and [`Self::ONE`] of both composers are
+    /// always identified. An identified witness keeps the value assigned in
+    /// `self`, so the circuit is unsatisfied if it differs in `other`.
+    ///
+    /// The gates of `other` are appended in order, including its constant
+    /// and dummy gates, along with their public inputs and the selectors of
+    /// their registered custom gates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a witness of `wire_mapping` isn't allocated in its composer.
+    pub fn append_circuit(
+        &mut self,
+        other: &Composer,
+        wire_mapping: &[(Witness, Witness)],
+    ) {
+        let mut witnesses = vec![None; other.witnesses.len()];
+        witnesses[Self::ZERO.index()] = Some(Self::ZERO);
+        witnesses[Self::ONE.index()] = Some(Self::ONE);
+
+        for (w_other, w_self) in wire_mapping {
+            assert!(
+                w_self.index() < self.witnesses.len(),
+                "the witness {} isn't allocated in the composer",
+                w_self.index()
+            );
+
+            witnesses[w_other.index()] = Some(*w_self);
+        }
+
+        let witnesses: Vec<Witness> = witnesses
+            .into_iter()
+            .zip(other.witnesses.iter())
+            .map(|(w, v)| w.unwrap_or_else(|| self.append_witness(*v)))
+            .collect();
+
+        let offset = self.constraints.len();
+
+        for (i, gate) in other.constraints.iter().enumerate() {
+            let mut constraint = [
+                Selector::Multiplication,
+                Selector::Left,
+                Selector::Right,
+                Selector::Output,
+                Selector::Fourth,
+                Selector::Constant,
+                Selector::Arithmetic,
+                Selector::Range,
+                Selector::Logic,
+                Selector::GroupAddFixedBase,
+                Selector::GroupAddVariableBase,
+            ]
+            .into_iter()
+            .fold(Constraint::new(), |c, s| c.set(s, gate.selector(s)));
+
+            for wire in [
+                WiredWitness::A,
+                WiredWitness::B,
+                WiredWitness::C,
+                WiredWitness::D,
+            ] {
+                constraint
+                    .set_witness(wire, witnesses[gate.wire(wire).index()]);
+            }
+
+            if let Some(pi) = other.public_inputs.get(&i) {
+                constraint = constraint.public(*pi);
+            }
+
+            self.append_custom_gate(constraint);
+        }
+
+        self.custom_selectors.extend(
+            other
+                .custom_selectors
+                .iter()
+                .map(|(id, gate_index)| (*id, gate_index + offset)),
+        );
+    }
+
     /// Performs a logical AND or XOR op between the inputs provided for
     /// `num_bits = BIT_PAIRS * 2` bits (counting from the least significant).
     ///
@@ -267,6 +669,22 @@ impl Composer {
         // the bits are iterated as chunks of two; hence, we require an even
         // number
         let num_bits = cmp::min(BIT_PAIRS * 2, 256);
+
+        let (_, _, d) = self.append_logic(a, b, num_bits, is_component_xor);
+
+        d
+    }
+
+    /// Performs a logical AND or XOR between the first even `num_bits` of
+    /// the inputs, returning the final accumulators of `a`, `b` and of the
+    /// output
+    fn append_logic(
+        &mut self,
+        a: Witness,
+        b: Witness,
+        num_bits: usize,
+        is_component_xor: bool,
+    ) -> (Witness, Witness, Witness) {
         let num_quads = num_bits >> 1;
 
         let bls_four = BlsScalar::from(4u64);
@@ -362,7 +780,76 @@ impl Composer {
 
         self.append_custom_gate(constraint);
 
-        d
+        (a, b, d)
+    }
+
+    /// Performs a logical AND or XOR between two inputs of at most
+    /// `num_bits` bits, for any `num_bits` up to [`Self::MAX_RANGE_BITS`],
+    /// returning a [`Witness`] holding the result.
+    ///
+    /// Unlike [`Self::append_logic_component`], the inputs are constrained
+    /// to fit in `num_bits`, so the circuit is unsatisfied otherwise. An odd
+    /// `num_bits` is rounded up to the next even width, bounding the inputs
+    /// as done by [`Self::range_gate`].
+    fn append_logic_gate(
+        &mut self,
+        a: Witness,
+        b: Witness,
+        num_bits: usize,
+        is_component_xor: bool,
+    ) -> Result<Witness, Error> {
+        if num_bits > Self::MAX_RANGE_BITS {
+            return Err(Error::BitLengthTooLarge {
+                num_bits,
+                max: Self::MAX_RANGE_BITS,
+            });
+        }
+
+        let even_bits = num_bits + num_bits % 2;
+        let (a_acc, b_acc, d) =
+            self.append_logic(a, b, even_bits, is_component_xor);
+
+        // the accumulators hold the quads of the inputs, so they're equal
+        // only if the inputs fit in the even width
+        self.assert_equal(a_acc, a);
+        self.assert_equal(b_acc, b);
+
+        if num_bits % 2 == 1 {
+            self.append_top_bit_unset(a, even_bits);
+            self.append_top_bit_unset(b, even_bits);
+        }
+
+        Ok(d)
+    }
+
+    /// Performs the bitwise XOR between two inputs of at most `num_bits`
+    /// bits, returning a [`Witness`] holding the result.
+    ///
+    /// The inputs are constrained to fit in `num_bits`, which can be any
+    /// length up to [`Self::MAX_RANGE_BITS`]. Returns
+    /// [`Error::BitLengthTooLarge`] otherwise.
+    pub fn xor_gate(
+        &mut self,
+        a: Witness,
+        b: Witness,
+        num_bits: usize,
+    ) -> Result<Witness, Error> {
+        self.append_logic_gate(a, b, num_bits, true)
+    }
+
+    /// Performs the bitwise AND between two inputs of at most `num_bits`
+    /// bits, returning a [`Witness`] holding the result.
+    ///
+    /// The inputs are constrained to fit in `num_bits`, which can be any
+    /// length up to [`Self::MAX_RANGE_BITS`]. Returns
+    /// [`Error::BitLengthTooLarge`] otherwise.
+    pub fn and_gate(
+        &mut self,
+        a: Witness,
+        b: Witness,
+        num_bits: usize,
+    ) -> Result<Witness, Error> {
+        self.append_logic_gate(a, b, num_bits, false)
     }
 
     /// Evaluate `jubjub · Generator` as a [`WitnessPoint`]
@@ -792,6 +1279,67 @@ impl Composer {
         WitnessPoint::new(x_3, y_3)
     }
 
+    /// Check that the arithmetic, range and logic identities of every gate
+    /// are satisfied by the witnesses appended so far.
+    ///
+    /// This is the check performed by [`Prover::prove_checked`] before
+    /// computing any polynomial. The curve addition and custom gates aren't
+    /// checked.
+    ///
+    /// Returns [`Error::UnsatisfiedConstraint`] for the first gate that isn't
+    /// satisfied. A [`RuntimeEvent::GateEvaluated`] is emitted for every gate
+    /// evaluated until then.
+    ///
+    /// [`Prover::prove_checked`]: crate::prelude::Prover::prove_checked
+    pub fn check_satisfied(&mut self) -> Result<(), Error> {
+        for gate_index in 0..self.constraints.len() {
+            let failing = self.failing_family(gate_index);
+
+            self.runtime.event(RuntimeEvent::GateEvaluated {
+                index: gate_index,
+                satisfied: failing.is_none(),
+            });
+
+            if let Some(family) = failing {
+                return Err(Error::UnsatisfiedConstraint {
+                    gate_index,
+                    selector: family.name(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Family of the first gate identity that isn't satisfied by the gate at
+    /// the given index, if any
+    fn failing_family(&self, gate_index: usize) -> Option<GateFamily> {
+        let wires = |gate: &Gate| {
+            [
+                WiredWitness::A,
+                WiredWitness::B,
+                WiredWitness::C,
+                WiredWitness::D,
+            ]
+            .map(|w| self[gate.wire(w)])
+        };
+
+        let gate = &self.constraints[gate_index];
+        let public_input = self
+            .public_inputs
+            .get(&gate_index)
+            .copied()
+            .unwrap_or_default();
+        let next = self.constraints.get(gate_index + 1);
+
+        GateFamily::failing(
+            |s| gate.selector(s),
+            public_input,
+            wires(gate),
+            next.map(wires).unwrap_or_default(),
+        )
+    }
+
     /// Adds a boolean constraint (also known as binary constraint) where the
     /// gate eq. will enforce that the [`Witness`] received is either `0` or `1`
     /// by adding a constraint in the circuit.
@@ -812,6 +1360,18 @@ impl Composer {
         self.append_gate(constraint);
     }
 
+    /// Adds a boolean constraint for each of the given [`Witness`]es, as
+    /// [`Composer::component_boolean`] does, reserving all the gates at once.
+    ///
+    /// Consumes one gate per witness
+    pub fn component_booleans(&mut self, witnesses: &[Witness]) {
+        self.constraints.reserve(witnesses.len());
+
+        witnesses
+            .iter()
+            .for_each(|witness| self.component_boolean(*witness));
+    }
+
     /// Decomposes `scalar` into an array truncated to `N` bits (max 256) in
     /// little endian.
     /// The `scalar` for 4, for example, would be deconstructed into the array
@@ -896,6 +1456,144 @@ impl Composer {
         result
     }
 
+    /// Evaluate the multi-scalar multiplication `Σ scalars[i] · points[i]`
+    /// as a [`WitnessPoint`]
+    ///
+    /// The scalars are split in windows of two bits, from the most
+    /// significant one. The multiples `O, P, 2P, 3P` of every point are
+    /// computed once, and for every window the multiple selected by its two
+    /// bits is added to the accumulator, which is doubled twice per window
+    /// for all the points at once. This takes half the additions of
+    /// [`Composer::component_mul_point`] per point, and a single chain of
+    /// doublings instead of one per point.
+    ///
+    /// Panics if the number of scalars doesn't match the number of points.
+    pub fn component_msm(
+        &mut self,
+        scalars: &[Witness],
+        points: &[WitnessPoint],
+    ) -> WitnessPoint {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "every scalar is multiplied by a point"
+        );
+
+        if scalars.is_empty() {
+            return Self::IDENTITY;
+        }
+
+        // Turn scalars into bits
+        let scalars_bits: Vec<[Witness; 252]> = scalars
+            .iter()
+            .map(|scalar| self.component_decomposition::<252>(*scalar))
+            .collect();
+
+        let tables: Vec<[WitnessPoint; 3]> = points
+            .iter()
+            .map(|point| self.msm_window_table(*point))
+            .collect();
+
+        let mut result = Self::IDENTITY;
+
+        for window in (0..126).rev() {
+            result = self.component_add_point(result, result);
+            result = self.component_add_point(result, result);
+
+            for (bits, table) in scalars_bits.iter().zip(&tables) {
+                let (lo, hi) = (bits[2 * window], bits[2 * window + 1]);
+                let point_to_add = self.msm_window_select(lo, hi, table);
+                result = self.component_add_point(result, point_to_add);
+            }
+        }
+
+        result
+    }
+
+    /// Coordinate-wise differences `[P - O, 2P - O, 3P - 2P - P + O]` of the
+    /// multiples of `point` selected by a window of
+    /// [`Composer::component_msm`], so the coordinates of the multiple of a
+    /// window `(lo, hi)` are the ones of the identity `O` plus `lo`, `hi` and
+    /// `lo · hi` times each of the differences.
+    fn msm_window_table(&mut self, point: WitnessPoint) -> [WitnessPoint; 3] {
+        let double = self.component_add_point(point, point);
+        let triple = self.component_add_point(double, point);
+
+        // the coordinates of the identity are (0, 1)
+        let minus_identity = |composer: &mut Self, p: WitnessPoint| {
+            let constraint = Constraint::new()
+                .left(1)
+                .constant(-BlsScalar::one())
+                .a(*p.y());
+            WitnessPoint::new(*p.x(), composer.gate_add(constraint))
+        };
+        let single_diff = minus_identity(self, point);
+        let double_diff = minus_identity(self, double);
+
+        let mut cross = |base: BlsScalar, triple, double, single| {
+            let constraint = Constraint::new()
+                .left(1)
+                .right(-BlsScalar::one())
+                .fourth(-BlsScalar::one())
+                .constant(base)
+                .a(triple)
+                .b(double)
+                .d(single);
+            self.gate_add(constraint)
+        };
+        let cross_x =
+            cross(BlsScalar::zero(), *triple.x(), *double.x(), *point.x());
+        let cross_y =
+            cross(BlsScalar::one(), *triple.y(), *double.y(), *point.y());
+
+        [
+            single_diff,
+            double_diff,
+            WitnessPoint::new(cross_x, cross_y),
+        ]
+    }
+
+    /// Select the multiple `(lo + 2 · hi) · P` out of the differences of
+    /// [`Composer::msm_window_table`], with three gates per coordinate.
+    ///
+    /// `lo` and `hi` are expected to be constrained by
+    /// [`Composer::component_boolean`]
+    fn msm_window_select(
+        &mut self,
+        lo: Witness,
+        hi: Witness,
+        table: &[WitnessPoint; 3],
+    ) -> WitnessPoint {
+        let constraint = Constraint::new().mult(1).a(lo).b(hi);
+        let both = self.gate_mul(constraint);
+
+        let mut select = |base: BlsScalar, c: fn(&WitnessPoint) -> &Witness| {
+            let constraint = Constraint::new()
+                .mult(1)
+                .constant(base)
+                .a(lo)
+                .b(*c(&table[0]));
+            let acc = self.gate_mul(constraint);
+
+            [(hi, &table[1]), (both, &table[2])].into_iter().fold(
+                acc,
+                |acc, (bit, diff)| {
+                    let constraint = Constraint::new()
+                        .mult(1)
+                        .fourth(1)
+                        .a(bit)
+                        .b(*c(diff))
+                        .d(acc);
+                    self.gate_mul(constraint)
+                },
+            )
+        };
+        let x = select(BlsScalar::zero(), WitnessPoint::x);
+        let y = select(BlsScalar::one(), WitnessPoint::y);
+
+        WitnessPoint::new(x, y)
+    }
+
     /// Conditionally selects a [`Witness`] based on an input bit.
     ///
     /// bit == 1 => a,
@@ -931,6 +1629,30 @@ impl Composer {
         self.gate_add(constraint)
     }
 
+    /// Conditionally selects a [`Witness`] as `b + cond · (a - b)`.
+    ///
+    /// cond == 1 => a,
+    /// cond == 0 => b,
+    ///
+    /// Takes two gates, against the four of [`Composer::component_select`].
+    /// `cond` is expected to be constrained by
+    /// [`Composer::component_boolean`]: any other value of `cond` yields the
+    /// same affine combination of `a` and `b`, which satisfies the circuit.
+    pub fn conditional_select(
+        &mut self,
+        cond: Witness,
+        a: Witness,
+        b: Witness,
+    ) -> Witness {
+        // a - b
+        let constraint = Constraint::new().left(1).right(-BlsScalar::one());
+        let a_min_b = self.gate_add(constraint.a(a).b(b));
+
+        // cond · (a - b) + b
+        let constraint = Constraint::new().mult(1).fourth(1);
+        self.gate_mul(constraint.a(cond).b(a_min_b).d(b))
+    }
+
     /// Conditionally selects a [`Witness`] based on an input bit.
     ///
     /// bit == 1 => value,
@@ -1016,6 +1738,51 @@ impl Composer {
         // number
         let num_bits = cmp::min(BIT_PAIRS * 2, 256);
 
+        self.append_range(witness, num_bits)
+    }
+
+    /// Constrains a [`Witness`] to be encoded in at most `num_bits` bits,
+    /// for any `num_bits` up to [`Self::MAX_RANGE_BITS`].
+    ///
+    /// An even `num_bits` appends the same gates as
+    /// [`Self::component_range`]. An odd `num_bits` is rounded up to the next
+    /// even width, and the witness is proved along with its double, which
+    /// fits in the rounded width only if the top bit of the witness is unset.
+    /// A `num_bits` of zero constrains the witness to zero.
+    ///
+    /// Returns [`Error::BitLengthTooLarge`] if `num_bits` exceeds
+    /// [`Self::MAX_RANGE_BITS`].
+    pub fn range_gate(
+        &mut self,
+        witness: Witness,
+        num_bits: usize,
+    ) -> Result<(), Error> {
+        if num_bits > Self::MAX_RANGE_BITS {
+            return Err(Error::BitLengthTooLarge {
+                num_bits,
+                max: Self::MAX_RANGE_BITS,
+            });
+        }
+
+        let even_bits = num_bits + num_bits % 2;
+        self.append_range(witness, even_bits);
+
+        if num_bits % 2 == 1 {
+            self.append_top_bit_unset(witness, even_bits);
+        }
+
+        Ok(())
+    }
+
+    /// Constrain the top bit of a [`Witness`] of an even `num_bits` to be
+    /// unset, by range-constraining its double to the same width
+    fn append_top_bit_unset(&mut self, witness: Witness, num_bits: usize) {
+        let double = self.gate_add(Constraint::new().left(2).a(witness));
+        self.append_range(double, num_bits);
+    }
+
+    /// Range-constrain a [`Witness`] to an even `num_bits` of at most 256
+    fn append_range(&mut self, witness: Witness, num_bits: usize) {
         // if num_bits = 0 constrain witness to 0
         if num_bits == 0 {
             let constraint = Constraint::new().left(1).a(witness);
@@ -1154,7 +1921,20 @@ impl Composer {
     where
         C: Circuit,
     {
-        let mut composer = Self::initialized();
+        Self::prove_with_runtime(constraints, circuit, Runtime::new())
+    }
+
+    /// Prove the circuit like [`Self::prove`], emitting the events to the
+    /// given runtime
+    pub(crate) fn prove_with_runtime<C>(
+        constraints: usize,
+        circuit: &C,
+        runtime: Runtime,
+    ) -> Result<Self, Error>
+    where
+        C: Circuit,
+    {
+        let mut composer = Self::initialized_with(runtime);
 
         circuit.circuit(&mut composer)?;
 
@@ -0,0 +1,33 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+/// State of a composer captured by [`Composer::checkpoint`], to discard the
+/// gates and witnesses appended after it with [`Composer::rollback`]
+///
+/// [`Composer::checkpoint`]: crate::prelude::Composer::checkpoint
+/// [`Composer::rollback`]: crate::prelude::Composer::rollback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Number of gates of the composer
+    pub(crate) gates: usize,
+    /// Number of witnesses of the composer
+    pub(crate) witnesses: usize,
+    /// Number of witnesses and constraints recorded by the debugger
+    #[cfg(feature = "debug")]
+    pub(crate) debugger: (usize, usize),
+}
+
+impl Checkpoint {
+    /// Number of gates of the composer when the checkpoint was taken
+    pub const fn gates(&self) -> usize {
+        self.gates
+    }
+
+    /// Number of witnesses of the composer when the checkpoint was taken
+    pub const fn witnesses(&self) -> usize {
+        self.witnesses
+    }
+}
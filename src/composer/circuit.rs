@@ -7,7 +7,7 @@
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
-use crate::prelude::{Composer, Error};
+use crate::prelude::{Composer, CustomGate, Error};
 
 use super::compress::CompressedCircuit;
 
@@ -15,6 +15,16 @@ use super::compress::CompressedCircuit;
 ///
 /// The default implementation will be used to generate the proving arguments.
 pub trait Circuit: Default {
+    /// Custom gates used by the circuit, enabled with
+    /// [`Composer::append_registered_gate`] by their index in this list.
+    ///
+    /// The quotient polynomial of a circuit whose gates have a maximum degree
+    /// `d` above [`CustomGate::DEFAULT_DEGREE`] is evaluated over a coset of
+    /// size `(d + 3).next_power_of_two()·n` and split into chunks of degree
+    /// `⌊d/4⌋·n`, the last one of degree `(d - 3·⌊d/4⌋)·n`, so the public
+    /// parameters must support polynomials of that degree.
+    const CUSTOM_GATES: &'static [CustomGate] = &[];
+
     /// Circuit definition
     fn circuit(&self, composer: &mut Composer) -> Result<(), Error>;
 
@@ -31,6 +41,8 @@ pub trait Circuit: Default {
     /// being compiled into its prover and verifier instances with
     /// [`Compiler::compile_with_compressed`].
     ///
+    /// Circuits enabling custom gates can't be compressed.
+    ///
     /// [`Compiler::compile_with_compressed`]:
     /// [`crate::prelude::Compiler::compile_with_compressed`]
     #[cfg(feature = "alloc")]
@@ -38,6 +50,10 @@ pub trait Circuit: Default {
         let mut composer = Composer::initialized();
         Self::default().circuit(&mut composer)?;
 
+        if !composer.custom_selectors.is_empty() {
+            return Err(Error::CustomGatesNotCompressible);
+        }
+
         let hades_optimization = true;
         Ok(CompressedCircuit::from_composer(
             hades_optimization,
@@ -9,7 +9,7 @@ use dusk_bls12_381::BlsScalar;
 
 /// Selectors used to address a coefficient inside of a [`Constraint`]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum Selector {
+pub enum Selector {
     /// Multiplication coefficient `q_M`
     Multiplication = 0x00,
     /// Left coefficient `q_L`
@@ -39,7 +39,7 @@ pub(crate) enum Selector {
 
 /// Wire used to address a witness inside of a [`Constraint`]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum WiredWitness {
+pub enum WiredWitness {
     /// `A` witness
     A = 0x00,
     /// `B` witness
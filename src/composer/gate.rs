@@ -4,9 +4,104 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use core::fmt;
+
 use dusk_bls12_381::BlsScalar;
 
-use crate::prelude::Witness;
+use crate::prelude::{Constraint, Selector, WiredWitness, Witness};
+use crate::proof_system::widget::logic::proverkey::{delta, delta_xor_and};
+
+/// Family of gate identities checked against the witnesses of a circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GateFamily {
+    /// Arithmetic gate, including the public inputs
+    Arithmetic,
+    /// Range gate
+    Range,
+    /// Logic gate
+    Logic,
+}
+
+impl GateFamily {
+    /// Name of the selector enabling the gate family
+    pub(crate) const fn name(&self) -> &'static str {
+        match self {
+            Self::Arithmetic => "arithmetic",
+            Self::Range => "range",
+            Self::Logic => "logic",
+        }
+    }
+
+    /// Evaluate the identities of the gate families enabled by `selector`,
+    /// returning the first one that isn't satisfied by the values of the
+    /// `wires` `[a, b, c, d]` of the gate and of the `next` gate.
+    ///
+    /// The range and logic identities are checked term by term instead of
+    /// being folded with a separation challenge, and read the shifted wires
+    /// from the next gate.
+    pub(crate) fn failing<S>(
+        selector: S,
+        public_input: BlsScalar,
+        [a, b, c, d]: [BlsScalar; 4],
+        [a_w, b_w, _, d_w]: [BlsScalar; 4],
+    ) -> Option<Self>
+    where
+        S: Fn(Selector) -> BlsScalar,
+    {
+        let qc = selector(Selector::Constant);
+        let four = BlsScalar::from(4);
+        let zero = BlsScalar::zero();
+
+        let arith = (selector(Selector::Multiplication) * a * b
+            + selector(Selector::Left) * a
+            + selector(Selector::Right) * b
+            + selector(Selector::Output) * c
+            + selector(Selector::Fourth) * d
+            + qc)
+            * selector(Selector::Arithmetic)
+            + public_input;
+
+        if arith != zero {
+            return Some(Self::Arithmetic);
+        }
+
+        if selector(Selector::Range) != zero {
+            let quads =
+                [c - four * d, b - four * c, a - four * b, d_w - four * a];
+
+            if quads.into_iter().any(|q| delta(q) != zero) {
+                return Some(Self::Range);
+            }
+        }
+
+        if selector(Selector::Logic) != zero {
+            let a_quad = a_w - four * a;
+            let b_quad = b_w - four * b;
+            let d_quad = d_w - four * d;
+
+            let terms = [
+                delta(a_quad),
+                delta(b_quad),
+                delta(d_quad),
+                c - a_quad * b_quad,
+                delta_xor_and(&a_quad, &b_quad, &c, &d_quad, &qc),
+            ];
+
+            if terms.into_iter().any(|t| t != zero) {
+                return Some(Self::Logic);
+            }
+        }
+
+        // TODO check ecc wires
+        None
+    }
+}
+
+impl fmt::Display for GateFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
 
 /// Represents a gate with its associated wire data
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,3 +139,132 @@ pub struct Gate {
     /// Fourth wire witness.
     pub(crate) d: Witness,
 }
+
+impl Gate {
+    /// Return the value of the specified selector of the gate.
+    ///
+    /// The public inputs are kept by the composer rather than by the gates, so
+    /// [`Selector::PublicInput`] always yields zero.
+    pub const fn selector(&self, sel: Selector) -> BlsScalar {
+        match sel {
+            Selector::Multiplication => self.q_m,
+            Selector::Left => self.q_l,
+            Selector::Right => self.q_r,
+            Selector::Output => self.q_o,
+            Selector::Fourth => self.q_f,
+            Selector::Constant => self.q_c,
+            Selector::PublicInput => BlsScalar::zero(),
+            Selector::Arithmetic => self.q_arith,
+            Selector::Range => self.q_range,
+            Selector::Logic => self.q_logic,
+            Selector::GroupAddFixedBase => self.q_fixed_group_add,
+            Selector::GroupAddVariableBase => self.q_variable_group_add,
+        }
+    }
+
+    /// Return the witness of the specified wire of the gate.
+    pub const fn wire(&self, w: WiredWitness) -> Witness {
+        match w {
+            WiredWitness::A => self.a,
+            WiredWitness::B => self.b,
+            WiredWitness::C => self.c,
+            WiredWitness::D => self.d,
+        }
+    }
+
+    /// Whether the identity of the gate vanishes for any witness, apart from
+    /// its public input and custom gate, which are kept by the composer.
+    ///
+    /// The arithmetic coefficients, `q_c` included, are only checked when
+    /// `q_arith` is set.
+    pub(crate) fn is_trivial(&self) -> bool {
+        let zero = BlsScalar::zero();
+
+        let arithmetic = self.q_arith == zero
+            || [self.q_m, self.q_l, self.q_r, self.q_o, self.q_f, self.q_c]
+                .into_iter()
+                .all(|q| q == zero);
+
+        arithmetic && !self.reads_next()
+    }
+
+    /// Whether the identity of the gate reads the shifted wires of the next
+    /// gate, leaving aside the custom gates.
+    pub(crate) fn reads_next(&self) -> bool {
+        let zero = BlsScalar::zero();
+
+        [
+            self.q_range,
+            self.q_logic,
+            self.q_fixed_group_add,
+            self.q_variable_group_add,
+        ]
+        .into_iter()
+        .any(|q| q != zero)
+    }
+}
+
+impl From<&Constraint> for Gate {
+    /// Gate with the selectors and wires of the constraint.
+    ///
+    /// The public input of the constraint is dropped, as it is kept by the
+    /// composer rather than by the gates.
+    fn from(constraint: &Constraint) -> Self {
+        Self {
+            q_m: *constraint.coeff(Selector::Multiplication),
+            q_l: *constraint.coeff(Selector::Left),
+            q_r: *constraint.coeff(Selector::Right),
+            q_o: *constraint.coeff(Selector::Output),
+            q_f: *constraint.coeff(Selector::Fourth),
+            q_c: *constraint.coeff(Selector::Constant),
+            q_arith: *constraint.coeff(Selector::Arithmetic),
+            q_range: *constraint.coeff(Selector::Range),
+            q_logic: *constraint.coeff(Selector::Logic),
+            q_fixed_group_add: *constraint.coeff(Selector::GroupAddFixedBase),
+            q_variable_group_add: *constraint
+                .coeff(Selector::GroupAddVariableBase),
+            a: constraint.witness(WiredWitness::A),
+            b: constraint.witness(WiredWitness::B),
+            c: constraint.witness(WiredWitness::C),
+            d: constraint.witness(WiredWitness::D),
+        }
+    }
+}
+
+impl From<&Gate> for Constraint {
+    /// Constraint with the selectors and wires of the gate, without a public
+    /// input.
+    fn from(gate: &Gate) -> Self {
+        let selectors = [
+            Selector::Multiplication,
+            Selector::Left,
+            Selector::Right,
+            Selector::Output,
+            Selector::Fourth,
+            Selector::Constant,
+            Selector::Arithmetic,
+            Selector::Range,
+            Selector::Logic,
+            Selector::GroupAddFixedBase,
+            Selector::GroupAddVariableBase,
+        ];
+        let wires = [
+            WiredWitness::A,
+            WiredWitness::B,
+            WiredWitness::C,
+            WiredWitness::D,
+        ];
+
+        let mut constraint = selectors.into_iter().fold(
+            Constraint::new(),
+            |constraint, selector| {
+                constraint.set(selector, gate.selector(selector))
+            },
+        );
+        wires
+            .into_iter()
+            .for_each(|w| constraint.set_witness(w, gate.wire(w)));
+
+        constraint
+    }
+}
@@ -97,10 +97,45 @@ impl Permutation {
         vec_wire_data.push(wire_data);
     }
 
+    /// Remove the witnesses from the index `witnesses` onwards, and the wires
+    /// of the gates from the index `gates` onwards
+    pub(crate) fn truncate(&mut self, witnesses: usize, gates: usize) {
+        self.witness_map
+            .retain(|witness, _| witness.index() < witnesses);
+        self.witness_map.values_mut().for_each(|wires| {
+            wires.retain(|wire| match *wire {
+                WireData::Left(index)
+                | WireData::Right(index)
+                | WireData::Output(index)
+                | WireData::Fourth(index) => index < gates,
+            })
+        });
+    }
+
+    /// Move the wires of the gate of index `i` to the gate of index
+    /// `gates[i]`, removing the wires of the gates mapped to `None`
+    pub(crate) fn remap_gates(&mut self, gates: &[Option<usize>]) {
+        self.witness_map.values_mut().for_each(|wires| {
+            *wires = wires
+                .iter()
+                .filter_map(|wire| match *wire {
+                    WireData::Left(index) => gates[index].map(WireData::Left),
+                    WireData::Right(index) => gates[index].map(WireData::Right),
+                    WireData::Output(index) => {
+                        gates[index].map(WireData::Output)
+                    }
+                    WireData::Fourth(index) => {
+                        gates[index].map(WireData::Fourth)
+                    }
+                })
+                .collect();
+        });
+    }
+
     // Performs shift by one permutation and computes sigma_1, sigma_2 and
     // sigma_3, sigma_4 permutations from the witness maps
     pub(super) fn compute_sigma_permutations(
-        &mut self,
+        &self,
         n: usize,
     ) -> [Vec<WireData>; 4] {
         let sigma_1: Vec<_> = (0..n).map(WireData::Left).collect();
@@ -0,0 +1,192 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::Serializable;
+use msgpacker::{MsgPacker, Packable, Unpackable};
+
+use alloc::vec::Vec;
+
+use super::{BlsScalar, Composer, Constraint, Error, Gate, Witness};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, MsgPacker)]
+struct SerializedGate {
+    q_m: [u8; BlsScalar::SIZE],
+    q_l: [u8; BlsScalar::SIZE],
+    q_r: [u8; BlsScalar::SIZE],
+    q_o: [u8; BlsScalar::SIZE],
+    q_f: [u8; BlsScalar::SIZE],
+    q_c: [u8; BlsScalar::SIZE],
+    q_arith: [u8; BlsScalar::SIZE],
+    q_range: [u8; BlsScalar::SIZE],
+    q_logic: [u8; BlsScalar::SIZE],
+    q_fixed_group_add: [u8; BlsScalar::SIZE],
+    q_variable_group_add: [u8; BlsScalar::SIZE],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, MsgPacker)]
+struct SerializedCustomSelector {
+    id: usize,
+    gate: usize,
+}
+
+/// Lossless representation of the constraint system of a [`Composer`].
+///
+/// Unlike the compressed circuits, the selectors of every gate are kept
+/// as-is, along with the custom gates they enable. The permutation isn't
+/// stored, as it's rebuilt from the wires of the gates.
+///
+/// Only the witnesses up to the last one wired to a gate are counted, as the
+/// ones past it don't take part in the constraint system.
+#[derive(Debug, Clone, PartialEq, Eq, MsgPacker)]
+pub struct SerializedCircuit {
+    witnesses: u64,
+    public_inputs: Vec<usize>,
+    custom_selectors: Vec<SerializedCustomSelector>,
+    gates: Vec<SerializedGate>,
+}
+
+impl SerializedCircuit {
+    pub fn from_composer(composer: &Composer) -> Vec<u8> {
+        let mut public_inputs: Vec<_> =
+            composer.public_inputs.keys().copied().collect();
+        public_inputs.sort();
+
+        let custom_selectors = composer
+            .custom_selectors
+            .iter()
+            .map(|&(id, gate)| SerializedCustomSelector { id, gate })
+            .collect();
+
+        let gates = composer
+            .constraints
+            .iter()
+            .map(|gate| SerializedGate {
+                q_m: gate.q_m.to_bytes(),
+                q_l: gate.q_l.to_bytes(),
+                q_r: gate.q_r.to_bytes(),
+                q_o: gate.q_o.to_bytes(),
+                q_f: gate.q_f.to_bytes(),
+                q_c: gate.q_c.to_bytes(),
+                q_arith: gate.q_arith.to_bytes(),
+                q_range: gate.q_range.to_bytes(),
+                q_logic: gate.q_logic.to_bytes(),
+                q_fixed_group_add: gate.q_fixed_group_add.to_bytes(),
+                q_variable_group_add: gate.q_variable_group_add.to_bytes(),
+                a: gate.a.index(),
+                b: gate.b.index(),
+                c: gate.c.index(),
+                d: gate.d.index(),
+            })
+            .collect::<Vec<_>>();
+
+        let serialized = Self {
+            witnesses: Self::wired_witnesses(&gates) as u64,
+            public_inputs,
+            custom_selectors,
+            gates,
+        };
+
+        let mut buf = Vec::new();
+        serialized.pack(&mut buf);
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Composer, Error> {
+        let (
+            _,
+            Self {
+                witnesses,
+                public_inputs,
+                custom_selectors,
+                gates,
+            },
+        ) = Self::unpack(bytes).map_err(|_| Error::InvalidSerializedCircuit)?;
+
+        // every witness is appended to the composer, so their count is bound
+        // by the wires of the gates rather than trusted
+        let witnesses = usize::try_from(witnesses)
+            .ok()
+            .filter(|w| *w <= Self::wired_witnesses(&gates))
+            .ok_or(Error::InvalidSerializedCircuit)?;
+
+        let scalar = |bytes: &[u8; BlsScalar::SIZE]| {
+            Option::<BlsScalar>::from(BlsScalar::from_bytes(bytes))
+                .ok_or(Error::BlsScalarMalformed)
+        };
+        let witness = |index: usize| {
+            (index < witnesses)
+                .then(|| Witness::new(index))
+                .ok_or(Error::InvalidSerializedCircuit)
+        };
+
+        // the positions of the public inputs are strictly increasing, to be
+        // matched in order while appending the gates
+        if public_inputs.windows(2).any(|w| w[0] >= w[1])
+            || public_inputs.last().map_or(false, |pi| *pi >= gates.len())
+        {
+            return Err(Error::InvalidSerializedCircuit);
+        }
+        if custom_selectors.iter().any(|s| s.gate >= gates.len()) {
+            return Err(Error::InvalidSerializedCircuit);
+        }
+
+        // the serialized gates include the constant and dummy gates of the
+        // original composer, so we don't initialize it
+        let mut composer = Composer::uninitialized();
+
+        (0..witnesses).for_each(|_| {
+            composer.append_witness(BlsScalar::zero());
+        });
+
+        let mut pi = public_inputs.iter().peekable();
+        for (i, gate) in gates.iter().enumerate() {
+            let gate = Gate {
+                q_m: scalar(&gate.q_m)?,
+                q_l: scalar(&gate.q_l)?,
+                q_r: scalar(&gate.q_r)?,
+                q_o: scalar(&gate.q_o)?,
+                q_f: scalar(&gate.q_f)?,
+                q_c: scalar(&gate.q_c)?,
+                q_arith: scalar(&gate.q_arith)?,
+                q_range: scalar(&gate.q_range)?,
+                q_logic: scalar(&gate.q_logic)?,
+                q_fixed_group_add: scalar(&gate.q_fixed_group_add)?,
+                q_variable_group_add: scalar(&gate.q_variable_group_add)?,
+                a: witness(gate.a)?,
+                b: witness(gate.b)?,
+                c: witness(gate.c)?,
+                d: witness(gate.d)?,
+            };
+
+            let mut constraint = Constraint::from(&gate);
+            if pi.next_if_eq(&&i).is_some() {
+                constraint = constraint.public(BlsScalar::zero());
+            }
+
+            composer.append_custom_gate(constraint);
+        }
+
+        composer.custom_selectors = custom_selectors
+            .into_iter()
+            .map(|SerializedCustomSelector { id, gate }| (id, gate))
+            .collect();
+
+        Ok(composer)
+    }
+
+    /// Number of witnesses up to the last one wired to the gates
+    fn wired_witnesses(gates: &[SerializedGate]) -> usize {
+        gates
+            .iter()
+            .flat_map(|gate| [gate.a, gate.b, gate.c, gate.d])
+            .max()
+            .map_or(0, |index| index.saturating_add(1))
+    }
+}
@@ -0,0 +1,74 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::prelude::{Composer, Selector};
+
+/// Cost profile of a circuit, computed by [`Composer::stats`]
+///
+/// A gate is counted once for every widget whose selector it enables, so the
+/// counts by type don't necessarily add up to the total.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitStats {
+    /// Total number of gates
+    pub gates: usize,
+    /// Gates with the arithmetic selector set
+    pub arithmetic: usize,
+    /// Gates with the range selector set
+    pub range: usize,
+    /// Gates with the logic selector set
+    pub logic: usize,
+    /// Gates with the fixed base curve addition selector set
+    pub fixed_base: usize,
+    /// Gates with the variable base curve addition selector set
+    pub variable_base: usize,
+    /// Gates enabling a registered custom gate
+    pub custom: usize,
+    /// Number of distinct witnesses wired to the gates
+    pub witnesses: usize,
+    /// Maximum number of wires a single witness is copied to in the
+    /// permutation.
+    ///
+    /// Unused wires are bound to [`Composer::ZERO`], which usually has the
+    /// largest fan-out.
+    pub max_fan_out: usize,
+}
+
+impl CircuitStats {
+    pub(crate) fn from_composer(composer: &Composer) -> Self {
+        let mut stats = Self {
+            gates: composer.constraints(),
+            custom: composer.custom_selectors.len(),
+            ..Self::default()
+        };
+
+        let is_set = |s: BlsScalar| s != BlsScalar::zero();
+
+        composer.gates().iter().for_each(|gate| {
+            stats.arithmetic +=
+                is_set(gate.selector(Selector::Arithmetic)) as usize;
+            stats.range += is_set(gate.selector(Selector::Range)) as usize;
+            stats.logic += is_set(gate.selector(Selector::Logic)) as usize;
+            stats.fixed_base +=
+                is_set(gate.selector(Selector::GroupAddFixedBase)) as usize;
+            stats.variable_base +=
+                is_set(gate.selector(Selector::GroupAddVariableBase)) as usize;
+        });
+
+        composer
+            .perm
+            .witness_map
+            .values()
+            .filter(|wires| !wires.is_empty())
+            .for_each(|wires| {
+                stats.witnesses += 1;
+                stats.max_fan_out = stats.max_fan_out.max(wires.len());
+            });
+
+        stats
+    }
+}
@@ -7,81 +7,259 @@
 //! Debugger module
 
 use std::env;
+use std::io;
 use std::path::PathBuf;
 
 use dusk_bls12_381::BlsScalar;
 use dusk_cdf::{
     BaseConfig, Config, EncodableConstraint, EncodableSource, EncodableWitness,
-    Encoder, EncoderContextFileProvider, Polynomial, Selectors, WiredWitnesses,
+    Encoder, EncoderContextFileProvider, EncoderContextProvider, Polynomial,
+    Selectors, WiredWitnesses,
 };
+use hashbrown::HashMap;
 
-use crate::composer::{Constraint, Selector, WiredWitness, Witness};
-use crate::runtime::RuntimeEvent;
+use crate::composer::{
+    Constraint, GateFamily, Selector, WiredWitness, Witness,
+};
+use crate::runtime::{RuntimeEvent, RuntimeListener};
+
+/// Filesystem provider of the source contents that maps the unresolved
+/// sources, such as the ones of the constraints that weren't sampled, to
+/// empty contents
+#[derive(Debug, Default, Clone, Copy)]
+struct SourceProvider;
+
+impl EncoderContextProvider for SourceProvider {
+    fn contents<P>(&mut self, path: P) -> io::Result<String>
+    where
+        P: AsRef<str>,
+    {
+        match path.as_ref() {
+            "" => Ok(String::new()),
+            path => EncoderContextFileProvider.contents(path),
+        }
+    }
+}
 
 /// PLONK debugger
+///
+/// Setting `CDF_SAMPLE_RATE` to `N` resolves the caller source of, and
+/// encodes, only one in `N` constraints. Unsatisfied constraints are always
+/// encoded, but the ones that weren't sampled lose their source provenance.
 #[derive(Debug, Clone)]
 pub(crate) struct Debugger {
     witnesses: Vec<(EncodableSource, Witness, BlsScalar)>,
     constraints: Vec<(EncodableSource, Constraint)>,
+    sample_rate: usize,
+    callers: HashMap<usize, Option<EncodableSource>>,
 }
 
 impl Debugger {
     /// Resolver the caller function
-    fn resolve_caller() -> EncodableSource {
+    ///
+    /// The source resolved for each frame is cached by instruction pointer,
+    /// so call sites appending many witnesses or constraints are symbolized
+    /// only once.
+    fn resolve_caller(&mut self) -> EncodableSource {
+        let callers = &mut self.callers;
         let mut source = None;
 
         backtrace::trace(|frame| {
-            // Resolve this instruction pointer to a symbol name
-            backtrace::resolve_frame(frame, |symbol| {
-                if symbol
-                    .name()
-                    .map(|n| n.to_string())
-                    .filter(|s| !s.starts_with("backtrace::"))
-                    .filter(|s| !s.starts_with("dusk_plonk::"))
-                    .filter(|s| !s.starts_with("core::"))
-                    .filter(|s| !s.starts_with("std::"))
-                    .is_some()
-                {
-                    if let Some(path) = symbol.filename() {
-                        let line = symbol.lineno().unwrap_or_default() as u64;
-                        let col = symbol.colno().unwrap_or_default() as u64;
-                        let path = path
-                            .canonicalize()
-                            .unwrap_or_default()
-                            .display()
-                            .to_string();
-
-                        source.replace(EncodableSource::new(line, col, path));
+            let ip = frame.ip() as usize;
+
+            let frame_source = callers.entry(ip).or_insert_with(|| {
+                let mut source = None;
+
+                // Resolve this instruction pointer to a symbol name
+                backtrace::resolve_frame(frame, |symbol| {
+                    if symbol
+                        .name()
+                        .map(|n| n.to_string())
+                        .filter(|s| !s.starts_with("backtrace::"))
+                        .filter(|s| !s.starts_with("dusk_plonk::"))
+                        .filter(|s| !s.starts_with("<dusk_plonk::"))
+                        .filter(|s| !s.starts_with("core::"))
+                        .filter(|s| !s.starts_with("std::"))
+                        .is_some()
+                    {
+                        if let Some(path) = symbol.filename() {
+                            let line =
+                                symbol.lineno().unwrap_or_default() as u64;
+                            let col = symbol.colno().unwrap_or_default() as u64;
+                            let path = path
+                                .canonicalize()
+                                .unwrap_or_default()
+                                .display()
+                                .to_string();
+
+                            source
+                                .replace(EncodableSource::new(line, col, path));
+                        }
                     }
-                }
+                });
+
+                source
             });
 
+            source = frame_source.clone();
             source.is_none()
         });
 
         source.unwrap_or_default()
     }
 
-    fn write_output(&self) {
-        let path = match env::var("CDF_OUTPUT") {
-            Ok(path) => PathBuf::from(path),
-            Err(env::VarError::NotPresent) => return (),
-            Err(env::VarError::NotUnicode(_)) => {
-                eprintln!("the provided `CDF_OUTPUT` isn't valid unicode");
-                return ();
-            }
+    /// Value assigned to a witness, or zero if it wasn't appended
+    fn value(&self, witness: Witness) -> BlsScalar {
+        self.witnesses
+            .get(witness.index())
+            .map(|(_, _, v)| *v)
+            .unwrap_or_default()
+    }
+
+    /// Evaluate the identities of the gate families active in the constraint
+    /// `id`, returning the first one that isn't satisfied by the witnesses.
+    fn failing_family(&self, id: usize) -> Option<GateFamily> {
+        let (_, constraint) = self.constraints.get(id)?;
+        let next = self.constraints.get(id + 1).map(|(_, c)| c);
+
+        let wires = |c: &Constraint| {
+            [
+                WiredWitness::A,
+                WiredWitness::B,
+                WiredWitness::C,
+                WiredWitness::D,
+            ]
+            .map(|w| self.value(c.witness(w)))
         };
 
-        let witnesses = self.witnesses.iter().map(|(source, w, value)| {
+        GateFamily::failing(
+            |s| *constraint.coeff(s),
+            *constraint.coeff(Selector::PublicInput),
+            wires(constraint),
+            next.map(wires).unwrap_or_default(),
+        )
+    }
+
+    /// Whether the constraint `id` reads the shifted wires of the next one
+    fn reads_next(&self, id: usize) -> bool {
+        self.constraints.get(id).map_or(false, |(_, constraint)| {
+            [
+                Selector::Range,
+                Selector::Logic,
+                Selector::GroupAddFixedBase,
+                Selector::GroupAddVariableBase,
+            ]
+            .into_iter()
+            .any(|s| *constraint.coeff(s) != BlsScalar::zero())
+        })
+    }
+
+    /// Indexes of the constraints in the dependency cone of the constraint
+    /// `id`, in ascending order.
+    ///
+    /// The cone holds the constraint itself and, transitively, the first
+    /// constraint wiring each of the witnesses of a constraint in the cone,
+    /// as the one assigning it. The constraints chained by the shifted wires
+    /// of range, logic and curve addition gates are followed both ways, so a
+    /// gadget spanning several rows is included as a whole.
+    pub(crate) fn failing_cone(&self, id: usize) -> Vec<usize> {
+        if id >= self.constraints.len() {
+            return Vec::new();
+        }
+
+        let mut first_wiring: HashMap<Witness, usize> = HashMap::new();
+        self.constraints.iter().enumerate().for_each(
+            |(index, (_, constraint))| {
+                [
+                    WiredWitness::A,
+                    WiredWitness::B,
+                    WiredWitness::C,
+                    WiredWitness::D,
+                ]
+                .into_iter()
+                .for_each(|w| {
+                    first_wiring.entry(constraint.witness(w)).or_insert(index);
+                });
+            },
+        );
+
+        let mut cone = vec![false; self.constraints.len()];
+        let mut pending = vec![id];
+        while let Some(index) = pending.pop() {
+            if cone[index] {
+                continue;
+            }
+            cone[index] = true;
+
+            let (_, constraint) = &self.constraints[index];
+            pending.extend(
+                [
+                    WiredWitness::A,
+                    WiredWitness::B,
+                    WiredWitness::C,
+                    WiredWitness::D,
+                ]
+                .into_iter()
+                .filter_map(|w| first_wiring.get(&constraint.witness(w))),
+            );
+
+            if self.reads_next(index) && index + 1 < cone.len() {
+                pending.push(index + 1);
+            }
+            if index > 0 && self.reads_next(index - 1) {
+                pending.push(index - 1);
+            }
+        }
+
+        cone.into_iter()
+            .enumerate()
+            .filter_map(|(index, included)| included.then_some(index))
+            .collect()
+    }
+
+    /// Constraints that aren't satisfied by the appended witnesses, along
+    /// with the gate family that failed.
+    fn unsatisfied(
+        &self,
+    ) -> impl Iterator<Item = (usize, &EncodableSource, GateFamily)> + '_ {
+        self.constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(id, (source, _))| {
+                self.failing_family(id).map(|family| (id, source, family))
+            })
+    }
+
+    /// Encodable representation of the appended witnesses
+    fn encodable_witnesses(
+        &self,
+    ) -> impl ExactSizeIterator<Item = EncodableWitness> + '_ {
+        self.witnesses.iter().map(|(source, w, value)| {
             let id = w.index();
             let value = value.to_bytes().into();
             let source = source.clone();
 
             EncodableWitness::new(id, None, value, source)
-        });
+        })
+    }
+
+    /// Encodable representation of the appended constraints, along with
+    /// their evaluation against the witness values.
+    ///
+    /// When sampling, only the sampled and the unsatisfied constraints are
+    /// encoded, keeping their index in the circuit as id.
+    fn encodable_constraints(
+        &self,
+    ) -> impl ExactSizeIterator<Item = EncodableConstraint> + '_ {
+        self.constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(id, (source, constraint))| {
+                let evaluation = self.failing_family(id).is_none();
+                if evaluation && !self.is_sampled(id) {
+                    return None;
+                }
 
-        let constraints = self.constraints.iter().enumerate().map(
-            |(id, (source, constraint))| {
                 let source = source.clone();
 
                 let qm = constraint.coeff(Selector::Multiplication);
@@ -89,7 +267,7 @@ impl Debugger {
                 let qr = constraint.coeff(Selector::Right);
                 let qo = constraint.coeff(Selector::Output);
                 let qf = constraint.coeff(Selector::Fourth);
-                let qc = constraint.coeff(Selector::Constant);
+                let qc = *constraint.coeff(Selector::Constant);
                 let pi = constraint.coeff(Selector::PublicInput);
                 let qarith = constraint.coeff(Selector::Arithmetic);
                 let qlogic = constraint.coeff(Selector::Logic);
@@ -106,42 +284,6 @@ impl Debugger {
                     d: constraint.witness(WiredWitness::D).index(),
                 };
 
-                let wa = self
-                    .witnesses
-                    .get(witnesses.a)
-                    .map(|(_, _, v)| *v)
-                    .unwrap_or_default();
-
-                let wb = self
-                    .witnesses
-                    .get(witnesses.b)
-                    .map(|(_, _, v)| *v)
-                    .unwrap_or_default();
-
-                let wc = self
-                    .witnesses
-                    // TODO: change by 'c' in debugger crate
-                    .get(witnesses.o)
-                    .map(|(_, _, v)| *v)
-                    .unwrap_or_default();
-
-                let wd = self
-                    .witnesses
-                    .get(witnesses.d)
-                    .map(|(_, _, v)| *v)
-                    .unwrap_or_default();
-
-                // TODO check arith, range, logic & ecc wires
-                let evaluation = qm * wa * wb
-                    + ql * wa
-                    + qr * wb
-                    + qo * wc
-                    + qf * wd
-                    + qc
-                    + pi;
-
-                let evaluation = evaluation == BlsScalar::zero();
-
                 let selectors = Selectors {
                     qm: qm.to_bytes().into(),
                     ql: ql.to_bytes().into(),
@@ -161,17 +303,72 @@ impl Debugger {
                 let polynomial =
                     Polynomial::new(selectors, witnesses, evaluation);
 
-                EncodableConstraint::new(id, polynomial, source)
-            },
+                Some(EncodableConstraint::new(id, polynomial, source))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Encode the circuit description into an in-memory buffer, using the
+    /// same format as the file written to `CDF_OUTPUT`.
+    ///
+    /// Falls back to the default configuration if the CDF config file can't
+    /// be loaded.
+    pub(crate) fn write_to_vec(&self) -> io::Result<Vec<u8>> {
+        let config = Config::load().unwrap_or_default();
+        let mut encoder = Encoder::init_cursor(
+            config,
+            self.encodable_witnesses(),
+            self.encodable_constraints(),
         );
 
+        encoder.write_all(SourceProvider)?;
+
+        Ok(encoder.into_inner().into_inner())
+    }
+
+    fn write_output(&self) {
+        let path = match env::var("CDF_OUTPUT") {
+            Ok(path) => PathBuf::from(path),
+            Err(env::VarError::NotPresent) => return (),
+            Err(env::VarError::NotUnicode(_)) => {
+                eprintln!("the provided `CDF_OUTPUT` isn't valid unicode");
+                return ();
+            }
+        };
+
+        // the CDF format only encodes whether a constraint is satisfied, so
+        // the failing gate family is reported alongside it
+        for (id, source, family) in self.unsatisfied() {
+            eprintln!(
+                "constraint {} at {}:{}:{} doesn't satisfy the {} identity",
+                id,
+                source.path(),
+                source.line(),
+                source.col(),
+                family
+            );
+        }
+
+        let constraints = self.constraints.len();
+        let padded_size = constraints.next_power_of_two();
+        if padded_size - constraints > padded_size / 4 {
+            eprintln!(
+                "the circuit of {} constraints is padded to {}, leaving {} rows unused",
+                constraints,
+                padded_size,
+                padded_size - constraints
+            );
+        }
+
+        let witnesses = self.encodable_witnesses();
+        let constraints = self.encodable_constraints();
+
         if let Err(e) = Config::load()
             .and_then(|config| {
                 Encoder::init_file(config, witnesses, constraints, &path)
             })
-            .and_then(|mut c| {
-                c.write_all(EncoderContextFileProvider::default())
-            })
+            .and_then(|mut c| c.write_all(SourceProvider))
         {
             eprintln!(
                 "failed to output CDF file to '{}': {}",
@@ -181,26 +378,92 @@ impl Debugger {
         }
     }
 
+    /// Read the sampling rate of the constraints from `CDF_SAMPLE_RATE`,
+    /// defaulting to encode every constraint.
+    fn load_sample_rate() -> usize {
+        match env::var("CDF_SAMPLE_RATE") {
+            Ok(rate) => match rate.parse::<usize>() {
+                Ok(rate) if rate > 0 => rate,
+                _ => {
+                    eprintln!(
+                        "the provided `CDF_SAMPLE_RATE` isn't a positive integer"
+                    );
+                    1
+                }
+            },
+            Err(env::VarError::NotPresent) => 1,
+            Err(env::VarError::NotUnicode(_)) => {
+                eprintln!("the provided `CDF_SAMPLE_RATE` isn't valid unicode");
+                1
+            }
+        }
+    }
+
+    /// Check if the constraint `id` is sampled
+    const fn is_sampled(&self, id: usize) -> bool {
+        id % self.sample_rate == 0
+    }
+
+    /// Number of witnesses and constraints recorded
+    pub(crate) fn len(&self) -> (usize, usize) {
+        (self.witnesses.len(), self.constraints.len())
+    }
+
+    /// Discard the witnesses and constraints recorded after the given number
+    /// of each
+    pub(crate) fn truncate(
+        &mut self,
+        (witnesses, constraints): (usize, usize),
+    ) {
+        self.witnesses.truncate(witnesses);
+        self.constraints.truncate(constraints);
+    }
+
+    /// Keep only the constraints of the gates flagged in `gates`, indexed as
+    /// the gates of the composer
+    pub(crate) fn retain_constraints(&mut self, gates: &[bool]) {
+        let mut index = 0;
+        self.constraints.retain(|_| {
+            let keep = gates.get(index).copied().unwrap_or(true);
+            index += 1;
+            keep
+        });
+    }
+
     pub(crate) fn new() -> Self {
         Self {
             witnesses: Vec::new(),
             constraints: Vec::new(),
+            sample_rate: Self::load_sample_rate(),
+            callers: HashMap::new(),
         }
     }
+}
 
-    pub(crate) fn event(&mut self, event: RuntimeEvent) {
-        match event {
+impl RuntimeListener for Debugger {
+    fn on_event(&mut self, event: &RuntimeEvent) {
+        match *event {
             RuntimeEvent::WitnessAppended { w, v } => {
-                self.witnesses.push((Self::resolve_caller(), w, v));
+                let source = self.resolve_caller();
+                self.witnesses.push((source, w, v));
             }
 
             RuntimeEvent::ConstraintAppended { c } => {
-                self.constraints.push((Self::resolve_caller(), c));
+                let source = if self.is_sampled(self.constraints.len()) {
+                    self.resolve_caller()
+                } else {
+                    EncodableSource::default()
+                };
+
+                self.constraints.push((source, c));
             }
 
             RuntimeEvent::ProofFinished => {
                 self.write_output();
             }
+
+            // the debugger evaluates the gates on its own when writing
+            RuntimeEvent::GateEvaluated { .. } => (),
         }
     }
 }
@@ -9,7 +9,11 @@
 use dusk_bytes::Error as DuskBytesError;
 
 /// Defines all possible errors that can be encountered in PLONK.
+///
+/// None of the variants allocate, so the errors are available without the
+/// `alloc` feature.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     // FFT errors
     /// This error occurs when an error triggers on any of the fft module
@@ -95,11 +99,166 @@ pub enum Error {
     },
     /// The provided compressed circuit bytes representation is invalid.
     InvalidCompressedCircuit,
+    /// The provided serialized circuit bytes representation is invalid.
+    InvalidSerializedCircuit,
+    /// A field element that has to be inverted during the verification is
+    /// zero.
+    NonInvertibleDenominator,
+    /// An evaluation of the proof was never set
+    MissingProofEvaluation {
+        /// Name of the missing evaluation
+        name: &'static str,
+    },
+    /// The inputs provided for a batch verification don't have the same
+    /// length as the batch of proofs
+    InconsistentBatchLen {
+        /// Expected value
+        expected: usize,
+        /// Provided value
+        provided: usize,
+    },
+    /// The quotient polynomial can't be split into the requested number of
+    /// chunks
+    UnsupportedQuotientChunks {
+        /// Requested number of chunks
+        chunks: usize,
+        /// Highest number of chunks supported by the circuit
+        max: usize,
+    },
+    /// A proof doesn't commit to as many quotient chunks as its circuit
+    /// splits the quotient into
+    InconsistentQuotientChunks {
+        /// Number of chunks of the circuit
+        expected: usize,
+        /// Number of chunks of the proof
+        got: usize,
+    },
+    /// The serialized proof starts with an unknown version tag
+    UnsupportedProofVersion {
+        /// Tag of the serialized proof
+        version: u8,
+    },
+    /// A gate enables a custom gate that isn't registered by the circuit
+    UnregisteredCustomGate {
+        /// Index of the custom gate
+        id: usize,
+    },
+    /// A custom gate registered by the circuit doesn't match the one of a
+    /// serialized verifier
+    InconsistentCustomGate {
+        /// Index of the custom gate
+        id: usize,
+    },
+    /// The circuit enables custom gates, which the compressed circuit format
+    /// can't represent
+    CustomGatesNotCompressible,
+    /// The verifier of a proof added to an aggregation doesn't share the
+    /// opening key of the aggregated proofs
+    InconsistentOpeningKey,
+    /// The wire polynomials can't be blinded with the requested hiding
+    /// degree
+    HidingDegreeTooLarge {
+        /// Requested hiding degree
+        hiding_degree: usize,
+        /// Highest hiding degree supported by the circuit
+        max: usize,
+    },
+    /// A public input is declared at a position out of the evaluation domain
+    PublicInputOutOfDomain {
+        /// Position of the public input
+        index: usize,
+        /// Size of the evaluation domain
+        size: usize,
+    },
+    /// A commitment of a proof isn't the encoding of a point on the curve
+    ProofCommitmentNotOnCurve {
+        /// Label of the commitment
+        label: &'static str,
+    },
+    /// A commitment of a proof is a point out of the prime order subgroup
+    ProofCommitmentNotInSubgroup {
+        /// Label of the commitment
+        label: &'static str,
+    },
+    /// The evaluation challenge of a proof is an element of the evaluation
+    /// domain, where the vanishing polynomial is zero
+    EvaluationChallengeInDomain,
+    /// A gate of the circuit isn't satisfied by the witnesses
+    UnsatisfiedConstraint {
+        /// Index of the gate in the circuit
+        gate_index: usize,
+        /// Selector of the gate identity that isn't satisfied
+        selector: &'static str,
+    },
+    /// An item of a batch check is invalid
+    InvalidBatchItem {
+        /// Index of the first invalid item of the batch
+        index: usize,
+    },
+    /// The serialized key doesn't start with the expected magic prefix
+    InvalidKeyHeader,
+    /// The serialized key was produced with an incompatible layout
+    IncompatibleKeyVersion {
+        /// Version of the serialized key
+        found: u16,
+        /// Version supported by this crate
+        expected: u16,
+    },
+    /// A gadget was requested for more bits than it supports
+    BitLengthTooLarge {
+        /// Requested bit length
+        num_bits: usize,
+        /// Highest supported bit length
+        max: usize,
+    },
+    /// The circuit exceeds the largest evaluation domain of the scalar field
+    CircuitTooLarge {
+        /// Requested size of the circuit
+        requested: usize,
+        /// Largest supported size of the circuit
+        max_supported: usize,
+    },
+    /// The output of a powers-of-tau ceremony doesn't provide enough powers
+    /// for the requested degree
+    InsufficientPowersOfTau {
+        /// Number of G1 powers required for the degree
+        needed: usize,
+        /// Number of G1 powers provided by the ceremony
+        available: usize,
+    },
+    /// The commit key doesn't support the degree of the polynomials of the
+    /// circuit
+    InsufficientSRS {
+        /// Degree required by the circuit
+        needed: usize,
+        /// Highest degree supported by the commit key
+        available: usize,
+    },
+    /// A public input is placed at a gate that doesn't declare one in the
+    /// circuit description
+    UndeclaredPublicInput {
+        /// Index of the gate
+        index: usize,
+    },
+    /// The constant public inputs were built by the verifier of another
+    /// circuit
+    InconsistentConstantPublicInputs,
+    /// An encoded public input isn't the canonical encoding of a scalar
+    InvalidPublicInputEncoding {
+        /// Position of the public input
+        index: usize,
+    },
+    /// The bytes of a proof don't have the length of a serialized proof
+    InvalidProofLength {
+        /// Length of a serialized proof
+        expected: usize,
+        /// Length of the provided bytes
+        got: usize,
+    },
 }
 
-#[cfg(feature = "std")]
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidEvalDomainSize {
                 log_size_of_group,
@@ -165,6 +324,128 @@ impl std::fmt::Display for Error {
                 expected, provided,
             } => write!(f, "The provided public inputs set of length {} doesn't match the processed verifier: {}", provided, expected),
             Self::InvalidCompressedCircuit => write!(f, "invalid compressed circuit"),
+            Self::InvalidSerializedCircuit => write!(f, "invalid serialized circuit"),
+            Self::NonInvertibleDenominator => {
+                write!(f, "cannot invert a zero denominator")
+            }
+            Self::MissingProofEvaluation { name } => {
+                write!(f, "the proof evaluation {} was never set", name)
+            }
+            Self::InconsistentBatchLen {
+                expected, provided,
+            } => write!(f, "The provided batch inputs of length {} don't match the number of proofs: {}", provided, expected),
+            Self::UnsupportedQuotientChunks { chunks, max } => write!(
+                f,
+                "The quotient can't be split into {} chunks, which must be between 4 and {}",
+                chunks, max
+            ),
+            Self::InconsistentQuotientChunks { expected, got } => write!(
+                f,
+                "The proof commits to {} quotient chunks, but the circuit splits its quotient into {}",
+                got, expected
+            ),
+            Self::UnsupportedProofVersion { version } => write!(
+                f,
+                "The serialized proof version {} isn't supported",
+                version
+            ),
+            Self::UnregisteredCustomGate { id } => {
+                write!(f, "The custom gate {} isn't registered by the circuit", id)
+            }
+            Self::InconsistentCustomGate { id } => write!(
+                f,
+                "The custom gate {} doesn't match the one of the serialized verifier",
+                id
+            ),
+            Self::CustomGatesNotCompressible => {
+                write!(f, "circuits with custom gates can't be compressed")
+            }
+            Self::InconsistentOpeningKey => {
+                write!(f, "the opening key of the verifier doesn't match the aggregated proofs")
+            }
+            Self::HidingDegreeTooLarge { hiding_degree, max } => write!(
+                f,
+                "The hiding degree {} exceeds the highest hiding degree {} of the circuit",
+                hiding_degree, max
+            ),
+            Self::PublicInputOutOfDomain { index, size } => write!(
+                f,
+                "The public input at position {} is out of the domain of size {}",
+                index, size
+            ),
+            Self::ProofCommitmentNotOnCurve { label } => write!(
+                f,
+                "The proof commitment {} isn't a point on the curve",
+                label
+            ),
+            Self::ProofCommitmentNotInSubgroup { label } => write!(
+                f,
+                "The proof commitment {} isn't in the prime order subgroup",
+                label
+            ),
+            Self::EvaluationChallengeInDomain => {
+                write!(f, "The evaluation challenge lies in the domain")
+            }
+            Self::UnsatisfiedConstraint {
+                gate_index,
+                selector,
+            } => write!(
+                f,
+                "The gate {} doesn't satisfy the {} identity",
+                gate_index, selector
+            ),
+            Self::InvalidBatchItem { index } => {
+                write!(f, "The item {} of the batch is invalid", index)
+            }
+            Self::InvalidKeyHeader => {
+                write!(f, "the serialized key header is invalid")
+            }
+            Self::IncompatibleKeyVersion { found, expected } => write!(
+                f,
+                "The serialized key version {} doesn't match the supported version {}",
+                found, expected
+            ),
+            Self::BitLengthTooLarge { num_bits, max } => write!(
+                f,
+                "The bit length {} exceeds the supported {} bits",
+                num_bits, max
+            ),
+            Self::CircuitTooLarge {
+                requested,
+                max_supported,
+            } => write!(
+                f,
+                "The circuit of size {} exceeds the supported size {}",
+                requested, max_supported
+            ),
+            Self::InsufficientPowersOfTau { needed, available } => write!(
+                f,
+                "The ceremony provides {} powers of tau, but {} are needed",
+                available, needed
+            ),
+            Self::InsufficientSRS { needed, available } => write!(
+                f,
+                "The commit key supports polynomials up to degree {}, but degree {} is needed",
+                available, needed
+            ),
+            Self::UndeclaredPublicInput { index } => write!(
+                f,
+                "The gate of index {} doesn't declare a public input in the circuit description",
+                index
+            ),
+            Self::InconsistentConstantPublicInputs => {
+                write!(f, "the constant public inputs were built for another circuit")
+            }
+            Self::InvalidPublicInputEncoding { index } => write!(
+                f,
+                "The public input at position {} isn't a canonical scalar encoding",
+                index
+            ),
+            Self::InvalidProofLength { expected, got } => write!(
+                f,
+                "A serialized proof has {} bytes, but {} were provided",
+                expected, got
+            ),
         }
     }
 }
@@ -21,4 +21,4 @@ if #[cfg(feature = "alloc")]
 pub(crate) mod domain;
 
 #[allow(unused_imports)]
-pub(crate) use domain::EvaluationDomain;
+pub use domain::EvaluationDomain;
@@ -26,6 +26,10 @@ use rkyv::{
 /// Defines a domain over which finite field (I)FFTs can be performed. Works
 /// only for fields that have a large multiplicative subgroup of size that is
 /// a power-of-2.
+///
+/// The transforms take vectors of any length: shorter inputs are padded with
+/// zeros up to [`EvaluationDomain::size`], while longer inputs are truncated
+/// to it.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "rkyv-impl",
@@ -33,7 +37,7 @@ use rkyv::{
     archive(bound(serialize = "__S: Serializer + ScratchSpace")),
     archive_attr(derive(CheckBytes))
 )]
-pub(crate) struct EvaluationDomain {
+pub struct EvaluationDomain {
     /// The size of the domain.
     #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
     pub(crate) size: u64,
@@ -117,9 +121,31 @@ pub(crate) mod alloc {
     use rayon::prelude::*;
 
     impl EvaluationDomain {
+        /// Size of the largest domain, bounded by the two-adicity of the
+        /// scalar field.
+        pub const MAX_SIZE: usize = 1 << (TWO_ADACITY - 1);
+
+        /// Check that the domain of a circuit of `n` gates doesn't exceed
+        /// [`Self::MAX_SIZE`].
+        ///
+        /// Returns [`Error::CircuitTooLarge`] otherwise.
+        pub(crate) fn check_circuit_size(n: usize) -> Result<(), Error> {
+            if n > Self::MAX_SIZE {
+                return Err(Error::CircuitTooLarge {
+                    requested: n,
+                    max_supported: Self::MAX_SIZE,
+                });
+            }
+
+            Ok(())
+        }
+
         /// Construct a domain that is large enough for evaluations of a
         /// polynomial having `num_coeffs` coefficients.
-        pub(crate) fn new(num_coeffs: usize) -> Result<Self, Error> {
+        ///
+        /// The size of the domain is `num_coeffs` rounded up to the next
+        /// power of two, and can't exceed the two-adicity of the field.
+        pub fn new(num_coeffs: usize) -> Result<Self, Error> {
             // Compute the size of our evaluation domain
             let size = num_coeffs.next_power_of_two() as u64;
             let log_size_of_group = size.trailing_zeros();
@@ -153,12 +179,16 @@ pub(crate) mod alloc {
         }
 
         /// Return the size of `self`.
-        pub(crate) fn size(&self) -> usize {
+        pub fn size(&self) -> usize {
             self.size as usize
         }
 
-        /// Compute a FFT.
-        pub(crate) fn fft(&self, coeffs: &[BlsScalar]) -> Vec<BlsScalar> {
+        /// Compute a FFT, evaluating the polynomial with the given
+        /// coefficients, in ascending degree, over the elements of the domain.
+        ///
+        /// The coefficients are padded with zeros, or truncated, to the size
+        /// of the domain.
+        pub fn fft(&self, coeffs: &[BlsScalar]) -> Vec<BlsScalar> {
             let mut coeffs = coeffs.to_vec();
             self.fft_in_place(&mut coeffs);
             coeffs
@@ -170,8 +200,12 @@ pub(crate) mod alloc {
             best_fft(coeffs, self.group_gen, self.log_size_of_group)
         }
 
-        /// Compute an IFFT.
-        pub(crate) fn ifft(&self, evals: &[BlsScalar]) -> Vec<BlsScalar> {
+        /// Compute an IFFT, interpolating the coefficients of the polynomial
+        /// with the given evaluations over the elements of the domain.
+        ///
+        /// The evaluations are padded with zeros, or truncated, to the size of
+        /// the domain.
+        pub fn ifft(&self, evals: &[BlsScalar]) -> Vec<BlsScalar> {
             let mut evals = evals.to_vec();
             self.ifft_in_place(&mut evals);
             evals
@@ -198,8 +232,12 @@ pub(crate) mod alloc {
             })
         }
 
-        /// Compute a FFT over a coset of the domain.
-        pub(crate) fn coset_fft(&self, coeffs: &[BlsScalar]) -> Vec<BlsScalar> {
+        /// Compute a FFT over the coset of the domain by the multiplicative
+        /// generator of the field.
+        ///
+        /// The coefficients are padded with zeros, or truncated, to the size
+        /// of the domain.
+        pub fn coset_fft(&self, coeffs: &[BlsScalar]) -> Vec<BlsScalar> {
             let mut coeffs = coeffs.to_vec();
             self.coset_fft_in_place(&mut coeffs);
             coeffs
@@ -212,8 +250,12 @@ pub(crate) mod alloc {
             self.fft_in_place(coeffs);
         }
 
-        /// Compute an IFFT over a coset of the domain.
-        pub(crate) fn coset_ifft(&self, evals: &[BlsScalar]) -> Vec<BlsScalar> {
+        /// Compute an IFFT over the coset of the domain by the multiplicative
+        /// generator of the field, inverting [`EvaluationDomain::coset_fft`].
+        ///
+        /// The evaluations are padded with zeros, or truncated, to the size of
+        /// the domain.
+        pub fn coset_ifft(&self, evals: &[BlsScalar]) -> Vec<BlsScalar> {
             let mut evals = evals.to_vec();
             self.coset_ifft_in_place(&mut evals);
             evals
@@ -402,6 +444,25 @@ pub(crate) mod alloc {
 #[cfg(feature = "alloc")]
 mod tests {
     use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn circuit_too_large() {
+        let max = EvaluationDomain::MAX_SIZE;
+
+        assert!(EvaluationDomain::check_circuit_size(max).is_ok());
+        EvaluationDomain::new(max).expect("the largest domain is supported");
+
+        for n in [max + 1, usize::MAX] {
+            assert_eq!(
+                EvaluationDomain::check_circuit_size(n),
+                Err(Error::CircuitTooLarge {
+                    requested: n,
+                    max_supported: max,
+                })
+            );
+        }
+    }
 
     #[test]
     fn size_of_elements() {
@@ -424,6 +485,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fft_roundtrip() {
+        let domain = EvaluationDomain::new(8).unwrap();
+        let coeffs: Vec<BlsScalar> = (1..=5u64).map(BlsScalar::from).collect();
+
+        // the coefficients are padded with zeros to the size of the domain
+        let evals = domain.fft(&coeffs);
+        assert_eq!(evals.len(), domain.size());
+        for (element, eval) in domain.elements().zip(evals.iter()) {
+            let expected = coeffs
+                .iter()
+                .rev()
+                .fold(BlsScalar::zero(), |acc, coeff| acc * element + coeff);
+            assert_eq!(eval, &expected);
+        }
+
+        let mut padded = coeffs.clone();
+        padded.resize(domain.size(), BlsScalar::zero());
+        assert_eq!(domain.ifft(&evals), padded);
+        assert_eq!(domain.coset_ifft(&domain.coset_fft(&coeffs)), padded);
+        assert_ne!(domain.coset_fft(&coeffs), evals);
+    }
+
     #[test]
     fn dusk_bytes_evaluation_domain_serde() {
         let eval_domain = EvaluationDomain::new(1 << (13 - 1))
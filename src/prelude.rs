@@ -11,12 +11,25 @@
 
 #[cfg(feature = "alloc")]
 pub use crate::{
-    commitment_scheme::PublicParameters,
-    compiler::{Compiler, Prover, Verifier},
-    composer::{Circuit, Composer, Constraint, Witness, WitnessPoint},
+    commitment_scheme::{OpeningKey, PublicParameters},
+    compiler::{
+        Compiler, MultiProofAggregator, Prover, Verifier, VerifierKeyRef,
+    },
+    composer::{
+        Checkpoint, Circuit, CircuitStats, Composer, Constraint, Gate,
+        Selector, WiredWitness, Witness, WitnessPoint,
+    },
+    fft::EvaluationDomain,
+    proof_system::{
+        Challenges, ConstantPublicInputs, CustomGate, CustomGateWires,
+        ProofEvaluationsBuilder, PublicInputs, VerificationFailure,
+        VerificationReport,
+    },
+    runtime::{RuntimeEvent, RuntimeListener},
+    transcript::{ContextTranscript, ResumableTranscript, TranscriptBackend},
 };
 
 pub use crate::error::Error;
-pub use crate::proof_system::Proof;
+pub use crate::proof_system::{Proof, ProofEvaluations};
 pub use dusk_bls12_381::BlsScalar;
 pub use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar};
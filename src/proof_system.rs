@@ -14,10 +14,21 @@ cfg_if::cfg_if!(
     if #[cfg(feature = "alloc")] {
         pub(crate) mod quotient_poly;
         pub(crate) mod preprocess;
+        pub(crate) mod public_inputs;
+        pub(crate) mod report;
+        pub(crate) mod trace;
 
-        pub(crate) use widget::alloc::ProverKey;
+        #[cfg(feature = "test-reference")]
+        pub(crate) mod reference;
+
+        pub(crate) use widget::alloc::{LinearizationContext, ProverKey};
         pub(crate) use widget::VerifierKey;
 
+        pub use linearization_poly::ProofEvaluationsBuilder;
+        pub use public_inputs::{ConstantPublicInputs, PublicInputs};
+        pub use report::{Challenges, VerificationFailure, VerificationReport};
+        pub use widget::custom::{CustomGate, CustomGateWires};
+
         cfg_if::cfg_if!(
             if #[cfg(feature = "rkyv-impl")] {
                 pub use widget::alloc::{ArchivedProverKey, ProverKeyResolver};
@@ -26,6 +37,7 @@ cfg_if::cfg_if!(
     }
 );
 
+pub use linearization_poly::ProofEvaluations;
 pub use proof::Proof;
 
 cfg_if::cfg_if!(
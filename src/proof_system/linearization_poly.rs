@@ -4,14 +4,22 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 #[cfg(feature = "alloc")]
 use crate::{
+    error::Error,
     fft::{EvaluationDomain, Polynomial},
-    proof_system::{proof, ProverKey},
+    proof_system::{
+        proof, quotient_poly::QuotientLayout, widget::custom, ProverKey,
+    },
+    util::powers_of,
 };
 
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::{DeserializableSlice, Serializable};
+use subtle::{Choice, ConstantTimeEq};
 
 #[cfg(feature = "rkyv-impl")]
 use bytecheck::CheckBytes;
@@ -23,6 +31,9 @@ use rkyv::{
 
 /// Subset of all of the evaluations. These evaluations
 /// are added to the [`Proof`](super::Proof).
+///
+/// It's serialized along with the proof, and can be built out of its scalars
+/// with [`ProofEvaluations::builder`].
 #[derive(Debug, Eq, PartialEq, Clone, Default)]
 #[cfg_attr(
     feature = "rkyv-impl",
@@ -30,7 +41,7 @@ use rkyv::{
     archive(bound(serialize = "__S: Serializer + ScratchSpace")),
     archive_attr(derive(CheckBytes))
 )]
-pub(crate) struct ProofEvaluations {
+pub struct ProofEvaluations {
     // Evaluation of the witness polynomial for the left wire at `z`
     #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
     pub(crate) a_eval: BlsScalar,
@@ -82,6 +93,77 @@ pub(crate) struct ProofEvaluations {
     pub(crate) z_eval: BlsScalar,
 }
 
+impl ProofEvaluations {
+    /// Size in bytes of the serialized [`ProofEvaluations`].
+    pub(crate) const fn serialized_size() -> usize {
+        Self::SIZE
+    }
+
+    /// Evaluations labeled with their field name, in serialization order.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn named(&self) -> [(&'static str, &BlsScalar); 15] {
+        [
+            ("a_eval", &self.a_eval),
+            ("b_eval", &self.b_eval),
+            ("c_eval", &self.c_eval),
+            ("d_eval", &self.d_eval),
+            ("a_w_eval", &self.a_w_eval),
+            ("b_w_eval", &self.b_w_eval),
+            ("d_w_eval", &self.d_w_eval),
+            ("q_arith_eval", &self.q_arith_eval),
+            ("q_c_eval", &self.q_c_eval),
+            ("q_l_eval", &self.q_l_eval),
+            ("q_r_eval", &self.q_r_eval),
+            ("s_sigma_1_eval", &self.s_sigma_1_eval),
+            ("s_sigma_2_eval", &self.s_sigma_2_eval),
+            ("s_sigma_3_eval", &self.s_sigma_3_eval),
+            ("z_eval", &self.z_eval),
+        ]
+    }
+
+    /// Name and values of every evaluation that differs from `other`, in
+    /// serialization order.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn diff(
+        &self,
+        other: &Self,
+    ) -> Vec<(&'static str, BlsScalar, BlsScalar)> {
+        self.named()
+            .into_iter()
+            .zip(other.named())
+            .filter(|((_, a), (_, b))| a != b)
+            .map(|((name, a), (_, b))| (name, *a, *b))
+            .collect()
+    }
+
+    /// Create a builder for [`ProofEvaluations`] that checks that every
+    /// evaluation is set.
+    #[cfg(feature = "alloc")]
+    pub fn builder() -> ProofEvaluationsBuilder {
+        ProofEvaluationsBuilder::default()
+    }
+}
+
+impl ConstantTimeEq for ProofEvaluations {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.a_eval.ct_eq(&other.a_eval)
+            & self.b_eval.ct_eq(&other.b_eval)
+            & self.c_eval.ct_eq(&other.c_eval)
+            & self.d_eval.ct_eq(&other.d_eval)
+            & self.a_w_eval.ct_eq(&other.a_w_eval)
+            & self.b_w_eval.ct_eq(&other.b_w_eval)
+            & self.d_w_eval.ct_eq(&other.d_w_eval)
+            & self.q_arith_eval.ct_eq(&other.q_arith_eval)
+            & self.q_c_eval.ct_eq(&other.q_c_eval)
+            & self.q_l_eval.ct_eq(&other.q_l_eval)
+            & self.q_r_eval.ct_eq(&other.q_r_eval)
+            & self.s_sigma_1_eval.ct_eq(&other.s_sigma_1_eval)
+            & self.s_sigma_2_eval.ct_eq(&other.s_sigma_2_eval)
+            & self.s_sigma_3_eval.ct_eq(&other.s_sigma_3_eval)
+            & self.z_eval.ct_eq(&other.z_eval)
+    }
+}
+
 // The struct ProofEvaluations has 15 BlsScalars
 impl Serializable<{ 15 * BlsScalar::SIZE }> for ProofEvaluations {
     type Error = dusk_bytes::Error;
@@ -151,8 +233,72 @@ impl Serializable<{ 15 * BlsScalar::SIZE }> for ProofEvaluations {
     }
 }
 
+/// Builder of [`ProofEvaluations`] keeping track of the evaluations that were
+/// set.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct ProofEvaluationsBuilder {
+    evaluations: ProofEvaluations,
+    set: u16,
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! evaluation_setters {
+    ($($field:ident),* $(,)?) => {
+        impl ProofEvaluationsBuilder {
+            const FIELDS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            evaluation_setters!(@setter 0u16, $($field,)*);
+
+            /// Build the [`ProofEvaluations`], returning an error naming the
+            /// first evaluation that was never set.
+            pub fn build(self) -> Result<ProofEvaluations, Error> {
+                match Self::FIELDS
+                    .iter()
+                    .enumerate()
+                    .find(|(i, _)| self.set & (1 << i) == 0)
+                {
+                    Some((_, name)) => {
+                        Err(Error::MissingProofEvaluation { name })
+                    }
+                    None => Ok(self.evaluations),
+                }
+            }
+        }
+    };
+    (@setter $bit:expr, $field:ident, $($rest:ident,)*) => {
+        #[doc = concat!("Set the `", stringify!($field), "` evaluation.")]
+        pub fn $field(mut self, eval: BlsScalar) -> Self {
+            self.evaluations.$field = eval;
+            self.set |= 1 << $bit;
+            self
+        }
+
+        evaluation_setters!(@setter $bit + 1, $($rest,)*);
+    };
+    (@setter $bit:expr,) => {};
+}
+
 #[cfg(feature = "alloc")]
+evaluation_setters!(
+    a_eval,
+    b_eval,
+    c_eval,
+    d_eval,
+    a_w_eval,
+    b_w_eval,
+    d_w_eval,
+    q_arith_eval,
+    q_c_eval,
+    q_l_eval,
+    q_r_eval,
+    s_sigma_1_eval,
+    s_sigma_2_eval,
+    s_sigma_3_eval,
+    z_eval,
+);
 
+#[cfg(feature = "alloc")]
 /// Compute the linearization polynomial.
 // TODO: Improve the method signature
 #[allow(clippy::type_complexity)]
@@ -180,11 +326,13 @@ pub(crate) fn compute(
     z_poly: &Polynomial,
     evaluations: &ProofEvaluations,
     domain: &EvaluationDomain,
-    t_low_poly: &Polynomial,
-    t_mid_poly: &Polynomial,
-    t_high_poly: &Polynomial,
-    t_fourth_poly: &Polynomial,
+    layout: &QuotientLayout,
+    t_polys: &[Polynomial],
     pub_inputs: &[BlsScalar],
+    (custom_gates, custom_separation_challenge): (
+        &[custom::ProverKey],
+        &BlsScalar,
+    ),
 ) -> Polynomial {
     let f_1 = compute_circuit_satisfiability(
         (
@@ -202,6 +350,13 @@ pub(crate) fn compute(
 
     let f_1 = &f_1 + &pi_eval;
 
+    let f_1 = custom_gates
+        .iter()
+        .zip(custom::separation_challenges(*custom_separation_challenge))
+        .fold(f_1, |f_1, (gate, sep)| {
+            &f_1 + &gate.compute_linearization(&sep, evaluations)
+        });
+
     let f_2 = prover_key.permutation.compute_linearization(
         z_challenge,
         (alpha, beta, gamma),
@@ -220,19 +375,13 @@ pub(crate) fn compute(
         z_poly,
     );
 
-    let domain_size = domain.size();
-
-    let z_n = z_challenge.pow(&[domain_size as u64, 0, 0, 0]);
-    let z_two_n = z_challenge.pow(&[2 * domain_size as u64, 0, 0, 0]);
-    let z_three_n = z_challenge.pow(&[3 * domain_size as u64, 0, 0, 0]);
-
-    let a = t_low_poly;
-    let b = t_mid_poly * &z_n;
-    let c = t_high_poly * &z_two_n;
-    let d = t_fourth_poly * &z_three_n;
-    let abc = &(a + &b) + &c;
-
-    let quot = &abc + &d;
+    // sum_i z^(i·m) · t_i(X), for the chunk size m
+    let chunk_size = layout.chunk_factor() * domain.size();
+    let z_n = z_challenge.pow(&[chunk_size as u64, 0, 0, 0]);
+    let mut quot = Polynomial::zero();
+    for (t_poly, z_in) in t_polys.iter().zip(powers_of(&z_n, t_polys.len())) {
+        quot += (z_in, t_poly);
+    }
 
     let z_h_eval = -domain.evaluate_vanishing_polynomial(z_challenge);
 
@@ -293,4 +442,23 @@ mod evaluations_tests {
             .expect("Deserialization error");
         assert_eq!(proof_evals.to_bytes(), obtained_evals.to_bytes())
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn proof_evaluations_diff() {
+        let evals = ProofEvaluations::default();
+        assert!(evals.diff(&evals).is_empty());
+
+        let mut other = evals.clone();
+        other.b_w_eval = BlsScalar::one();
+        other.z_eval = BlsScalar::from(2);
+
+        assert_eq!(
+            evals.diff(&other),
+            [
+                ("b_w_eval", BlsScalar::zero(), BlsScalar::one()),
+                ("z_eval", BlsScalar::zero(), BlsScalar::from(2)),
+            ]
+        );
+    }
 }
@@ -9,14 +9,70 @@
 
 use super::linearization_poly::ProofEvaluations;
 use crate::commitment_scheme::Commitment;
+use crate::error::Error;
 
+use dusk_bls12_381::G1Affine;
 use dusk_bytes::{DeserializableSlice, Serializable};
+use subtle::{Choice, ConstantTimeEq};
 
 #[cfg(feature = "std")]
 use rayon::prelude::*;
 
 const V_MAX_DEGREE: usize = 7;
 
+/// Transcript labels of the commitments of a proof, in serialization order.
+const COMMITMENT_LABELS: [&str; 11] = [
+    "a_comm",
+    "b_comm",
+    "c_comm",
+    "d_comm",
+    "z_comm",
+    "t_low_comm",
+    "t_mid_comm",
+    "t_high_comm",
+    "t_fourth_comm",
+    "w_z_chall_comm",
+    "w_z_chall_w_comm",
+];
+
+/// Number of degree `n` polynomials the quotient polynomial is split into by
+/// default, and the fewest chunks of any proof.
+pub(crate) const QUOTIENT_CHUNKS: usize = 4;
+
+/// Highest number of chunks the quotient polynomial can be split into.
+pub(crate) const MAX_QUOTIENT_CHUNKS: usize = 16;
+
+/// Number of quotient chunks a proof can hold past the [`QUOTIENT_CHUNKS`]
+/// named ones.
+const EXTRA_QUOTIENT_CHUNKS: usize = MAX_QUOTIENT_CHUNKS - QUOTIENT_CHUNKS;
+
+/// Transcript labels of the commitments to the quotient polynomial chunks.
+pub(crate) const QUOTIENT_LABELS: [&str; MAX_QUOTIENT_CHUNKS] = [
+    "t_low_comm",
+    "t_mid_comm",
+    "t_high_comm",
+    "t_fourth_comm",
+    "t_4_comm",
+    "t_5_comm",
+    "t_6_comm",
+    "t_7_comm",
+    "t_8_comm",
+    "t_9_comm",
+    "t_10_comm",
+    "t_11_comm",
+    "t_12_comm",
+    "t_13_comm",
+    "t_14_comm",
+    "t_15_comm",
+];
+
+/// Leading byte of a proof serialized by [`Proof::to_var_bytes`] with more
+/// than [`QUOTIENT_CHUNKS`] quotient chunks.
+///
+/// Compressed points always have their most significant bit set, so a
+/// serialized proof starting with a commitment is never mistaken for it.
+const VAR_PROOF_TAG: u8 = 0x01;
+
 #[cfg(feature = "rkyv-impl")]
 use crate::util::check_field;
 #[cfg(feature = "rkyv-impl")]
@@ -37,6 +93,11 @@ use rkyv::{
 /// capabilities of adquiring any kind of knowledge about the witness used to
 /// construct the Proof.
 ///
+/// The derived [`PartialEq`] returns as soon as a difference is found, so
+/// its running time leaks where two proofs differ. Use the
+/// [`ConstantTimeEq`] implementation when comparing proofs with secret or
+/// attacker controlled data.
+///
 /// [`Composer`]: [`crate::prelude::Composer`]
 /// [`Verifier`]: [`crate::prelude::Verifier`]
 #[derive(Debug, Eq, PartialEq, Clone, Default)]
@@ -75,6 +136,12 @@ pub struct Proof {
     /// Commitment to the quotient polynomial.
     #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
     pub(crate) t_fourth_comm: Commitment,
+    /// Commitments to the quotient chunks past the fourth, if the circuit
+    /// splits its quotient into more chunks, and the identity otherwise.
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) t_extra_comms: [Commitment; EXTRA_QUOTIENT_CHUNKS],
+    /// Number of the `t_extra_comms` the quotient is split into.
+    pub(crate) extra_chunks: u8,
 
     /// Commitment to the opening polynomial.
     #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
@@ -106,6 +173,8 @@ impl<C> CheckBytes<C> for ArchivedProof {
         check_field(&(*value).t_mid_comm, context, "t_mid_comm")?;
         check_field(&(*value).t_high_comm, context, "t_high_comm")?;
         check_field(&(*value).t_fourth_comm, context, "t_fourth_comm")?;
+        check_field(&(*value).t_extra_comms, context, "t_extra_comms")?;
+        check_field(&(*value).extra_chunks, context, "extra_chunks")?;
 
         check_field(&(*value).w_z_chall_comm, context, "w_z_chall_comm")?;
         check_field(&(*value).w_z_chall_w_comm, context, "w_z_chall_w_comm")?;
@@ -115,14 +184,232 @@ impl<C> CheckBytes<C> for ArchivedProof {
     }
 }
 
+impl ConstantTimeEq for Proof {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.a_comm.ct_eq(&other.a_comm)
+            & self.b_comm.ct_eq(&other.b_comm)
+            & self.c_comm.ct_eq(&other.c_comm)
+            & self.d_comm.ct_eq(&other.d_comm)
+            & self.z_comm.ct_eq(&other.z_comm)
+            & self.t_low_comm.ct_eq(&other.t_low_comm)
+            & self.t_mid_comm.ct_eq(&other.t_mid_comm)
+            & self.t_high_comm.ct_eq(&other.t_high_comm)
+            & self.t_fourth_comm.ct_eq(&other.t_fourth_comm)
+            & self
+                .t_extra_comms
+                .iter()
+                .zip(other.t_extra_comms.iter())
+                .fold(Choice::from(1), |eq, (a, b)| eq & a.ct_eq(b))
+            & self.extra_chunks.ct_eq(&other.extra_chunks)
+            & self.w_z_chall_comm.ct_eq(&other.w_z_chall_comm)
+            & self.w_z_chall_w_comm.ct_eq(&other.w_z_chall_w_comm)
+            & self.evaluations.ct_eq(&other.evaluations)
+    }
+}
+
 // The struct Proof has 11 commitments + 1 ProofEvaluations
+//
+// A proof with more than `QUOTIENT_CHUNKS` quotient chunks doesn't fit the
+// fixed-size encoding, and is only serialized by `Proof::to_var_bytes`.
 impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
     for Proof
 {
     type Error = dusk_bytes::Error;
 
-    #[allow(unused_must_use)]
+    /// Serialize a [`Proof`] with four quotient chunks.
+    ///
+    /// Proofs of circuits compiled with more chunks don't fit this encoding
+    /// and have to be serialized with [`Proof::to_var_bytes`]. In debug
+    /// builds, serializing them with this function panics.
     fn to_bytes(&self) -> [u8; Self::SIZE] {
+        debug_assert_eq!(
+            self.quotient_chunks(),
+            QUOTIENT_CHUNKS,
+            "a proof with more than four quotient chunks is serialized with `Proof::to_var_bytes`"
+        );
+
+        self.fixed_bytes()
+    }
+
+    /// Deserialize a [`Proof`], checking every commitment is a point of the
+    /// prime order subgroup of G1.
+    ///
+    /// Use [`Proof::from_bytes_checked`] to know which commitment is invalid.
+    fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
+        let mut buffer = &buf[..];
+
+        let a_comm = Commitment::from_reader(&mut buffer)?;
+        let b_comm = Commitment::from_reader(&mut buffer)?;
+        let c_comm = Commitment::from_reader(&mut buffer)?;
+        let d_comm = Commitment::from_reader(&mut buffer)?;
+        let z_comm = Commitment::from_reader(&mut buffer)?;
+        let t_low_comm = Commitment::from_reader(&mut buffer)?;
+        let t_mid_comm = Commitment::from_reader(&mut buffer)?;
+        let t_high_comm = Commitment::from_reader(&mut buffer)?;
+        let t_fourth_comm = Commitment::from_reader(&mut buffer)?;
+        let w_z_chall_comm = Commitment::from_reader(&mut buffer)?;
+        let w_z_chall_w_comm = Commitment::from_reader(&mut buffer)?;
+        let evaluations = ProofEvaluations::from_reader(&mut buffer)?;
+
+        Ok(Proof {
+            a_comm,
+            b_comm,
+            c_comm,
+            d_comm,
+            z_comm,
+            t_low_comm,
+            t_mid_comm,
+            t_high_comm,
+            t_fourth_comm,
+            t_extra_comms: Default::default(),
+            extra_chunks: 0,
+            w_z_chall_comm,
+            w_z_chall_w_comm,
+            evaluations,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for Proof {
+    type Error = Error;
+
+    /// Deserialize a [`Proof`] from a slice of exactly [`Proof::SIZE`]
+    /// bytes, with the checks of [`Proof::from_bytes_checked`].
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: &[u8; Self::SIZE] =
+            bytes.try_into().map_err(|_| Error::InvalidProofLength {
+                expected: Self::SIZE,
+                got: bytes.len(),
+            })?;
+
+        Self::from_bytes_checked(bytes)
+    }
+}
+
+impl Proof {
+    /// Size in bytes of the serialized [`Proof`].
+    ///
+    /// A proof is serialized as its 11 commitments, in order:
+    /// - `a`, `b`, `c`, `d`: the witness polynomials
+    /// - `z`: the permutation polynomial
+    /// - `t_low`, `t_mid`, `t_high`, `t_fourth`: the quotient polynomial split
+    /// - `w_z`, `w_zw`: the opening polynomials at `z` and `z·ω`
+    ///
+    /// followed by the 15 scalar evaluations opened at those points.
+    pub const fn serialized_size() -> usize {
+        11 * Commitment::SIZE + ProofEvaluations::serialized_size()
+    }
+
+    /// Largest size in bytes of a proof serialized with
+    /// [`Proof::to_var_bytes`], split into sixteen quotient
+    /// chunks.
+    pub const MAX_VAR_SIZE: usize =
+        2 + EXTRA_QUOTIENT_CHUNKS * Commitment::SIZE + Self::SIZE;
+
+    /// Number of chunks the quotient polynomial of the proof is split into,
+    /// as selected when the circuit was compiled.
+    pub fn quotient_chunks(&self) -> usize {
+        QUOTIENT_CHUNKS + self.extra_chunks as usize
+    }
+
+    /// Deserialize a [`Proof`] serialized with [`Proof::to_var_bytes`],
+    /// with the checks of [`Proof::from_bytes_checked`].
+    ///
+    /// The bytes of a proof serialized with [`Serializable::to_bytes`] are
+    /// accepted as well, as they're the encoding of the proofs with four
+    /// quotient chunks.
+    ///
+    /// Returns [`Error::UnsupportedProofVersion`] if the bytes start with an
+    /// unknown tag, and [`Error::InvalidProofLength`] if their length doesn't
+    /// match the number of quotient chunks they declare.
+    pub fn from_var_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match bytes.first() {
+            Some(&VAR_PROOF_TAG) => (),
+            Some(&version) if version & 0x80 == 0 => {
+                return Err(Error::UnsupportedProofVersion { version })
+            }
+            _ => return Self::try_from(bytes),
+        }
+
+        let extras = bytes.get(1).copied().ok_or(Error::NotEnoughBytes)?;
+        let extras = extras as usize;
+        if extras == 0 || extras > EXTRA_QUOTIENT_CHUNKS {
+            return Err(Error::UnsupportedQuotientChunks {
+                chunks: QUOTIENT_CHUNKS + extras,
+                max: MAX_QUOTIENT_CHUNKS,
+            });
+        }
+
+        let expected = 2 + extras * Commitment::SIZE + Self::SIZE;
+        if bytes.len() != expected {
+            return Err(Error::InvalidProofLength {
+                expected,
+                got: bytes.len(),
+            });
+        }
+
+        // the extra chunks are inserted right after `t_fourth_comm`
+        let split = 2 + 9 * Commitment::SIZE;
+        let extras_end = split + extras * Commitment::SIZE;
+
+        let mut buf = [0u8; Self::SIZE];
+        buf[..split - 2].copy_from_slice(&bytes[2..split]);
+        buf[split - 2..].copy_from_slice(&bytes[extras_end..]);
+        let mut proof = Self::from_bytes_checked(&buf)?;
+
+        for (i, (comm, label)) in proof
+            .t_extra_comms
+            .iter_mut()
+            .zip(&QUOTIENT_LABELS[QUOTIENT_CHUNKS..])
+            .take(extras)
+            .enumerate()
+        {
+            let start = split + i * Commitment::SIZE;
+            *comm = Self::commitment_checked(
+                &bytes[start..start + Commitment::SIZE],
+                label,
+            )?;
+        }
+        proof.extra_chunks = extras as u8;
+
+        Ok(proof)
+    }
+
+    /// Serialize the proof into `buf`, returning the number of bytes
+    /// written, as described in [`Proof::to_var_bytes`].
+    #[cfg(any(feature = "alloc", feature = "serde"))]
+    #[allow(unused_must_use)]
+    pub(crate) fn write_var_bytes(
+        &self,
+        buf: &mut [u8; Self::MAX_VAR_SIZE],
+    ) -> usize {
+        use dusk_bytes::Write;
+
+        let bytes = self.fixed_bytes();
+        let extras = self.extra_chunks as usize;
+        if extras == 0 {
+            buf[..Self::SIZE].copy_from_slice(&bytes);
+            return Self::SIZE;
+        }
+
+        let split = 9 * Commitment::SIZE;
+
+        buf[0] = VAR_PROOF_TAG;
+        buf[1] = extras as u8;
+        let mut writer = &mut buf[2..];
+        writer.write(&bytes[..split]);
+        self.t_extra_comms[..extras].iter().for_each(|comm| {
+            writer.write(&comm.to_bytes());
+        });
+        writer.write(&bytes[split..]);
+
+        2 + extras * Commitment::SIZE + Self::SIZE
+    }
+
+    /// Serialize the commitments of the proof, with its first four quotient
+    /// chunks, and its evaluations.
+    #[allow(unused_must_use)]
+    fn fixed_bytes(&self) -> [u8; Self::SIZE] {
         use dusk_bytes::Write;
 
         let mut buf = [0u8; Self::SIZE];
@@ -143,21 +430,99 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
         buf
     }
 
-    fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
-        let mut buffer = &buf[..];
+    /// Iterate the commitments of the proof, in the order they are
+    /// serialized, labeled as they are appended to the transcript.
+    ///
+    /// A proof with more than four quotient chunks yields the commitments to
+    /// the extra chunks right after `t_fourth_comm`.
+    pub fn commitments(
+        &self,
+    ) -> impl Iterator<Item = (&'static str, &G1Affine)> {
+        let extras = self.quotient_chunks() - QUOTIENT_CHUNKS;
+
+        COMMITMENT_LABELS[..9]
+            .iter()
+            .copied()
+            .zip([
+                &self.a_comm.0,
+                &self.b_comm.0,
+                &self.c_comm.0,
+                &self.d_comm.0,
+                &self.z_comm.0,
+                &self.t_low_comm.0,
+                &self.t_mid_comm.0,
+                &self.t_high_comm.0,
+                &self.t_fourth_comm.0,
+            ])
+            .chain(
+                QUOTIENT_LABELS[QUOTIENT_CHUNKS..]
+                    .iter()
+                    .copied()
+                    .zip(self.t_extra_comms.iter().map(|comm| &comm.0))
+                    .take(extras),
+            )
+            .chain(
+                COMMITMENT_LABELS[9..]
+                    .iter()
+                    .copied()
+                    .zip([&self.w_z_chall_comm.0, &self.w_z_chall_w_comm.0]),
+            )
+    }
 
-        let a_comm = Commitment::from_reader(&mut buffer)?;
-        let b_comm = Commitment::from_reader(&mut buffer)?;
-        let c_comm = Commitment::from_reader(&mut buffer)?;
-        let d_comm = Commitment::from_reader(&mut buffer)?;
-        let z_comm = Commitment::from_reader(&mut buffer)?;
-        let t_low_comm = Commitment::from_reader(&mut buffer)?;
-        let t_mid_comm = Commitment::from_reader(&mut buffer)?;
-        let t_high_comm = Commitment::from_reader(&mut buffer)?;
-        let t_fourth_comm = Commitment::from_reader(&mut buffer)?;
-        let w_z_chall_comm = Commitment::from_reader(&mut buffer)?;
-        let w_z_chall_w_comm = Commitment::from_reader(&mut buffer)?;
-        let evaluations = ProofEvaluations::from_reader(&mut buffer)?;
+    /// Commitments to the opening witnesses of the proof, `[W_z(x)]_1` for
+    /// the openings at the evaluation challenge `z` and `[W_zw(x)]_1` for the
+    /// openings at `z·ω`.
+    ///
+    /// The points to open them at are returned by
+    /// [`Verifier::opening_points`], so the openings of many proofs can be
+    /// batched in an external multi-pairing.
+    ///
+    /// [`Verifier::opening_points`]: crate::prelude::Verifier::opening_points
+    pub fn opening_witnesses(&self) -> (G1Affine, G1Affine) {
+        (self.w_z_chall_comm.0, self.w_z_chall_w_comm.0)
+    }
+
+    /// Cheap structural check flagging the proofs an honest prover only
+    /// produces with negligible probability, to drop them before paying for
+    /// the pairings of the verification.
+    ///
+    /// Every commitment of a proof is blinded by the prover, so a proof with
+    /// an identity commitment is rejected, such as an all-default proof or
+    /// one whose opening witnesses are the identity.
+    ///
+    /// Passing this check does NOT imply the proof is valid, which is only
+    /// established by verifying it.
+    pub fn quick_reject(&self) -> bool {
+        self.commitments()
+            .any(|(_, point)| bool::from(point.is_identity()))
+    }
+
+    /// Deserialize a [`Proof`], reporting which of its commitments isn't a
+    /// valid point of the prime order subgroup of G1.
+    ///
+    /// [`Proof::from_bytes`] performs the same checks, as every compressed
+    /// point is decoded on the curve and rejected if it isn't torsion free,
+    /// but it fails with an opaque [`dusk_bytes::Error::InvalidData`].
+    ///
+    /// Returns [`Error::ProofCommitmentNotOnCurve`] or
+    /// [`Error::ProofCommitmentNotInSubgroup`] with the label of the first
+    /// invalid commitment, as yielded by [`Proof::commitments`].
+    pub fn from_bytes_checked(buf: &[u8; Self::SIZE]) -> Result<Self, Error> {
+        let mut comms = [Commitment(G1Affine::identity()); 11];
+        for (i, (comm, label)) in
+            comms.iter_mut().zip(COMMITMENT_LABELS).enumerate()
+        {
+            *comm = Self::commitment_checked(
+                &buf[i * Commitment::SIZE..(i + 1) * Commitment::SIZE],
+                label,
+            )?;
+        }
+
+        let evaluations =
+            ProofEvaluations::from_slice(&buf[11 * Commitment::SIZE..])?;
+
+        let [a_comm, b_comm, c_comm, d_comm, z_comm, t_low_comm, t_mid_comm, t_high_comm, t_fourth_comm, w_z_chall_comm, w_z_chall_w_comm] =
+            comms;
 
         Ok(Proof {
             a_comm,
@@ -169,24 +534,214 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
             t_mid_comm,
             t_high_comm,
             t_fourth_comm,
+            t_extra_comms: Default::default(),
+            extra_chunks: 0,
             w_z_chall_comm,
             w_z_chall_w_comm,
             evaluations,
         })
     }
+
+    /// Decode a commitment of a proof, reporting whether it's off the curve
+    /// or out of the prime order subgroup under the given `label`.
+    fn commitment_checked(
+        bytes: &[u8],
+        label: &'static str,
+    ) -> Result<Commitment, Error> {
+        let mut buf = [0u8; Commitment::SIZE];
+        buf.copy_from_slice(bytes);
+
+        let point: Option<G1Affine> =
+            G1Affine::from_compressed_unchecked(&buf).into();
+        let point = point.ok_or(Error::ProofCommitmentNotOnCurve { label })?;
+        if !bool::from(point.is_torsion_free()) {
+            return Err(Error::ProofCommitmentNotInSubgroup { label });
+        }
+
+        Ok(Commitment(point))
+    }
+
+    /// Commitments to the chunks of the quotient polynomial, from the lowest
+    /// to the highest degree.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn quotient_comms(&self) -> impl Iterator<Item = &Commitment> {
+        [
+            &self.t_low_comm,
+            &self.t_mid_comm,
+            &self.t_high_comm,
+            &self.t_fourth_comm,
+        ]
+        .into_iter()
+        .chain(self.t_extra_comms.iter())
+        .take(self.quotient_chunks())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Proof {
+    /// Write the proof to a stream as a frame, its serialized size as a
+    /// big-endian `u64` followed by its bytes, so several proofs can be
+    /// written one after the other.
+    pub fn write_framed<W: std::io::Write>(
+        &self,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        let mut buf = [0u8; Self::MAX_VAR_SIZE];
+        let len = self.write_var_bytes(&mut buf);
+
+        w.write_all(&(len as u64).to_be_bytes())?;
+        w.write_all(&buf[..len])
+    }
+
+    /// Read a proof framed by [`Proof::write_framed`] from a stream.
+    ///
+    /// A stream ending before the frame fails with
+    /// [`std::io::ErrorKind::UnexpectedEof`], which marks the end of a stream
+    /// of proofs. A frame longer than [`Proof::MAX_VAR_SIZE`], or whose bytes
+    /// aren't a valid proof, fails with [`std::io::ErrorKind::InvalidData`].
+    pub fn read_framed<R: std::io::Read>(r: &mut R) -> std::io::Result<Self> {
+        use std::io::{Error as IoError, ErrorKind};
+
+        let mut len = [0u8; u64::SIZE];
+        r.read_exact(&mut len)?;
+        let len = u64::from_be_bytes(len);
+
+        if len > Self::MAX_VAR_SIZE as u64 {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                Error::BytesError(dusk_bytes::Error::BadLength {
+                    found: len as usize,
+                    expected: Self::MAX_VAR_SIZE,
+                }),
+            ));
+        }
+
+        let mut bytes = [0u8; Self::MAX_VAR_SIZE];
+        let bytes = &mut bytes[..len as usize];
+        r.read_exact(bytes)?;
+
+        Self::from_var_bytes(bytes)
+            .map_err(|e| IoError::new(ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(any(test, feature = "bench"))]
+impl Proof {
+    /// Create a structurally valid proof, with identity commitments and
+    /// random evaluations, for benchmark and fuzz harnesses.
+    ///
+    /// The proof isn't cryptographically valid and is rejected by any
+    /// verifier. It's only available with the `bench` feature.
+    pub fn dummy<R: rand_core::RngCore>(rng: &mut R) -> Self {
+        use ff::Field;
+
+        let mut random = || dusk_bls12_381::BlsScalar::random(&mut *rng);
+
+        Proof {
+            a_comm: Commitment::default(),
+            b_comm: Commitment::default(),
+            c_comm: Commitment::default(),
+            d_comm: Commitment::default(),
+            z_comm: Commitment::default(),
+            t_low_comm: Commitment::default(),
+            t_mid_comm: Commitment::default(),
+            t_high_comm: Commitment::default(),
+            t_fourth_comm: Commitment::default(),
+            t_extra_comms: Default::default(),
+            extra_chunks: 0,
+            w_z_chall_comm: Commitment::default(),
+            w_z_chall_w_comm: Commitment::default(),
+            evaluations: ProofEvaluations {
+                a_eval: random(),
+                b_eval: random(),
+                c_eval: random(),
+                d_eval: random(),
+                a_w_eval: random(),
+                b_w_eval: random(),
+                d_w_eval: random(),
+                q_arith_eval: random(),
+                q_c_eval: random(),
+                q_l_eval: random(),
+                q_r_eval: random(),
+                s_sigma_1_eval: random(),
+                s_sigma_2_eval: random(),
+                s_sigma_3_eval: random(),
+                z_eval: random(),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Proof {
+    /// Deserialize a [`Proof`] from a [`std::io::Read`], reading each of its
+    /// commitments and evaluations sequentially without buffering the whole
+    /// proof.
+    ///
+    /// Returns [`Error::NotEnoughBytes`] if the reader fails before providing
+    /// [`Proof::serialized_size`] bytes.
+    pub fn from_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> Result<Self, Error> {
+        fn read<R, T, const N: usize>(reader: &mut R) -> Result<T, Error>
+        where
+            R: std::io::Read,
+            T: Serializable<N, Error = dusk_bytes::Error>,
+        {
+            let mut buf = [0u8; N];
+            reader
+                .read_exact(&mut buf)
+                .map_err(|_| Error::NotEnoughBytes)?;
+            Ok(T::from_bytes(&buf)?)
+        }
+
+        Ok(Proof {
+            a_comm: read(reader)?,
+            b_comm: read(reader)?,
+            c_comm: read(reader)?,
+            d_comm: read(reader)?,
+            z_comm: read(reader)?,
+            t_low_comm: read(reader)?,
+            t_mid_comm: read(reader)?,
+            t_high_comm: read(reader)?,
+            t_fourth_comm: read(reader)?,
+            t_extra_comms: Default::default(),
+            extra_chunks: 0,
+            w_z_chall_comm: read(reader)?,
+            w_z_chall_w_comm: read(reader)?,
+            evaluations: read(reader)?,
+        })
+    }
 }
 
+#[cfg(feature = "serde")]
+mod serde_support;
+
+#[cfg(all(feature = "std", feature = "serde"))]
+mod json_trace;
+
 #[cfg(feature = "alloc")]
 #[allow(unused_imports)]
 pub(crate) mod alloc {
     use super::*;
     use crate::{
-        commitment_scheme::{AggregateProof, OpeningKey},
+        commitment_scheme::{AggregateProof, OpeningKey, OpeningProof},
+        compiler::{Verifier, VerifierKeyRef},
         error::Error,
         fft::EvaluationDomain,
-        proof_system::widget::VerifierKey,
-        transcript::TranscriptProtocol,
-        util::batch_inversion,
+        proof_system::public_inputs::{ConstantPublicInputs, PublicInputs},
+        proof_system::report::{
+            Challenges, VerificationFailure, VerificationReport,
+        },
+        proof_system::trace::VerifyTrace,
+        proof_system::widget::{
+            alloc::LinearizationContext, custom, VerifierKey,
+        },
+        transcript::{TranscriptBackend, TranscriptProtocol},
+        util::{
+            batch_inversion, batch_inversion_checked, powers_of,
+            sum_of_products,
+        },
     };
     #[rustfmt::skip]
     use ::alloc::vec::Vec;
@@ -196,58 +751,325 @@ pub(crate) mod alloc {
     use merlin::Transcript;
     #[cfg(feature = "std")]
     use rayon::prelude::*;
+    use sha2::{Digest, Sha256};
+
+    /// Domain separator of [`Proof::statement_digest`]
+    const STATEMENT_DIGEST_DST: &[u8] = b"dusk-plonk-statement-digest-v1";
 
     impl Proof {
-        /// Performs the verification of a [`Proof`] returning a boolean result.
-        #[allow(non_snake_case)]
-        pub(crate) fn verify(
+        /// Serialize the proof along with the commitments to all of its
+        /// quotient chunks.
+        ///
+        /// A proof split into four quotient chunks is serialized as by
+        /// [`Serializable::to_bytes`]. A proof with more chunks starts with a
+        /// version tag and the number of extra chunks, followed by its
+        /// commitments, the extra chunks right after `t_fourth_comm`, and its
+        /// evaluations. It's deserialized by [`Proof::from_var_bytes`].
+        pub fn to_var_bytes(&self) -> Vec<u8> {
+            let mut buf = [0u8; Self::MAX_VAR_SIZE];
+            let len = self.write_var_bytes(&mut buf);
+
+            buf[..len].to_vec()
+        }
+
+        /// Check the proof commits to as many quotient chunks as the
+        /// circuit of the verifier splits its quotient into.
+        pub(crate) fn check_quotient_chunks(
+            &self,
+            linearization: &LinearizationContext,
+        ) -> Result<(), Error> {
+            let expected = linearization.quotient_layout().chunks();
+            let got = self.quotient_chunks();
+
+            match expected == got {
+                true => Ok(()),
+                false => {
+                    Err(Error::InconsistentQuotientChunks { expected, got })
+                }
+            }
+        }
+
+        /// Name and values of every evaluation of the proof that differs
+        /// from the evaluations of `other`, in serialization order.
+        ///
+        /// Useful to compare a proof field by field against the one produced
+        /// by a reference implementation.
+        pub fn evaluations_diff(
+            &self,
+            other: &Proof,
+        ) -> Vec<(&'static str, BlsScalar, BlsScalar)> {
+            self.evaluations.diff(&other.evaluations)
+        }
+
+        /// Digest identifying the statement of the proof along with its
+        /// public inputs, to index verified statements.
+        ///
+        /// Hashes the proof serialized by [`Proof::to_var_bytes`], the
+        /// number of public inputs and each of them with a domain separated
+        /// SHA-256, so distinct vectors of public inputs yield distinct
+        /// digests for the same proof.
+        pub fn statement_digest(&self, pub_inputs: &[BlsScalar]) -> [u8; 32] {
+            let mut hasher = Sha256::new();
+
+            hasher.update(STATEMENT_DIGEST_DST);
+            hasher.update(self.to_var_bytes());
+            hasher.update((pub_inputs.len() as u64).to_le_bytes());
+            pub_inputs
+                .iter()
+                .for_each(|pi| hasher.update(pi.to_bytes()));
+
+            hasher.finalize().into()
+        }
+
+        /// Verify the proof against a verifier serialized with
+        /// [`Verifier::to_compact_bytes`], without keeping the full
+        /// [`Verifier`] around.
+        pub fn verify_compact(
+            &self,
+            verifier: &[u8],
+            public_inputs: &[BlsScalar],
+        ) -> Result<(), Error> {
+            Verifier::try_from_compact_bytes(verifier)?
+                .verify(self, public_inputs)
+        }
+
+        /// Verify the proof against a borrowed view of a serialized verifier.
+        ///
+        /// The commitments of the verifier are deserialized and checked on
+        /// every call, and dropped once the proof is verified. The label and
+        /// the positions of the public inputs are read from the view in
+        /// place, without building an owned [`Verifier`].
+        pub fn verify_with_ref(
+            &self,
+            verifier: &VerifierKeyRef,
+            public_inputs: &[BlsScalar],
+        ) -> Result<(), Error> {
+            verifier.verify(self, public_inputs)
+        }
+
+        /// Verify the proof against public inputs encoded as bytes, as
+        /// received from an external system.
+        ///
+        /// Every input has to be the canonical encoding of a scalar, or
+        /// [`Error::InvalidPublicInputEncoding`] is returned with its
+        /// position.
+        pub fn verify_from_bytes_pi(
+            &self,
+            verifier: &Verifier,
+            pi_bytes: &[[u8; BlsScalar::SIZE]],
+        ) -> Result<(), Error> {
+            let public_inputs = pi_bytes
+                .iter()
+                .enumerate()
+                .map(|(index, bytes)| {
+                    Option::from(BlsScalar::from_bytes(bytes))
+                        .ok_or(Error::InvalidPublicInputEncoding { index })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            verifier.verify(self, &public_inputs)
+        }
+
+        /// Performs the verification of a [`Proof`] returning a
+        /// [`VerificationReport`] with the intermediate values computed by the
+        /// verifier and the sub-check that failed, if any.
+        ///
+        /// The public inputs fixed by the circuit, if any, are evaluated
+        /// apart with their precomputed roots, and not expected in
+        /// `pub_inputs`.
+        pub(crate) fn verify_with_report<T: TranscriptProtocol>(
             &self,
             verifier_key: &VerifierKey,
-            transcript: &mut Transcript,
+            linearization: &LinearizationContext,
+            transcript: &mut T,
+            opening_key: &OpeningKey,
+            pub_inputs: &PublicInputs,
+            constants: Option<&ConstantPublicInputs>,
+        ) -> VerificationReport {
+            let mut trace = VerifyTrace::new("verify");
+
+            let (mut report, left, right) = match self.compute_pairing_points(
+                verifier_key,
+                linearization,
+                transcript,
+                opening_key,
+                pub_inputs,
+                constants,
+                &mut trace,
+            ) {
+                Ok(points) => points,
+                Err(err) => {
+                    trace.finish(false);
+                    return VerificationReport::setup_failure(err);
+                }
+            };
+
+            // Compute the two pairings and subtract them
+            let pairing = dusk_bls12_381::multi_miller_loop(&[
+                (&left, &opening_key.prepared_x_h),
+                (&right, &opening_key.prepared_h),
+            ])
+            .final_exponentiation();
+
+            // Report a pairing failure if the two pairings are not equal
+            let valid = pairing == dusk_bls12_381::Gt::identity();
+            trace.step("pairing check", format_args!("valid: {}", valid));
+            if !valid {
+                report.fail(VerificationFailure::Pairing);
+            };
+
+            trace.finish(valid);
+            report
+        }
+
+        /// Performs the verification of a batch of [`Proof`]s, collapsing
+        /// all their pairing checks into a single multi-pairing.
+        ///
+        /// The pairing points of each proof are combined using powers of a
+        /// challenge derived from all of them, so a single invalid proof makes
+        /// the whole batch fail. The denominators of the Lagrange evaluations
+        /// of all the proofs are inverted at once, with a single field
+        /// inversion for the whole batch.
+        #[allow(non_snake_case)]
+        pub(crate) fn batch_verify(
+            proofs: &[Proof],
+            verifier_keys: &[(&VerifierKey, &LinearizationContext)],
+            transcripts: &mut [Transcript],
             opening_key: &OpeningKey,
-            pub_inputs: &[BlsScalar],
+            pub_inputs: &[&PublicInputs],
         ) -> Result<(), Error> {
-            let domain = EvaluationDomain::new(verifier_key.n)?;
+            let expected = proofs.len();
+            for provided in
+                [verifier_keys.len(), transcripts.len(), pub_inputs.len()]
+            {
+                if provided != expected {
+                    return Err(Error::InconsistentBatchLen {
+                        expected,
+                        provided,
+                    });
+                }
+            }
+
+            if proofs.is_empty() {
+                return Ok(());
+            }
 
-            // Subgroup checks are done when the proof is deserialized.
+            let mut trace = VerifyTrace::new("batch_verify");
+
+            // Derive the challenges of every proof and gather the
+            // denominators of their Lagrange evaluations, to invert them all
+            // with a single field inversion
+            let mut challenges = Vec::with_capacity(expected);
+            let mut lagranges = Vec::with_capacity(expected);
+            for ((proof, (_, linearization)), (transcript, pub_inputs)) in
+                proofs
+                    .iter()
+                    .zip(verifier_keys)
+                    .zip(transcripts.iter_mut().zip(pub_inputs))
+            {
+                proof.check_quotient_chunks(linearization).map_err(|err| {
+                    trace.finish(false);
+                    err
+                })?;
+                let proof_challenges = proof.derive_challenges(
+                    transcript,
+                    !linearization.custom_gates().is_empty(),
+                );
+                trace.step(
+                    "challenges derived",
+                    format_args!("z: {:?}", proof_challenges.z),
+                );
+
+                let lagrange = LagrangeEvaluations::gather(
+                    linearization.domain(),
+                    &proof_challenges.z,
+                    pub_inputs,
+                    None,
+                )
+                .map_err(|err| {
+                    trace.finish(false);
+                    err
+                })?;
 
-            // In order for the Verifier and Prover to have the same view in the
-            // non-interactive setting Both parties must commit the same
-            // elements into the transcript Below the verifier will simulate
-            // an interaction with the prover by adding the same elements
-            // that the prover added into the transcript, hence generating the
-            // same challenges
-            //
-            // Add commitment to witness polynomials to transcript
-            transcript.append_commitment(b"a_comm", &self.a_comm);
-            transcript.append_commitment(b"b_comm", &self.b_comm);
-            transcript.append_commitment(b"c_comm", &self.c_comm);
-            transcript.append_commitment(b"d_comm", &self.d_comm);
+                challenges.push(proof_challenges);
+                lagranges.push(lagrange);
+            }
 
-            // Compute beta and gamma challenges
-            let beta = transcript.challenge_scalar(b"beta");
-            transcript.append_scalar(b"beta", &beta);
-            let gamma = transcript.challenge_scalar(b"gamma");
+            LagrangeEvaluations::invert_all(&mut lagranges).map_err(|err| {
+                trace.finish(false);
+                err
+            })?;
 
-            // Add commitment to permutation polynomial to transcript
-            transcript.append_commitment(b"z_comm", &self.z_comm);
+            let points: Vec<(G1Affine, G1Affine)> = proofs
+                .iter()
+                .zip(verifier_keys)
+                .zip(challenges.into_iter().zip(lagranges.iter()))
+                .map(|((proof, (verifier_key, linearization)), (c, l))| {
+                    let (_, challenges, r_0_eval, D) = proof.linearize(
+                        verifier_key,
+                        linearization,
+                        c,
+                        l,
+                        &mut trace,
+                    );
+
+                    proof.pairing_points(
+                        verifier_key,
+                        linearization.domain(),
+                        opening_key,
+                        challenges,
+                        r_0_eval,
+                        D,
+                        &mut trace,
+                    )
+                })
+                .collect();
 
-            // Compute quotient challenge
-            let alpha = transcript.challenge_scalar(b"alpha");
-            let range_sep_challenge =
-                transcript.challenge_scalar(b"range separation challenge");
-            let logic_sep_challenge =
-                transcript.challenge_scalar(b"logic separation challenge");
-            let fixed_base_sep_challenge =
-                transcript.challenge_scalar(b"fixed base separation challenge");
-            let var_base_sep_challenge = transcript
-                .challenge_scalar(b"variable base separation challenge");
+            let result = opening_key
+                .batch_check_pairing_points(
+                    &points,
+                    &mut Transcript::new(b"batch_verify"),
+                )
+                .map_err(|_| Error::ProofVerificationError);
+            trace.step(
+                "batch pairing check",
+                format_args!("proofs: {}, valid: {}", expected, result.is_ok()),
+            );
 
-            // Add commitment to quotient polynomial to transcript
-            transcript.append_commitment(b"t_low_comm", &self.t_low_comm);
-            transcript.append_commitment(b"t_mid_comm", &self.t_mid_comm);
-            transcript.append_commitment(b"t_high_comm", &self.t_high_comm);
-            transcript.append_commitment(b"t_fourth_comm", &self.t_fourth_comm);
+            trace.finish(result.is_ok());
+            result
+        }
+
+        /// Evaluate at `point` the polynomial with the given `evaluations`
+        /// over the roots of unity, as done with the public inputs of a
+        /// proof.
+        ///
+        /// It's only available with the `bench` feature, to measure the
+        /// evaluation apart from the rest of the proof.
+        #[cfg(feature = "bench")]
+        pub fn barycentric_eval(
+            evaluations: &[BlsScalar],
+            point: &BlsScalar,
+        ) -> Result<BlsScalar, Error> {
+            let domain = EvaluationDomain::new(evaluations.len())?;
+
+            Ok(compute_barycentric_eval(evaluations, point, &domain))
+        }
+
+        /// Replays the transcript of the proof, deriving the Fiat-Shamir
+        /// challenges without performing any check.
+        ///
+        /// The transcript is expected to be seeded with the verifier key and
+        /// the public inputs, as done by the verifier. The custom separation
+        /// challenge is only drawn if the circuit registers custom gates, so
+        /// the transcript of circuits without them is left untouched.
+        pub(crate) fn derive_challenges<T: TranscriptProtocol>(
+            &self,
+            transcript: &mut T,
+            custom_gates: bool,
+        ) -> Challenges {
+            let mut challenges =
+                self.derive_quotient_challenges(transcript, custom_gates);
 
             // Compute evaluation challenge z
             let z_challenge = transcript.challenge_scalar(b"z_challenge");
@@ -286,49 +1108,197 @@ pub(crate) mod alloc {
             let v_challenge = transcript.challenge_scalar(b"v_challenge");
             let v_w_challenge = transcript.challenge_scalar(b"v_w_challenge");
 
-            // Add commitment to openings to transcript
-            transcript
-                .append_commitment(b"w_z_chall_comm", &self.w_z_chall_comm);
-            transcript
-                .append_commitment(b"w_z_chall_w_comm", &self.w_z_chall_w_comm);
+            // Add commitment to openings to transcript, and compute the
+            // challenge 'u'
+            let u_challenge = transcript.opening_challenge(
+                &self.w_z_chall_comm,
+                &self.w_z_chall_w_comm,
+            );
 
-            // Compute the challenge 'u'
-            let u_challenge = transcript.challenge_scalar(b"u_challenge");
+            challenges.z = z_challenge;
+            challenges.v = v_challenge;
+            challenges.v_w = v_w_challenge;
+            challenges.u = u_challenge;
 
-            // Compute zero polynomial evaluated at challenge `z`
-            let z_h_eval = domain.evaluate_vanishing_polynomial(&z_challenge);
+            challenges
+        }
 
-            // Compute first lagrange polynomial evaluated at challenge `z`
-            let l1_eval = compute_first_lagrange_evaluation(
-                &domain,
-                &z_h_eval,
-                &z_challenge,
-            );
+        /// Replays the transcript of the proof up to the quotient
+        /// commitments, deriving the challenges drawn until then.
+        ///
+        /// The transcript is left right before the evaluation challenge is
+        /// drawn, and the challenges of the later rounds are set to zero.
+        pub(crate) fn derive_quotient_challenges<T: TranscriptProtocol>(
+            &self,
+            transcript: &mut T,
+            custom_gates: bool,
+        ) -> Challenges {
+            // Subgroup checks are done when the proof is deserialized, as
+            // `Proof::from_bytes` rejects points that aren't torsion free.
 
-            // Compute '[D]_1'
-            let D = self
-                .compute_linearization_commitment(
-                    &alpha,
-                    &beta,
-                    &gamma,
-                    (
-                        &range_sep_challenge,
-                        &logic_sep_challenge,
-                        &fixed_base_sep_challenge,
-                        &var_base_sep_challenge,
-                    ),
-                    &z_challenge,
-                    &u_challenge,
-                    l1_eval,
-                    verifier_key,
-                    &domain,
+            // In order for the Verifier and Prover to have the same view in the
+            // non-interactive setting Both parties must commit the same
+            // elements into the transcript Below the verifier will simulate
+            // an interaction with the prover by adding the same elements
+            // that the prover added into the transcript, hence generating the
+            // same challenges
+            //
+            // Add commitment to witness polynomials to transcript
+            transcript.append_commitment(b"a_comm", &self.a_comm);
+            transcript.append_commitment(b"b_comm", &self.b_comm);
+            transcript.append_commitment(b"c_comm", &self.c_comm);
+            transcript.append_commitment(b"d_comm", &self.d_comm);
+
+            // Compute beta and gamma challenges
+            let beta = transcript.challenge_scalar(b"beta");
+            transcript.append_scalar(b"beta", &beta);
+            let gamma = transcript.challenge_scalar(b"gamma");
+
+            // Add commitment to permutation polynomial to transcript
+            transcript.append_commitment(b"z_comm", &self.z_comm);
+
+            // Compute quotient challenge
+            let alpha = transcript.challenge_scalar(b"alpha");
+            let range_sep_challenge =
+                transcript.challenge_scalar(b"range separation challenge");
+            let logic_sep_challenge =
+                transcript.challenge_scalar(b"logic separation challenge");
+            let fixed_base_sep_challenge =
+                transcript.challenge_scalar(b"fixed base separation challenge");
+            let var_base_sep_challenge = transcript
+                .challenge_scalar(b"variable base separation challenge");
+            let custom_sep_challenge = match custom_gates {
+                true => {
+                    transcript.challenge_scalar(b"custom separation challenge")
+                }
+                false => BlsScalar::zero(),
+            };
+
+            // Add commitment to quotient polynomial to transcript
+            for (label, t_comm) in
+                QUOTIENT_LABELS.iter().zip(self.quotient_comms())
+            {
+                transcript.append_commitment(label.as_bytes(), t_comm);
+            }
+
+            Challenges {
+                beta,
+                gamma,
+                alpha,
+                range_sep: range_sep_challenge,
+                logic_sep: logic_sep_challenge,
+                fixed_base_sep: fixed_base_sep_challenge,
+                var_base_sep: var_base_sep_challenge,
+                custom_sep: custom_sep_challenge,
+                z: BlsScalar::zero(),
+                v: BlsScalar::zero(),
+                v_w: BlsScalar::zero(),
+                u: BlsScalar::zero(),
+            }
+        }
+
+        /// Replays the transcript of the proof and computes the linearization
+        /// commitment `[D]_1` along with the evaluation `r_0`, returning them
+        /// with the challenges in a [`VerificationReport`].
+        #[allow(non_snake_case)]
+        fn compute_linearization<T: TranscriptProtocol>(
+            &self,
+            verifier_key: &VerifierKey,
+            linearization: &LinearizationContext,
+            transcript: &mut T,
+            pub_inputs: &PublicInputs,
+            constants: Option<&ConstantPublicInputs>,
+            trace: &mut VerifyTrace,
+        ) -> Result<(VerificationReport, Challenges, BlsScalar, G1Affine), Error>
+        {
+            // The domain is computed once along with the linearization layout
+            let domain = linearization.domain();
+
+            self.check_quotient_chunks(linearization)?;
+            let challenges = self.derive_challenges(
+                transcript,
+                !linearization.custom_gates().is_empty(),
+            );
+            trace.step(
+                "challenges derived",
+                format_args!("z: {:?}", challenges.z),
+            );
+
+            let mut lagrange = LagrangeEvaluations::gather(
+                domain,
+                &challenges.z,
+                pub_inputs,
+                constants,
+            )?;
+            LagrangeEvaluations::invert_all(core::slice::from_mut(
+                &mut lagrange,
+            ))?;
+
+            Ok(self.linearize(
+                verifier_key,
+                linearization,
+                challenges,
+                &lagrange,
+                trace,
+            ))
+        }
+
+        /// Computes the linearization commitment `[D]_1` along with the
+        /// evaluation `r_0` from the derived challenges and the inverted
+        /// Lagrange denominators, returning them with the challenges in a
+        /// [`VerificationReport`].
+        #[allow(non_snake_case)]
+        fn linearize(
+            &self,
+            verifier_key: &VerifierKey,
+            linearization: &LinearizationContext,
+            challenges: Challenges,
+            lagrange: &LagrangeEvaluations,
+            trace: &mut VerifyTrace,
+        ) -> (VerificationReport, Challenges, BlsScalar, G1Affine) {
+            let domain = linearization.domain();
+
+            let Challenges {
+                beta,
+                gamma,
+                alpha,
+                range_sep: range_sep_challenge,
+                logic_sep: logic_sep_challenge,
+                fixed_base_sep: fixed_base_sep_challenge,
+                var_base_sep: var_base_sep_challenge,
+                custom_sep: custom_sep_challenge,
+                z: z_challenge,
+                u: u_challenge,
+                ..
+            } = challenges;
+
+            // Compute the first lagrange polynomial and the public inputs
+            // polynomial evaluated at challenge `z`
+            let (l1_eval, pi_eval) = lagrange.evaluations();
+
+            // Compute '[D]_1'
+            let D = self
+                .compute_linearization_commitment(
+                    &alpha,
+                    &beta,
+                    &gamma,
+                    (
+                        &range_sep_challenge,
+                        &logic_sep_challenge,
+                        &fixed_base_sep_challenge,
+                        &var_base_sep_challenge,
+                        &custom_sep_challenge,
+                    ),
+                    &z_challenge,
+                    &u_challenge,
+                    l1_eval,
+                    verifier_key,
+                    linearization,
+                    domain,
+                    trace,
                 )
                 .0;
 
-            // Evaluate public inputs
-            let pi_eval =
-                compute_barycentric_eval(pub_inputs, &z_challenge, &domain);
-
             // Compute r_0
             let r_0_eval = pi_eval
                 - l1_eval * alpha.square()
@@ -344,6 +1314,76 @@ pub(crate) mod alloc {
                         + gamma)
                     * (self.evaluations.d_eval + gamma)
                     * self.evaluations.z_eval;
+            trace.step(
+                "quotient evaluation computed",
+                format_args!("r_0: {:?}", r_0_eval),
+            );
+
+            let report = VerificationReport::new(challenges, pi_eval, r_0_eval);
+
+            (report, challenges, r_0_eval, D)
+        }
+
+        /// Replays the transcript of the proof and computes the two G_1
+        /// elements that have to be paired with `[x]_2` and `[1]_2`
+        /// respectively for the proof to be valid.
+        #[allow(non_snake_case)]
+        #[allow(clippy::too_many_arguments)]
+        pub(crate) fn compute_pairing_points<T: TranscriptProtocol>(
+            &self,
+            verifier_key: &VerifierKey,
+            linearization: &LinearizationContext,
+            transcript: &mut T,
+            opening_key: &OpeningKey,
+            pub_inputs: &PublicInputs,
+            constants: Option<&ConstantPublicInputs>,
+            trace: &mut VerifyTrace,
+        ) -> Result<(VerificationReport, G1Affine, G1Affine), Error> {
+            let (report, challenges, r_0_eval, D) = self
+                .compute_linearization(
+                    verifier_key,
+                    linearization,
+                    transcript,
+                    pub_inputs,
+                    constants,
+                    trace,
+                )?;
+
+            let (left, right) = self.pairing_points(
+                verifier_key,
+                linearization.domain(),
+                opening_key,
+                challenges,
+                r_0_eval,
+                D,
+                trace,
+            );
+
+            Ok((report, left, right))
+        }
+
+        /// Computes the two G_1 elements of the pairing check from the
+        /// challenges, the evaluation `r_0` and the linearization commitment
+        /// `[D]_1` of the proof.
+        #[allow(non_snake_case)]
+        #[allow(clippy::too_many_arguments)]
+        fn pairing_points(
+            &self,
+            verifier_key: &VerifierKey,
+            domain: &EvaluationDomain,
+            opening_key: &OpeningKey,
+            challenges: Challenges,
+            r_0_eval: BlsScalar,
+            D: G1Affine,
+            trace: &mut VerifyTrace,
+        ) -> (G1Affine, G1Affine) {
+            let Challenges {
+                z: z_challenge,
+                v: v_challenge,
+                v_w: v_w_challenge,
+                u: u_challenge,
+                ..
+            } = challenges;
 
             // Coefficients to compute [E]_1
             let mut v_coeffs_E = vec![v_challenge];
@@ -454,21 +1494,129 @@ pub(crate) mod alloc {
                 scalarmuls[V_MAX_DEGREE + 2] + scalarmuls[V_MAX_DEGREE + 3] + F
                     - E,
             );
+            trace.step(
+                "pairing points computed",
+                format_args!("scalar multiplications: {}", scalarmuls.len()),
+            );
 
-            // Compute the two pairings and subtract them
-            let pairing = dusk_bls12_381::multi_miller_loop(&[
-                (&left, &opening_key.prepared_x_h),
-                (&right, &opening_key.prepared_h),
-            ])
-            .final_exponentiation();
+            (left, right)
+        }
+
+        /// Replays the transcript of the proof and splits its pairing check
+        /// into the KZG openings at the challenge `z` and at the shifted
+        /// point `z·ω`.
+        ///
+        /// Checking both openings with [`OpeningKey::batch_check`] is
+        /// equivalent to verifying the proof, while allowing the openings of
+        /// many proofs to be checked at once.
+        #[allow(non_snake_case)]
+        pub(crate) fn compute_openings<T: TranscriptProtocol>(
+            &self,
+            verifier_key: &VerifierKey,
+            linearization: &LinearizationContext,
+            transcript: &mut T,
+            pub_inputs: &PublicInputs,
+        ) -> Result<[(BlsScalar, OpeningProof); 2], Error> {
+            let domain = linearization.domain();
+
+            let (_, challenges, r_0_eval, D) = self.compute_linearization(
+                verifier_key,
+                linearization,
+                transcript,
+                pub_inputs,
+                None,
+                &mut VerifyTrace::new("openings"),
+            )?;
+            let Challenges {
+                z: z_challenge,
+                v: v_challenge,
+                v_w: v_w_challenge,
+                u: u_challenge,
+                ..
+            } = challenges;
+
+            // Compute the powers of the v_challenge
+            let mut v_coeffs = vec![v_challenge];
+            for i in 1..V_MAX_DEGREE {
+                v_coeffs.push(v_coeffs[i - 1] * v_challenge);
+            }
+
+            // E_z = -r_0 + (v)a + (v^2)b + (v^3)c + (v^4)d +
+            // + (v^5)s_sigma_1 + (v^6)s_sigma_2 + (v^7)s_sigma_3
+            let E_z_evals = [
+                self.evaluations.a_eval,
+                self.evaluations.b_eval,
+                self.evaluations.c_eval,
+                self.evaluations.d_eval,
+                self.evaluations.s_sigma_1_eval,
+                self.evaluations.s_sigma_2_eval,
+                self.evaluations.s_sigma_3_eval,
+            ];
+            let E_z: BlsScalar = E_z_evals
+                .iter()
+                .zip(v_coeffs.iter())
+                .map(|(eval, coeff)| eval * coeff)
+                .sum::<BlsScalar>()
+                - r_0_eval;
 
-            // Return 'ProofVerificationError' if the two
-            // pairings are not equal, continue otherwise
-            if pairing != dusk_bls12_381::Gt::identity() {
-                return Err(Error::ProofVerificationError);
+            // [F_z]_1 = [D]_1 - (u)[z]_1 + (v)[a]_1 + (v^2)[b]_1 + (v^3)[c]_1
+            // + (v^4)[d]_1 + (v^5)[s_sigma_1]_1 + (v^6)[s_sigma_2]_1 +
+            // + (v^7)[s_sigma_3]_1
+            //
+            // The linearization commitment `[D]_1` includes the shifted
+            // opening of `[z]_1`, which is removed here
+            let F_z_points = [
+                self.a_comm.0,
+                self.b_comm.0,
+                self.c_comm.0,
+                self.d_comm.0,
+                verifier_key.permutation.s_sigma_1.0,
+                verifier_key.permutation.s_sigma_2.0,
+                verifier_key.permutation.s_sigma_3.0,
+                self.z_comm.0,
+            ];
+            v_coeffs.push(-u_challenge);
+            let F_z = msm_variable_base(&F_z_points, &v_coeffs) + D;
+
+            // E_zw = z_w + (v_w)a_w + (v_w^2)b_w + (v_w^3)d_w
+            let v_w_coeffs = [
+                BlsScalar::one(),
+                v_w_challenge,
+                v_w_challenge.square(),
+                v_w_challenge.square() * v_w_challenge,
+            ];
+            let E_zw_evals = [
+                self.evaluations.z_eval,
+                self.evaluations.a_w_eval,
+                self.evaluations.b_w_eval,
+                self.evaluations.d_w_eval,
+            ];
+            let E_zw: BlsScalar = E_zw_evals
+                .iter()
+                .zip(v_w_coeffs.iter())
+                .map(|(eval, coeff)| eval * coeff)
+                .sum();
+
+            // [F_zw]_1 = [z]_1 + (v_w)[a]_1 + (v_w^2)[b]_1 + (v_w^3)[d]_1
+            let F_zw_points =
+                [self.z_comm.0, self.a_comm.0, self.b_comm.0, self.d_comm.0];
+            let F_zw = msm_variable_base(&F_zw_points, &v_w_coeffs);
+
+            let opening_z = OpeningProof {
+                commitment_to_witness: self.w_z_chall_comm,
+                evaluated_point: E_z,
+                commitment_to_polynomial: Commitment::from(F_z),
+            };
+            let opening_zw = OpeningProof {
+                commitment_to_witness: self.w_z_chall_w_comm,
+                evaluated_point: E_zw,
+                commitment_to_polynomial: Commitment::from(F_zw),
             };
 
-            Ok(())
+            Ok([
+                (z_challenge, opening_z),
+                (z_challenge * domain.group_gen, opening_zw),
+            ])
         }
 
         // Commitment to [r]_1
@@ -483,97 +1631,227 @@ pub(crate) mod alloc {
                 logic_sep_challenge,
                 fixed_base_sep_challenge,
                 var_base_sep_challenge,
-            ): (&BlsScalar, &BlsScalar, &BlsScalar, &BlsScalar),
+                custom_sep_challenge,
+            ): (
+                &BlsScalar,
+                &BlsScalar,
+                &BlsScalar,
+                &BlsScalar,
+                &BlsScalar,
+            ),
             z_challenge: &BlsScalar,
             u_challenge: &BlsScalar,
             l1_eval: BlsScalar,
             verifier_key: &VerifierKey,
+            linearization: &LinearizationContext,
             domain: &EvaluationDomain,
+            trace: &mut VerifyTrace,
         ) -> Commitment {
-            let mut scalars: Vec<_> = Vec::with_capacity(6);
-            let mut points: Vec<G1Affine> = Vec::with_capacity(6);
+            // The scalars are computed in the layout of the linearization
+            // context, which only holds the verifier key commitments
+            let custom_gates = linearization.custom_gates();
+            let mut scalars: Vec<_> =
+                Vec::with_capacity(11 + custom_gates.len());
 
-            verifier_key.arithmetic.compute_linearization_commitment(
-                &mut scalars,
-                &mut points,
-                &self.evaluations,
-            );
+            verifier_key
+                .arithmetic
+                .compute_linearization_scalars(&mut scalars, &self.evaluations);
 
-            verifier_key.range.compute_linearization_commitment(
+            verifier_key.range.compute_linearization_scalars(
                 range_sep_challenge,
                 &mut scalars,
-                &mut points,
                 &self.evaluations,
             );
 
-            verifier_key.logic.compute_linearization_commitment(
+            verifier_key.logic.compute_linearization_scalars(
                 logic_sep_challenge,
                 &mut scalars,
-                &mut points,
                 &self.evaluations,
             );
 
-            verifier_key.fixed_base.compute_linearization_commitment(
+            verifier_key.fixed_base.compute_linearization_scalars(
                 fixed_base_sep_challenge,
                 &mut scalars,
-                &mut points,
                 &self.evaluations,
             );
 
-            verifier_key.variable_base.compute_linearization_commitment(
+            verifier_key.variable_base.compute_linearization_scalars(
                 var_base_sep_challenge,
                 &mut scalars,
-                &mut points,
                 &self.evaluations,
             );
 
-            verifier_key.permutation.compute_linearization_commitment(
-                &mut scalars,
-                &mut points,
-                &self.evaluations,
-                z_challenge,
-                u_challenge,
-                (alpha, beta, gamma),
-                &l1_eval,
-                self.z_comm.0,
+            let z_scalar =
+                verifier_key.permutation.compute_linearization_scalars(
+                    &mut scalars,
+                    &self.evaluations,
+                    z_challenge,
+                    u_challenge,
+                    (alpha, beta, gamma),
+                    &l1_eval,
+                );
+
+            custom_gates
+                .iter()
+                .zip(custom::separation_challenges(*custom_sep_challenge))
+                .for_each(|(gate, sep)| {
+                    gate.compute_linearization_scalars(
+                        &sep,
+                        &mut scalars,
+                        &self.evaluations,
+                    )
+                });
+
+            // The commitments of the proof are `[z]_1` and the quotient
+            // commitment `sum_i z^(i·m) · [t_i]_1`, for the chunk size `m`,
+            // scaled by the evaluation of the vanishing polynomial
+            let chunks = linearization.quotient_layout().chunks();
+            let mut proof_scalars = Vec::with_capacity(1 + chunks);
+            let mut proof_points = Vec::with_capacity(1 + chunks);
+
+            proof_scalars.push(z_scalar);
+            proof_points.push(self.z_comm.0);
+
+            let chunk_size =
+                linearization.quotient_layout().chunk_factor() * domain.size();
+            let z_n = z_challenge.pow(&[chunk_size as u64, 0, 0, 0]);
+            let z_h_eval = -domain.evaluate_vanishing_polynomial(z_challenge);
+
+            for (t_comm, z_in) in
+                self.quotient_comms().zip(powers_of(&z_n, chunks - 1))
+            {
+                proof_scalars.push(z_in * z_h_eval);
+                proof_points.push(t_comm.0);
+            }
+
+            let commitment = linearization.commit(&scalars)
+                + msm_variable_base(&proof_points, &proof_scalars);
+            trace.step(
+                "linearization commitment computed",
+                format_args!(
+                    "msm size: {}",
+                    scalars.len() + proof_points.len()
+                ),
             );
 
-            let domain_size = domain.size();
-            let z_h_eval = -domain.evaluate_vanishing_polynomial(z_challenge);
+            Commitment::from(commitment)
+        }
+    }
 
-            let z_n =
-                z_challenge.pow(&[domain_size as u64, 0, 0, 0]) * z_h_eval;
-            let z_two_n =
-                z_challenge.pow(&[2 * domain_size as u64, 0, 0, 0]) * z_h_eval;
-            let z_three_n =
-                z_challenge.pow(&[3 * domain_size as u64, 0, 0, 0]) * z_h_eval;
+    /// Evaluate the vanishing polynomial of the domain at the challenge `z`.
+    ///
+    /// The evaluation is zero if `z` is an element of the domain, which
+    /// cancels the quotient polynomial out of the verifier equation. The
+    /// challenge is uniformly distributed, so this happens with probability
+    /// `n / r`, below `2^-222` for every supported domain size. Such a
+    /// challenge is rejected rather than derived again from the transcript,
+    /// as a prover hitting it can prove again with fresh blinding scalars.
+    pub(crate) fn compute_vanishing_evaluation(
+        domain: &EvaluationDomain,
+        z_challenge: &BlsScalar,
+    ) -> Result<BlsScalar, Error> {
+        let z_h_eval = domain.evaluate_vanishing_polynomial(z_challenge);
 
-            scalars.push(z_h_eval);
-            points.push(self.t_low_comm.0);
+        if z_h_eval == BlsScalar::zero() {
+            return Err(Error::EvaluationChallengeInDomain);
+        }
 
-            scalars.push(z_n);
-            points.push(self.t_mid_comm.0);
+        Ok(z_h_eval)
+    }
 
-            scalars.push(z_two_n);
-            points.push(self.t_high_comm.0);
+    /// Evaluations at the challenge `z` of a proof of the first Lagrange
+    /// polynomial `L_1` and of the public inputs polynomial.
+    ///
+    /// Both are sums of `L_i(z) = (z^n - 1) / (n·(ω^-i·z - 1))` terms, with
+    /// `ω^0 = 1` for `L_1`. Their denominators are gathered first, so the
+    /// ones of every proof of a batch are inverted at once with
+    /// [`Self::invert_all`], and the evaluations are then computed from the
+    /// inverses.
+    pub(crate) struct LagrangeEvaluations {
+        /// `(z^n - 1) / n`
+        numerator: BlsScalar,
+        /// Non-zero public inputs, the constant ones included
+        values: Vec<BlsScalar>,
+        /// Denominator of `L_1` followed by the ones of the public inputs,
+        /// replaced by their inverses with [`Self::invert_all`]
+        denominators: Vec<BlsScalar>,
+    }
 
-            scalars.push(z_three_n);
-            points.push(self.t_fourth_comm.0);
+    impl LagrangeEvaluations {
+        /// Gather the denominators of the evaluations at `z_challenge`,
+        /// rejecting a challenge in the domain, where they vanish.
+        pub(crate) fn gather(
+            domain: &EvaluationDomain,
+            z_challenge: &BlsScalar,
+            pub_inputs: &PublicInputs,
+            constants: Option<&ConstantPublicInputs>,
+        ) -> Result<Self, Error> {
+            let z_h_eval = compute_vanishing_evaluation(domain, z_challenge)?;
+
+            let mut values = Vec::with_capacity(pub_inputs.len());
+            let mut denominators = Vec::with_capacity(pub_inputs.len() + 1);
+            denominators.push(z_challenge - BlsScalar::one());
+
+            pub_inputs
+                .values_and_roots(domain)
+                .chain(constants.into_iter().flat_map(|c| c.values_and_roots()))
+                .filter(|(value, _)| *value != BlsScalar::zero())
+                .for_each(|(value, root)| {
+                    values.push(value);
+                    denominators.push(root * z_challenge - BlsScalar::one());
+                });
+
+            Ok(Self {
+                numerator: z_h_eval * domain.size_inv,
+                values,
+                denominators,
+            })
+        }
+
+        /// Invert the denominators of all the `evaluations` with a single
+        /// field inversion.
+        pub(crate) fn invert_all(
+            evaluations: &mut [Self],
+        ) -> Result<(), Error> {
+            let mut denominators: Vec<BlsScalar> = evaluations
+                .iter()
+                .flat_map(|evaluations| evaluations.denominators.iter())
+                .copied()
+                .collect();
+            batch_inversion_checked(&mut denominators)
+                .map_err(|_| Error::NonInvertibleDenominator)?;
+
+            evaluations
+                .iter_mut()
+                .flat_map(|evaluations| evaluations.denominators.iter_mut())
+                .zip(denominators)
+                .for_each(|(denominator, inverse)| *denominator = inverse);
 
-            Commitment::from(msm_variable_base(&points, &scalars))
+            Ok(())
         }
-    }
 
-    fn compute_first_lagrange_evaluation(
-        domain: &EvaluationDomain,
-        z_h_eval: &BlsScalar,
-        z_challenge: &BlsScalar,
-    ) -> BlsScalar {
-        let n_fr = BlsScalar::from(domain.size() as u64);
-        let denom = n_fr * (z_challenge - BlsScalar::one());
-        z_h_eval * denom.invert().unwrap()
+        /// Evaluations of `L_1` and of the public inputs polynomial, once the
+        /// denominators are inverted
+        pub(crate) fn evaluations(&self) -> (BlsScalar, BlsScalar) {
+            let l1_eval = self.numerator * self.denominators[0];
+            let pi_eval = sum_of_products(
+                self.values.iter().zip(self.denominators[1..].iter()),
+            ) * self.numerator;
+
+            (l1_eval, pi_eval)
+        }
     }
 
+    /// Evaluations with fewer than one in `SEQUENTIAL_BARYCENTRIC_RATIO`
+    /// non-zero entries are summed sequentially even with the `std` feature,
+    /// as the parallel sum walks every root of the domain while the
+    /// sequential one only visits the non-zero entries. Only the prover
+    /// evaluates the public inputs this way, the verifier evaluating their
+    /// Lagrange coefficients instead. The ratio is tuned with the `verify`
+    /// benchmark.
+    #[cfg(feature = "std")]
+    pub(crate) const SEQUENTIAL_BARYCENTRIC_RATIO: usize = 64;
+
     pub(crate) fn compute_barycentric_eval(
         evaluations: &[BlsScalar],
         point: &BlsScalar,
@@ -583,12 +1861,80 @@ pub(crate) mod alloc {
             - BlsScalar::one())
             * domain.size_inv;
 
-        // Indices with non-zero evaluations
+        #[cfg(feature = "std")]
+        let result = {
+            let non_zero = evaluations
+                .iter()
+                .filter(|&eval| eval != &BlsScalar::zero())
+                .count();
+
+            match non_zero * SEQUENTIAL_BARYCENTRIC_RATIO < evaluations.len() {
+                true => barycentric_sum_sequential(evaluations, point, domain),
+                false => barycentric_sum_parallel(evaluations, point, domain),
+            }
+        };
+
         #[cfg(not(feature = "std"))]
-        let range = (0..evaluations.len()).into_iter();
+        let result = barycentric_sum_sequential(evaluations, point, domain);
 
-        #[cfg(feature = "std")]
-        let range = (0..evaluations.len()).into_par_iter();
+        result * numerator
+    }
+
+    /// Sum the `eval_i / denominator_i` terms as a single fraction, so that
+    /// only one inversion is needed and no intermediate vectors are
+    /// allocated. Each chunk derives its first root of unity once and steps
+    /// through the following ones with a single multiplication. Zero
+    /// denominators are skipped, as `batch_inversion` does.
+    #[cfg(feature = "std")]
+    pub(crate) fn barycentric_sum_parallel(
+        evaluations: &[BlsScalar],
+        point: &BlsScalar,
+        domain: &EvaluationDomain,
+    ) -> BlsScalar {
+        const CHUNK_SIZE: usize = 64;
+
+        let zero = || (BlsScalar::zero(), BlsScalar::one());
+        let add = |(n1, d1): (BlsScalar, BlsScalar),
+                   (n2, d2): (BlsScalar, BlsScalar)| {
+            (n1 * d2 + n2 * d1, d1 * d2)
+        };
+
+        let (numerators, denominators) = evaluations
+            .par_chunks(CHUNK_SIZE)
+            .enumerate()
+            .map(|(chunk, evaluations)| {
+                let offset = (chunk * CHUNK_SIZE) as u64;
+                let mut root = domain.group_gen_inv.pow(&[offset, 0, 0, 0]);
+
+                evaluations.iter().fold(zero(), |acc, eval| {
+                    let denominator = root * point - BlsScalar::one();
+                    root *= domain.group_gen_inv;
+
+                    if eval == &BlsScalar::zero()
+                        || denominator == BlsScalar::zero()
+                    {
+                        acc
+                    } else {
+                        add(acc, (*eval, denominator))
+                    }
+                })
+            })
+            .reduce(zero, add);
+
+        // Guaranteed to be nonzero
+        numerators * denominators.invert().unwrap()
+    }
+
+    /// Sum the `eval_i / denominator_i` terms of the non-zero evaluations,
+    /// deriving the root of unity of each of them apart and inverting the
+    /// denominators in a batch.
+    pub(crate) fn barycentric_sum_sequential(
+        evaluations: &[BlsScalar],
+        point: &BlsScalar,
+        domain: &EvaluationDomain,
+    ) -> BlsScalar {
+        // Indices with non-zero evaluations
+        let range = 0..evaluations.len();
 
         let non_zero_evaluations: Vec<usize> = range
             .filter(|&i| {
@@ -598,14 +1944,9 @@ pub(crate) mod alloc {
             .collect();
 
         // Only compute the denominators with non-zero evaluations
-        #[cfg(not(feature = "std"))]
-        let range = (0..non_zero_evaluations.len()).into_iter();
-
-        #[cfg(feature = "std")]
-        let range = (0..non_zero_evaluations.len()).into_par_iter();
+        let range = 0..non_zero_evaluations.len();
 
         let mut denominators: Vec<BlsScalar> = range
-            .clone()
             .map(|i| {
                 // index of non-zero evaluation
                 let index = non_zero_evaluations[i];
@@ -616,61 +1957,611 @@ pub(crate) mod alloc {
             .collect();
         batch_inversion(&mut denominators);
 
-        let result: BlsScalar = range
-            .map(|i| {
-                let eval_index = non_zero_evaluations[i];
-                let eval = evaluations[eval_index];
-
-                denominators[i] * eval
-            })
-            .sum();
-
-        result * numerator
+        sum_of_products(
+            non_zero_evaluations
+                .iter()
+                .map(|&index| &evaluations[index])
+                .zip(denominators.iter()),
+        )
     }
 }
 
 #[cfg(test)]
 mod proof_tests {
     use super::*;
-    use dusk_bls12_381::BlsScalar;
+    use dusk_bls12_381::{BlsScalar, G1Affine};
     use ff::Field;
     use rand_core::OsRng;
 
+    #[cfg(feature = "alloc")]
     #[test]
-    fn test_dusk_bytes_serde_proof() {
-        let proof = Proof {
-            a_comm: Commitment::default(),
-            b_comm: Commitment::default(),
-            c_comm: Commitment::default(),
-            d_comm: Commitment::default(),
-            z_comm: Commitment::default(),
-            t_low_comm: Commitment::default(),
-            t_mid_comm: Commitment::default(),
-            t_high_comm: Commitment::default(),
-            t_fourth_comm: Commitment::default(),
-            w_z_chall_comm: Commitment::default(),
-            w_z_chall_w_comm: Commitment::default(),
-            evaluations: ProofEvaluations {
-                a_eval: BlsScalar::random(&mut OsRng),
-                b_eval: BlsScalar::random(&mut OsRng),
-                c_eval: BlsScalar::random(&mut OsRng),
-                d_eval: BlsScalar::random(&mut OsRng),
-                a_w_eval: BlsScalar::random(&mut OsRng),
-                b_w_eval: BlsScalar::random(&mut OsRng),
-                d_w_eval: BlsScalar::random(&mut OsRng),
-                q_arith_eval: BlsScalar::random(&mut OsRng),
-                q_c_eval: BlsScalar::random(&mut OsRng),
-                q_l_eval: BlsScalar::random(&mut OsRng),
-                q_r_eval: BlsScalar::random(&mut OsRng),
-                s_sigma_1_eval: BlsScalar::random(&mut OsRng),
-                s_sigma_2_eval: BlsScalar::random(&mut OsRng),
-                s_sigma_3_eval: BlsScalar::random(&mut OsRng),
-                z_eval: BlsScalar::random(&mut OsRng),
-            },
+    fn test_first_lagrange_evaluation_at_one() {
+        use super::alloc::LagrangeEvaluations;
+        use crate::fft::EvaluationDomain;
+        use crate::proof_system::PublicInputs;
+
+        let domain = EvaluationDomain::new(16).unwrap();
+        let public_inputs = PublicInputs::dense(&[BlsScalar::from(7)]);
+
+        // the denominator of `L_1` vanishes at one, which is in the domain
+        assert_eq!(
+            LagrangeEvaluations::gather(
+                &domain,
+                &BlsScalar::one(),
+                &public_inputs,
+                None
+            )
+            .err(),
+            Some(Error::EvaluationChallengeInDomain)
+        );
+
+        let z_challenge = BlsScalar::from(2);
+        let mut lagrange = LagrangeEvaluations::gather(
+            &domain,
+            &z_challenge,
+            &public_inputs,
+            None,
+        )
+        .unwrap();
+        LagrangeEvaluations::invert_all(core::slice::from_mut(&mut lagrange))
+            .unwrap();
+
+        let (l1_eval, pi_eval) = lagrange.evaluations();
+        let expected = domain.evaluate_all_lagrange_coefficients(z_challenge);
+        assert_eq!(l1_eval, expected[0]);
+        assert_eq!(pi_eval, expected[0] * BlsScalar::from(7));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_lagrange_evaluations_batch() {
+        use super::alloc::LagrangeEvaluations;
+        use crate::fft::EvaluationDomain;
+        use crate::proof_system::PublicInputs;
+
+        let domains = [
+            EvaluationDomain::new(16).unwrap(),
+            EvaluationDomain::new(64).unwrap(),
+        ];
+        let public_inputs = [
+            PublicInputs::dense(&[BlsScalar::from(3), BlsScalar::zero()]),
+            [(5, BlsScalar::from(11)), (40, BlsScalar::from(13))]
+                .into_iter()
+                .collect(),
+        ];
+        let z_challenges =
+            [BlsScalar::random(&mut OsRng), BlsScalar::random(&mut OsRng)];
+
+        let gather = |i: usize| {
+            LagrangeEvaluations::gather(
+                &domains[i],
+                &z_challenges[i],
+                &public_inputs[i],
+                None,
+            )
+            .unwrap()
+        };
+
+        // inverting the denominators of a batch at once yields the same
+        // evaluations as inverting them proof by proof
+        let mut batch = [gather(0), gather(1)];
+        LagrangeEvaluations::invert_all(&mut batch).unwrap();
+
+        for (i, lagrange) in batch.iter().enumerate() {
+            let mut single = gather(i);
+            LagrangeEvaluations::invert_all(core::slice::from_mut(&mut single))
+                .unwrap();
+
+            assert_eq!(lagrange.evaluations(), single.evaluations());
+            // against every Lagrange coefficient of the domain
+            let coefficients =
+                domains[i].evaluate_all_lagrange_coefficients(z_challenges[i]);
+            let expected = public_inputs[i]
+                .iter()
+                .fold(BlsScalar::zero(), |acc, (index, value)| {
+                    acc + coefficients[index] * value
+                });
+            assert_eq!(lagrange.evaluations().1, expected);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_vanishing_evaluation_in_domain() {
+        use crate::fft::EvaluationDomain;
+
+        let domain = EvaluationDomain::new(16).unwrap();
+
+        for element in domain.elements() {
+            assert_eq!(
+                alloc::compute_vanishing_evaluation(&domain, &element),
+                Err(Error::EvaluationChallengeInDomain)
+            );
+        }
+
+        let z_challenge = BlsScalar::random(&mut OsRng);
+        assert_eq!(
+            alloc::compute_vanishing_evaluation(&domain, &z_challenge),
+            Ok(domain.evaluate_vanishing_polynomial(&z_challenge))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_barycentric_dispatch() {
+        use crate::fft::EvaluationDomain;
+        use ::alloc::vec::Vec;
+
+        let domain = EvaluationDomain::new(1024).unwrap();
+        let threshold = domain.size() / alloc::SEQUENTIAL_BARYCENTRIC_RATIO;
+        let point = BlsScalar::random(&mut OsRng);
+
+        for non_zero in [threshold - 1, threshold, threshold + 1] {
+            let evaluations: Vec<BlsScalar> = (0..domain.size())
+                .map(|i| match i % 7 == 0 && i / 7 < non_zero {
+                    true => BlsScalar::random(&mut OsRng),
+                    false => BlsScalar::zero(),
+                })
+                .collect();
+
+            let sequential = alloc::barycentric_sum_sequential(
+                &evaluations,
+                &point,
+                &domain,
+            );
+            let parallel =
+                alloc::barycentric_sum_parallel(&evaluations, &point, &domain);
+            assert_eq!(sequential, parallel);
+
+            let numerator = (point.pow(&[domain.size() as u64, 0, 0, 0])
+                - BlsScalar::one())
+                * domain.size_inv;
+            assert_eq!(
+                alloc::compute_barycentric_eval(&evaluations, &point, &domain),
+                sequential * numerator
+            );
+        }
+
+        // the sums skip the terms of a point in the domain
+        let evaluations = [BlsScalar::one(); 16];
+        let point = domain.group_gen;
+        assert_eq!(
+            alloc::barycentric_sum_sequential(&evaluations, &point, &domain),
+            alloc::barycentric_sum_parallel(&evaluations, &point, &domain)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_barycentric_eval() {
+        use super::alloc::LagrangeEvaluations;
+        use crate::fft::{EvaluationDomain, Polynomial};
+        use crate::proof_system::{ConstantPublicInputs, PublicInputs};
+        use ::alloc::vec::Vec;
+
+        let domain = EvaluationDomain::new(1024).unwrap();
+
+        // sparse public inputs, as produced by the composer
+        let evaluations: Vec<BlsScalar> = (0..domain.size())
+            .map(|i| match i % 3 {
+                0 => BlsScalar::zero(),
+                _ => BlsScalar::random(&mut OsRng),
+            })
+            .collect();
+        let point = BlsScalar::random(&mut OsRng);
+
+        let poly = Polynomial::from_coefficients_vec(domain.ifft(&evaluations));
+        let expected = poly.evaluate(&point);
+
+        let eval =
+            alloc::compute_barycentric_eval(&evaluations, &point, &domain);
+        assert_eq!(eval, expected);
+
+        let eval = alloc::compute_barycentric_eval(&[], &point, &domain);
+        assert_eq!(eval, BlsScalar::zero());
+
+        // the positional evaluation of the verifier only visits the public
+        // inputs
+        let pi_eval = |public_inputs: &PublicInputs, constants| {
+            let mut lagrange = LagrangeEvaluations::gather(
+                &domain,
+                &point,
+                public_inputs,
+                constants,
+            )
+            .unwrap();
+            LagrangeEvaluations::invert_all(core::slice::from_mut(
+                &mut lagrange,
+            ))
+            .unwrap();
+            lagrange.evaluations().1
+        };
+
+        let public_inputs: PublicInputs = evaluations
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, eval)| eval != &BlsScalar::zero())
+            .collect();
+        assert_eq!(pi_eval(&public_inputs, None), expected);
+        assert_eq!(pi_eval(&PublicInputs::dense(&evaluations), None), expected);
+        assert_eq!(pi_eval(&PublicInputs::new(), None), BlsScalar::zero());
+
+        // splitting the constant public inputs apart doesn't change the sum
+        let split = |even| -> PublicInputs {
+            public_inputs
+                .iter()
+                .filter(|(index, _)| (index % 2 == 0) == even)
+                .map(|(index, value)| (index, *value))
+                .collect()
+        };
+        let (constant, variable) = (split(true), split(false));
+        let constant = ConstantPublicInputs::new(constant, &domain, [0; 32]);
+        assert_eq!(pi_eval(&variable, Some(&constant)), expected);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_barycentric_sums() {
+        use crate::fft::EvaluationDomain;
+        use ::alloc::vec::Vec;
+
+        for size in [1, 63, 64, 65, 1000, 1024] {
+            let domain = EvaluationDomain::new(size).unwrap();
+            let evaluations: Vec<BlsScalar> =
+                (0..size).map(|_| BlsScalar::random(&mut OsRng)).collect();
+            let point = BlsScalar::random(&mut OsRng);
+
+            assert_eq!(
+                alloc::barycentric_sum_parallel(&evaluations, &point, &domain),
+                alloc::barycentric_sum_sequential(
+                    &evaluations,
+                    &point,
+                    &domain
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_commitments() {
+        let mut proof = Proof::dummy(&mut OsRng);
+        proof.z_comm = Commitment(G1Affine::generator());
+
+        assert_eq!(proof.commitments().count(), 11);
+
+        // the commitments are yielded in their serialization order
+        let bytes = proof.to_bytes();
+        proof.commitments().enumerate().for_each(|(i, (_, point))| {
+            let offset = i * Commitment::SIZE;
+            assert_eq!(
+                &bytes[offset..offset + Commitment::SIZE],
+                &point.to_bytes()[..]
+            );
+        });
+
+        let (label, point) = proof.commitments().nth(4).unwrap();
+        assert_eq!(label, "z_comm");
+        assert_eq!(point, &G1Affine::generator());
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let proof = Proof::dummy(&mut OsRng);
+        let bytes = proof.to_bytes();
+
+        assert_eq!(Proof::try_from(&bytes[..]), Ok(proof));
+
+        for len in [0, Proof::SIZE - 1, Proof::SIZE + 1] {
+            let mut bytes = bytes.to_vec();
+            bytes.resize(len, 0);
+            assert_eq!(
+                Proof::try_from(&bytes[..]),
+                Err(Error::InvalidProofLength {
+                    expected: Proof::SIZE,
+                    got: len,
+                })
+            );
+        }
+
+        // the bytes are checked as with `from_bytes_checked`
+        let mut bytes = bytes;
+        bytes[..Commitment::SIZE].fill(0xff);
+        assert_eq!(
+            Proof::try_from(&bytes[..]),
+            Proof::from_bytes_checked(&bytes)
+        );
+        assert!(Proof::try_from(&bytes[..]).is_err());
+    }
+
+    #[test]
+    fn test_quick_reject() {
+        let mut proof = Proof::dummy(&mut OsRng);
+        assert!(proof.quick_reject());
+
+        let mut comms = [
+            &mut proof.a_comm,
+            &mut proof.b_comm,
+            &mut proof.c_comm,
+            &mut proof.d_comm,
+            &mut proof.z_comm,
+            &mut proof.t_low_comm,
+            &mut proof.t_mid_comm,
+            &mut proof.t_high_comm,
+            &mut proof.t_fourth_comm,
+            &mut proof.w_z_chall_comm,
+            &mut proof.w_z_chall_w_comm,
+        ];
+        comms.iter_mut().for_each(|comm| {
+            let point = G1Affine::generator() * BlsScalar::random(&mut OsRng);
+            **comm = Commitment(point.into());
+        });
+        assert!(!proof.quick_reject());
+
+        // a single identity commitment is enough to reject the proof
+        let mut other = proof.clone();
+        other.w_z_chall_w_comm = Commitment::default();
+        assert!(other.quick_reject());
+
+        let mut other = proof.clone();
+        other.t_fourth_comm = Commitment::default();
+        assert!(other.quick_reject());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_statement_digest() {
+        let proof = Proof::dummy(&mut OsRng);
+        let pi = [BlsScalar::from(1), BlsScalar::from(2)];
+
+        let digest = proof.statement_digest(&pi);
+        assert_eq!(digest, proof.statement_digest(&pi));
+
+        // any change of the public inputs or of the proof changes the digest
+        assert_ne!(digest, proof.statement_digest(&pi[..1]));
+        assert_ne!(digest, proof.statement_digest(&[pi[1], pi[0]]));
+        assert_ne!(
+            proof.statement_digest(&[]),
+            proof.statement_digest(&[BlsScalar::zero()])
+        );
+        assert_ne!(digest, Proof::dummy(&mut OsRng).statement_digest(&pi));
+    }
+
+    #[test]
+    fn test_from_bytes_checked() {
+        let proof = Proof::dummy(&mut OsRng);
+        let bytes = proof.to_bytes();
+        assert_eq!(Proof::from_bytes_checked(&bytes).unwrap(), proof);
+
+        // find the encodings of a point out of the curve and a point on the
+        // curve out of the prime order subgroup
+        let encode = |x: u8| {
+            let mut point = [0u8; Commitment::SIZE];
+            point[0] = 0x80;
+            point[Commitment::SIZE - 1] = x;
+            point
+        };
+        let decode = |x: u8| -> Option<G1Affine> {
+            G1Affine::from_compressed_unchecked(&encode(x)).into()
         };
+        let off_curve = (1..=u8::MAX).find(|x| decode(*x).is_none()).unwrap();
+        let off_subgroup = (1..=u8::MAX)
+            .find(|x| {
+                decode(*x).map_or(false, |p| !bool::from(p.is_torsion_free()))
+            })
+            .unwrap();
+
+        let mut bytes = proof.to_bytes();
+        bytes[4 * Commitment::SIZE..5 * Commitment::SIZE]
+            .copy_from_slice(&encode(off_curve));
+        assert!(Proof::from_bytes(&bytes).is_err());
+        assert_eq!(
+            Proof::from_bytes_checked(&bytes),
+            Err(Error::ProofCommitmentNotOnCurve { label: "z_comm" })
+        );
+
+        let mut bytes = proof.to_bytes();
+        bytes[7 * Commitment::SIZE..8 * Commitment::SIZE]
+            .copy_from_slice(&encode(off_subgroup));
+        assert!(Proof::from_bytes(&bytes).is_err());
+        assert_eq!(
+            Proof::from_bytes_checked(&bytes),
+            Err(Error::ProofCommitmentNotInSubgroup {
+                label: "t_high_comm"
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_malformed() {
+        use rand_core::RngCore;
+
+        let proof = Proof::dummy(&mut OsRng);
+        let bytes = proof.to_bytes();
+
+        for len in [0, 1, Commitment::SIZE, Proof::SIZE - 1] {
+            assert!(Proof::from_slice(&bytes[..len]).is_err());
+        }
+
+        let ones = [0xff; Proof::SIZE];
+        assert!(Proof::from_bytes(&ones).is_err());
+        assert!(Proof::from_bytes_checked(&ones).is_err());
+
+        // the encodings of the modulus and of all ones are out of the field
+        let mut modulus = (-BlsScalar::one()).to_bytes();
+        modulus[0] += 1;
+        for non_canonical in [modulus, [0xff; BlsScalar::SIZE]] {
+            for i in 0..15 {
+                let offset = 11 * Commitment::SIZE + i * BlsScalar::SIZE;
+                let mut bytes = bytes;
+                bytes[offset..offset + BlsScalar::SIZE]
+                    .copy_from_slice(&non_canonical);
+
+                assert!(Proof::from_bytes(&bytes).is_err());
+                assert!(Proof::from_bytes_checked(&bytes).is_err());
+            }
+        }
+
+        // arbitrary bytes are rejected or decoded to the same encoding
+        for _ in 0..64 {
+            let mut bytes = [0u8; Proof::SIZE];
+            OsRng.fill_bytes(&mut bytes);
+
+            let decoded = Proof::from_bytes(&bytes);
+            assert_eq!(
+                decoded.is_ok(),
+                Proof::from_bytes_checked(&bytes).is_ok()
+            );
+            if let Ok(proof) = decoded {
+                assert_eq!(proof.to_bytes(), bytes);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_framed() {
+        let proofs = [
+            Proof::dummy(&mut OsRng),
+            Proof::dummy(&mut OsRng),
+            Proof::dummy(&mut OsRng),
+        ];
+
+        let mut stream = Vec::new();
+        for proof in &proofs {
+            proof.write_framed(&mut stream).unwrap();
+        }
+        assert_eq!(stream.len(), 3 * (u64::SIZE + Proof::SIZE));
+
+        let mut reader = &stream[..];
+        for proof in &proofs {
+            assert_eq!(&Proof::read_framed(&mut reader).unwrap(), proof);
+        }
+        assert_eq!(
+            Proof::read_framed(&mut reader).unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+
+        // a frame of another length is rejected
+        let mut corrupted = stream.clone();
+        corrupted[..u64::SIZE]
+            .copy_from_slice(&(Proof::SIZE as u64 + 1).to_be_bytes());
+        assert_eq!(
+            Proof::read_framed(&mut &corrupted[..]).unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_serialized_size() {
+        assert_eq!(Proof::serialized_size(), Proof::SIZE);
+        assert_eq!(ProofEvaluations::serialized_size(), ProofEvaluations::SIZE);
+        assert_eq!(
+            Proof::serialized_size(),
+            Proof::dummy(&mut OsRng).to_bytes().len()
+        );
+    }
+
+    #[test]
+    fn test_ct_eq_proof() {
+        let proof = Proof::dummy(&mut OsRng);
+        assert!(bool::from(proof.ct_eq(&proof.clone())));
+
+        let mut other = proof.clone();
+        other.evaluations.z_eval += BlsScalar::one();
+        assert!(bool::from(!proof.ct_eq(&other)));
+
+        let mut other = proof.clone();
+        other.w_z_chall_w_comm = Commitment(G1Affine::generator());
+        assert!(bool::from(!proof.ct_eq(&other)));
+    }
+
+    #[test]
+    fn test_dusk_bytes_serde_proof() {
+        let proof = Proof::dummy(&mut OsRng);
 
         let proof_bytes = proof.to_bytes();
         let got_proof = Proof::from_bytes(&proof_bytes).unwrap();
         assert_eq!(got_proof, proof);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_proof_from_reader() {
+        let proof = Proof::dummy(&mut OsRng);
+        let proof_bytes = proof.to_bytes();
+
+        let mut reader = &proof_bytes[..];
+        let got_proof = Proof::from_reader(&mut reader).unwrap();
+        assert_eq!(got_proof, proof);
+        assert!(reader.is_empty());
+
+        // a proof followed by more data leaves the rest in the reader
+        let mut bytes = proof_bytes.to_vec();
+        bytes.extend_from_slice(&[1, 2, 3]);
+        let mut reader = std::io::Cursor::new(bytes);
+        let got_proof = Proof::from_reader(&mut reader).unwrap();
+        assert_eq!(got_proof, proof);
+        assert_eq!(reader.position() as usize, Proof::serialized_size());
+
+        // truncated input is rejected
+        for len in [0, Commitment::SIZE + 1, Proof::serialized_size() - 1] {
+            let mut reader = &proof_bytes[..len];
+            assert_eq!(
+                Proof::from_reader(&mut reader),
+                Err(Error::NotEnoughBytes)
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_proof() {
+        let proof = Proof::dummy(&mut OsRng);
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(json.len(), 2 * Proof::SIZE + 2);
+
+        let got_proof: Proof = serde_json::from_str(&json).unwrap();
+        assert_eq!(got_proof, proof);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_bincode_proof() {
+        let proof = Proof::dummy(&mut OsRng);
+
+        let bytes = bincode::serialize(&proof).unwrap();
+        let got_proof: Proof = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(got_proof, proof);
+
+        assert!(
+            bincode::deserialize::<Proof>(&bytes[..bytes.len() - 1]).is_err()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_var_bytes_chunks() {
+        // the number of chunks is read from the encoding, so identity
+        // commitments to the extra chunks are kept
+        let mut proof = Proof::dummy(&mut OsRng);
+        proof.extra_chunks = 2;
+
+        let bytes = proof.to_var_bytes();
+        assert_eq!(bytes[1], 2);
+        let restored = Proof::from_var_bytes(&bytes).unwrap();
+        assert_eq!(restored.quotient_chunks(), 6);
+        assert_eq!(restored, proof);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "serialized with `Proof::to_var_bytes`")]
+    fn test_to_bytes_extra_chunks() {
+        let mut proof = Proof::dummy(&mut OsRng);
+        proof.extra_chunks = 1;
+
+        let _ = proof.to_bytes();
+    }
 }
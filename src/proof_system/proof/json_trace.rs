@@ -0,0 +1,252 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! JSON trace of the verification of a [`Proof`].
+//!
+//! Scalars and points are encoded as the hex string of their
+//! [`Serializable`] representation, and transcript messages as the hex
+//! string of their bytes, so two traces can be diffed line by line.
+
+use std::fmt::Write;
+
+use dusk_bls12_381::{BlsScalar, G1Affine};
+use dusk_bytes::Serializable;
+use merlin::Transcript;
+
+use super::Proof;
+use crate::compiler::Verifier;
+use crate::error::Error;
+use crate::proof_system::{
+    Challenges, VerificationFailure, VerificationReport,
+};
+use crate::transcript::TranscriptBackend;
+
+/// Operation performed against the transcript, with the appended or derived
+/// value, if any
+struct TranscriptEntry {
+    op: &'static str,
+    label: Vec<u8>,
+    value: Option<Vec<u8>>,
+}
+
+/// Merlin transcript recording every operation performed against it
+struct TracingTranscript {
+    transcript: Transcript,
+    entries: Vec<TranscriptEntry>,
+}
+
+impl TracingTranscript {
+    fn record(&mut self, op: &'static str, label: &'static [u8], value: &[u8]) {
+        self.entries.push(TranscriptEntry {
+            op,
+            label: label.to_vec(),
+            value: Some(value.to_vec()),
+        });
+    }
+}
+
+impl TranscriptBackend for TracingTranscript {
+    fn new(label: &[u8]) -> Self {
+        let entry = TranscriptEntry {
+            op: "new",
+            label: label.to_vec(),
+            value: None,
+        };
+
+        Self {
+            transcript: TranscriptBackend::new(label),
+            entries: vec![entry],
+        }
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.record("message", label, message);
+        TranscriptBackend::append_message(&mut self.transcript, label, message);
+    }
+
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        self.record("point", label, &point.to_bytes());
+        self.transcript.append_point(label, point);
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        self.record("scalar", label, &s.to_bytes());
+        TranscriptBackend::append_scalar(&mut self.transcript, label, s);
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        let challenge = self.transcript.challenge_scalar(label);
+        self.record("challenge", label, &challenge.to_bytes());
+
+        challenge
+    }
+}
+
+impl Proof {
+    /// Verify the proof while recording a JSON trace of the verification,
+    /// to compare it against the one of another implementation.
+    ///
+    /// The trace lists every operation performed against the transcript
+    /// along with its label and value, the derived [`Challenges`], the
+    /// evaluation of the public inputs, the constant term `r_0` of the
+    /// linearization polynomial, the points `left` and `right` paired with
+    /// `[x]_2` and `[1]_2` respectively by the final check, and the outcome
+    /// of the verification.
+    ///
+    /// An invalid proof is traced as well. Only public inputs inconsistent
+    /// with the circuit of the `verifier` return an error, as the
+    /// verification can't start.
+    ///
+    /// It's only available with the `std` and `serde` features.
+    pub fn trace_to_json(
+        &self,
+        verifier: &Verifier,
+        public_inputs: &[BlsScalar],
+    ) -> Result<String, Error> {
+        let public_inputs = verifier.public_inputs(public_inputs)?;
+        let mut transcript =
+            verifier.transcript_with::<TracingTranscript>(&public_inputs);
+
+        let (report, points) = match verifier.pairing_points(
+            self,
+            &mut transcript,
+            &public_inputs,
+        ) {
+            Ok((mut report, left, right)) => {
+                let opening_key = verifier.opening_key();
+                let pairing = dusk_bls12_381::multi_miller_loop(&[
+                    (&left, &opening_key.prepared_x_h),
+                    (&right, &opening_key.prepared_h),
+                ])
+                .final_exponentiation();
+
+                if pairing != dusk_bls12_381::Gt::identity() {
+                    report.fail(VerificationFailure::Pairing);
+                }
+
+                (report, Some((left, right)))
+            }
+            Err(err) => (VerificationReport::setup_failure(err), None),
+        };
+
+        Ok(write_trace(&transcript.entries, &report, points))
+    }
+}
+
+fn write_trace(
+    entries: &[TranscriptEntry],
+    report: &VerificationReport,
+    points: Option<(G1Affine, G1Affine)>,
+) -> String {
+    let mut json = String::from("{\n  \"transcript\": [");
+
+    for (i, entry) in entries.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        let _ = write!(
+            json,
+            "{}\n    {{ \"op\": \"{}\", \"label\": {}",
+            separator,
+            entry.op,
+            string(&String::from_utf8_lossy(&entry.label)),
+        );
+        if let Some(value) = &entry.value {
+            let _ = write!(json, ", \"value\": {}", hex(value));
+        }
+        json.push_str(" }");
+    }
+    json.push_str("\n  ],\n");
+
+    json.push_str("  \"challenges\": ");
+    match report.challenges() {
+        Some(challenges) => write_challenges(&mut json, challenges),
+        None => json.push_str("null"),
+    }
+    json.push_str(",\n");
+
+    let scalar = |s: Option<&BlsScalar>| {
+        s.map(|s| hex(&s.to_bytes()))
+            .unwrap_or_else(|| String::from("null"))
+    };
+    let _ = writeln!(json, "  \"pi_eval\": {},", scalar(report.pi_eval()));
+    let _ = writeln!(json, "  \"r_0_eval\": {},", scalar(report.r_0_eval()));
+
+    json.push_str("  \"pairing\": ");
+    match points {
+        Some((left, right)) => {
+            let _ = write!(
+                json,
+                "{{ \"left\": {}, \"right\": {} }}",
+                hex(&left.to_bytes()),
+                hex(&right.to_bytes())
+            );
+        }
+        None => json.push_str("null"),
+    }
+    json.push_str(",\n");
+
+    let _ = writeln!(json, "  \"valid\": {},", report.is_valid());
+    let failure = report
+        .failure()
+        .map(|failure| string(&format!("{:?}", failure)))
+        .unwrap_or_else(|| String::from("null"));
+    let _ = writeln!(json, "  \"failure\": {}", failure);
+    json.push('}');
+
+    json
+}
+
+fn write_challenges(json: &mut String, challenges: &Challenges) {
+    let challenges = [
+        ("beta", challenges.beta),
+        ("gamma", challenges.gamma),
+        ("alpha", challenges.alpha),
+        ("range_sep", challenges.range_sep),
+        ("logic_sep", challenges.logic_sep),
+        ("fixed_base_sep", challenges.fixed_base_sep),
+        ("var_base_sep", challenges.var_base_sep),
+        ("custom_sep", challenges.custom_sep),
+        ("z", challenges.z),
+        ("v", challenges.v),
+        ("v_w", challenges.v_w),
+        ("u", challenges.u),
+    ];
+
+    json.push('{');
+    for (i, (name, challenge)) in challenges.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        let _ = write!(
+            json,
+            "{}\n    \"{}\": {}",
+            separator,
+            name,
+            hex(&challenge.to_bytes())
+        );
+    }
+    json.push_str("\n  }");
+}
+
+/// Quoted hex string of `bytes`
+fn hex(bytes: &[u8]) -> String {
+    format!("\"{}\"", hex::encode(bytes))
+}
+
+/// Quoted JSON string of `s`, escaping quotes, backslashes and control
+/// characters
+fn string(s: &str) -> String {
+    let mut string = String::with_capacity(s.len() + 2);
+    string.push('"');
+    s.chars().for_each(|c| match c {
+        '"' => string.push_str("\\\""),
+        '\\' => string.push_str("\\\\"),
+        c if c.is_control() => {
+            let _ = write!(string, "\\u{:04x}", c as u32);
+        }
+        c => string.push(c),
+    });
+    string.push('"');
+
+    string
+}
@@ -0,0 +1,119 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Serde support for [`Proof`].
+//!
+//! The proof is encoded as by [`Proof::to_var_bytes`], as a hex string for
+//! human-readable formats and as raw bytes otherwise. Proofs with four
+//! quotient chunks are encoded with their [`Serializable`] representation.
+//!
+//! [`Serializable`]: dusk_bytes::Serializable
+
+use core::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+
+use super::Proof;
+use crate::error::Error;
+
+impl Serialize for Proof {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut buf = [0u8; Proof::MAX_VAR_SIZE];
+        let len = self.write_var_bytes(&mut buf);
+        let bytes = &buf[..len];
+
+        if serializer.is_human_readable() {
+            let mut hex = [0u8; 2 * Proof::MAX_VAR_SIZE];
+            let hex = &mut hex[..2 * len];
+            hex::encode_to_slice(bytes, hex).map_err(|_| {
+                ser::Error::custom("failed to hex encode proof")
+            })?;
+            let hex = core::str::from_utf8(hex).map_err(|_| {
+                ser::Error::custom("failed to hex encode proof")
+            })?;
+            serializer.serialize_str(hex)
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Proof {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(ProofVisitor)
+        } else {
+            deserializer.deserialize_bytes(ProofVisitor)
+        }
+    }
+}
+
+struct ProofVisitor;
+
+impl ProofVisitor {
+    fn from_bytes<E: de::Error>(bytes: &[u8]) -> Result<Proof, E> {
+        if bytes.len() > Proof::MAX_VAR_SIZE {
+            return Err(E::invalid_length(bytes.len(), &ProofVisitor));
+        }
+        Proof::from_var_bytes(bytes).map_err(|err| match err {
+            Error::InvalidProofLength { got, .. } => {
+                E::invalid_length(got, &ProofVisitor)
+            }
+            _ => E::custom("invalid proof bytes"),
+        })
+    }
+}
+
+impl<'de> Visitor<'de> for ProofVisitor {
+    type Value = Proof;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a proof of at most {} bytes",
+            Proof::MAX_VAR_SIZE
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Proof, E> {
+        if v.len() > 2 * Proof::MAX_VAR_SIZE || v.len() % 2 != 0 {
+            return Err(E::invalid_value(de::Unexpected::Str(v), &self));
+        }
+
+        let mut bytes = [0u8; Proof::MAX_VAR_SIZE];
+        let bytes = &mut bytes[..v.len() / 2];
+        hex::decode_to_slice(v, bytes)
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))?;
+        Self::from_bytes(bytes)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Proof, E> {
+        Self::from_bytes(v)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Proof, A::Error> {
+        let mut bytes = [0u8; Proof::MAX_VAR_SIZE];
+        let mut len = 0;
+        while let Some(byte) = seq.next_element()? {
+            if len == Proof::MAX_VAR_SIZE {
+                return Err(de::Error::invalid_length(len + 1, &self));
+            }
+            bytes[len] = byte;
+            len += 1;
+        }
+        Self::from_bytes(&bytes[..len])
+    }
+}
@@ -0,0 +1,167 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Public inputs of a circuit, placed at the positions of their gates.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::fft::EvaluationDomain;
+
+/// Public inputs of a proof, mapping the index of the gate that declares each
+/// public input to its value.
+///
+/// The public inputs are iterated in ascending order of their gate index,
+/// which is the order the prover returns them in and appends them to the
+/// transcript.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PublicInputs {
+    positions: BTreeMap<usize, BlsScalar>,
+}
+
+impl PublicInputs {
+    /// Create an empty set of public inputs
+    pub const fn new() -> Self {
+        Self {
+            positions: BTreeMap::new(),
+        }
+    }
+
+    /// Create the public inputs of contiguous gates, starting at the first
+    /// gate of the circuit
+    pub fn dense(values: &[BlsScalar]) -> Self {
+        values.iter().copied().enumerate().collect()
+    }
+
+    /// Set the public input of the gate at `index`, returning its previous
+    /// value, if any
+    pub fn insert(
+        &mut self,
+        index: usize,
+        value: BlsScalar,
+    ) -> Option<BlsScalar> {
+        self.positions.insert(index, value)
+    }
+
+    /// Public input of the gate at `index`
+    pub fn get(&self, index: usize) -> Option<&BlsScalar> {
+        self.positions.get(&index)
+    }
+
+    /// Number of public inputs
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Whether there are no public inputs
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Iterate the gate indexes and values of the public inputs
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &BlsScalar)> {
+        self.positions.iter().map(|(index, value)| (*index, value))
+    }
+
+    /// Iterate the gate indexes of the public inputs
+    pub fn indexes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.positions.keys().copied()
+    }
+
+    /// Iterate the values of the public inputs
+    pub fn values(&self) -> impl Iterator<Item = &BlsScalar> {
+        self.positions.values()
+    }
+
+    /// Iterate the non-zero public inputs along with the root `ω^-i` of
+    /// their position `i` in the `domain`
+    pub(crate) fn values_and_roots<'a>(
+        &'a self,
+        domain: &'a EvaluationDomain,
+    ) -> impl Iterator<Item = (BlsScalar, BlsScalar)> + 'a {
+        self.positions
+            .iter()
+            .filter(|(_, value)| **value != BlsScalar::zero())
+            .map(|(index, value)| {
+                let root = domain.group_gen_inv.pow(&[*index as u64, 0, 0, 0]);
+                (*value, root)
+            })
+    }
+}
+
+/// Public inputs whose values are fixed by the circuit definition, with the
+/// roots of unity of their positions precomputed for the evaluation domain of
+/// the circuit.
+///
+/// Built with [`Verifier::constant_public_inputs`], it lets
+/// [`Verifier::verify_with_constants`] evaluate the public inputs polynomial
+/// only computing the roots of the public inputs that vary across proofs. The
+/// positions of the constants are checked once when they're built, and bound
+/// to the hash of the circuit they were checked against.
+///
+/// [`Verifier::constant_public_inputs`]:
+/// crate::prelude::Verifier::constant_public_inputs
+/// [`Verifier::verify_with_constants`]:
+/// crate::prelude::Verifier::verify_with_constants
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstantPublicInputs {
+    inputs: PublicInputs,
+    roots: Vec<BlsScalar>,
+    circuit_hash: [u8; 32],
+}
+
+impl ConstantPublicInputs {
+    pub(crate) fn new(
+        inputs: PublicInputs,
+        domain: &EvaluationDomain,
+        circuit_hash: [u8; 32],
+    ) -> Self {
+        let roots = inputs
+            .indexes()
+            .map(|index| domain.group_gen_inv.pow(&[index as u64, 0, 0, 0]))
+            .collect();
+
+        Self {
+            inputs,
+            roots,
+            circuit_hash,
+        }
+    }
+
+    /// Gate indexes and values of the constant public inputs
+    pub const fn inputs(&self) -> &PublicInputs {
+        &self.inputs
+    }
+
+    /// Hash of the circuit the constants were checked against
+    pub(crate) const fn circuit_hash(&self) -> &[u8; 32] {
+        &self.circuit_hash
+    }
+
+    /// Iterate the constant public inputs along with the precomputed root
+    /// `ω^-i` of their position `i`
+    pub(crate) fn values_and_roots(
+        &self,
+    ) -> impl Iterator<Item = (BlsScalar, BlsScalar)> + '_ {
+        self.inputs
+            .values()
+            .copied()
+            .zip(self.roots.iter().copied())
+    }
+}
+
+impl FromIterator<(usize, BlsScalar)> for PublicInputs {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, BlsScalar)>,
+    {
+        Self {
+            positions: iter.into_iter().collect(),
+        }
+    }
+}
@@ -7,6 +7,8 @@
 use crate::{
     error::Error,
     fft::{EvaluationDomain, Polynomial},
+    proof_system::proof::{MAX_QUOTIENT_CHUNKS, QUOTIENT_CHUNKS},
+    proof_system::widget::custom::{self, CustomGate, CustomGateWires},
     proof_system::ProverKey,
 };
 use alloc::vec::Vec;
@@ -14,10 +16,98 @@ use dusk_bls12_381::BlsScalar;
 #[cfg(feature = "std")]
 use rayon::prelude::*;
 
+/// Layout of the quotient polynomial of a circuit, selected from the highest
+/// degree of its gate identities.
+///
+/// The numerator of the quotient has to be evaluated over a coset large
+/// enough to interpolate it, and the quotient is split into
+/// [`QUOTIENT_CHUNKS`] chunks by default, the last one holding the remaining
+/// higher coefficients. Circuits using only gates of
+/// [`CustomGate::DEFAULT_DEGREE`] are evaluated over a coset of size `8n` and
+/// split into chunks of degree `n`.
+///
+/// Circuits with higher degree gates can split their quotient into more
+/// chunks, up to one per multiple of `n`, to lower the degree of the
+/// committed polynomials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QuotientLayout {
+    degree: usize,
+    chunks: usize,
+}
+
+impl QuotientLayout {
+    /// Layout of the quotient of a circuit with the given custom gates
+    pub(crate) fn new<'a, I>(custom_gates: I) -> Self
+    where
+        I: IntoIterator<Item = &'a CustomGate>,
+    {
+        let degree = custom_gates
+            .into_iter()
+            .map(CustomGate::degree)
+            .fold(CustomGate::DEFAULT_DEGREE, usize::max);
+
+        Self {
+            degree,
+            chunks: QUOTIENT_CHUNKS,
+        }
+    }
+
+    /// Split the quotient into the given number of chunks.
+    ///
+    /// Returns [`Error::UnsupportedQuotientChunks`] if there are fewer than
+    /// [`QUOTIENT_CHUNKS`] chunks, or more than either the degree of the
+    /// quotient in multiples of `n` or [`MAX_QUOTIENT_CHUNKS`].
+    pub(crate) fn with_chunks(self, chunks: usize) -> Result<Self, Error> {
+        let max = self.degree.min(MAX_QUOTIENT_CHUNKS);
+        if !(QUOTIENT_CHUNKS..=max).contains(&chunks) {
+            return Err(Error::UnsupportedQuotientChunks { chunks, max });
+        }
+
+        Ok(Self { chunks, ..self })
+    }
+
+    /// Highest degree of the gate identities in the wires
+    pub(crate) const fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Number of chunks the quotient is split into
+    pub(crate) const fn chunks(&self) -> usize {
+        self.chunks
+    }
+
+    /// Size of the coset the quotient is evaluated over, as a multiple of
+    /// the size of the circuit domain.
+    ///
+    /// The numerator has degree `(degree + 1)·n` plus the blinding of the
+    /// wires, so the coset leaves room for two extra multiples of `n`.
+    pub(crate) const fn coset_factor(&self) -> usize {
+        (self.degree + 3).next_power_of_two()
+    }
+
+    /// Degree bound of the quotient chunks but the last, as a multiple of the
+    /// size of the circuit domain
+    pub(crate) const fn chunk_factor(&self) -> usize {
+        self.degree / self.chunks
+    }
+
+    /// Degree bound of the last quotient chunk, without the blinding of the
+    /// wires, as a multiple of the size of the circuit domain.
+    ///
+    /// The quotient has degree `degree·n`, so the last chunk is never empty.
+    pub(crate) const fn last_chunk_factor(&self) -> usize {
+        self.degree - (self.chunks - 1) * self.chunk_factor()
+    }
+}
+
 /// Computes the Quotient [`Polynomial`] given the [`EvaluationDomain`], a
 /// [`ProverKey`] and some other info.
+///
+/// The evaluations of the prover key are expected to be over the coset of
+/// the given layout.
 pub(crate) fn compute(
     domain: &EvaluationDomain,
+    layout: &QuotientLayout,
     prover_key: &ProverKey,
     z_poly: &Polynomial,
     (a_poly, b_poly, c_poly, d_poly): (
@@ -27,6 +117,7 @@ pub(crate) fn compute(
         &Polynomial,
     ),
     public_inputs_poly: &Polynomial,
+    (custom_gates, custom_challenge): (&[custom::ProverKey], &BlsScalar),
     (
         alpha,
         beta,
@@ -45,26 +136,29 @@ pub(crate) fn compute(
         BlsScalar,
     ),
 ) -> Result<Polynomial, Error> {
-    // Compute 8n evals
-    let domain_8n = EvaluationDomain::new(8 * domain.size())?;
-
-    let mut z_eval_8n = domain_8n.coset_fft(z_poly);
-
-    let mut a_eval_8n = domain_8n.coset_fft(a_poly);
-    let mut b_eval_8n = domain_8n.coset_fft(b_poly);
-    let c_eval_8n = domain_8n.coset_fft(c_poly);
-    let mut d_eval_8n = domain_8n.coset_fft(d_poly);
-
-    for i in 0..8 {
-        z_eval_8n.push(z_eval_8n[i]);
-        a_eval_8n.push(a_eval_8n[i]);
-        b_eval_8n.push(b_eval_8n[i]);
-        // c_eval_8n push not required
-        d_eval_8n.push(d_eval_8n[i]);
+    // Compute the evals over the coset of the layout
+    let factor = layout.coset_factor();
+    let coset_domain = EvaluationDomain::new(factor * domain.size())?;
+
+    let mut z_eval_coset = coset_domain.coset_fft(z_poly);
+
+    let mut a_eval_coset = coset_domain.coset_fft(a_poly);
+    let mut b_eval_coset = coset_domain.coset_fft(b_poly);
+    let c_eval_coset = coset_domain.coset_fft(c_poly);
+    let mut d_eval_coset = coset_domain.coset_fft(d_poly);
+
+    // the shift by the domain generator is a rotation by `factor` positions
+    for i in 0..factor {
+        z_eval_coset.push(z_eval_coset[i]);
+        a_eval_coset.push(a_eval_coset[i]);
+        b_eval_coset.push(b_eval_coset[i]);
+        // c_eval_coset push not required
+        d_eval_coset.push(d_eval_coset[i]);
     }
 
     let t_1 = compute_circuit_satisfiability_equation(
         domain,
+        &coset_domain,
         (
             range_challenge,
             logic_challenge,
@@ -72,33 +166,35 @@ pub(crate) fn compute(
             var_base_challenge,
         ),
         prover_key,
-        (&a_eval_8n, &b_eval_8n, &c_eval_8n, &d_eval_8n),
+        (custom_gates, custom_challenge),
+        (&a_eval_coset, &b_eval_coset, &c_eval_coset, &d_eval_coset),
         public_inputs_poly,
     );
 
     let t_2 = compute_permutation_checks(
         domain,
+        &coset_domain,
         prover_key,
-        (&a_eval_8n, &b_eval_8n, &c_eval_8n, &d_eval_8n),
-        &z_eval_8n,
+        (&a_eval_coset, &b_eval_coset, &c_eval_coset, &d_eval_coset),
+        &z_eval_coset,
         (alpha, beta, gamma),
     );
 
     #[cfg(not(feature = "std"))]
-    let range = (0..domain_8n.size()).into_iter();
+    let range = (0..coset_domain.size()).into_iter();
 
     #[cfg(feature = "std")]
-    let range = (0..domain_8n.size()).into_par_iter();
+    let range = (0..coset_domain.size()).into_par_iter();
 
     let quotient: Vec<_> = range
         .map(|i| {
             let numerator = t_1[i] + t_2[i];
-            let denominator = prover_key.v_h_coset_8n()[i];
+            let denominator = prover_key.v_h_coset()[i];
             numerator * denominator.invert().unwrap()
         })
         .collect();
 
-    let coset = domain_8n.coset_ifft(&quotient);
+    let coset = coset_domain.coset_ifft(&quotient);
 
     Ok(Polynomial::from_coefficients_vec(coset))
 }
@@ -106,6 +202,7 @@ pub(crate) fn compute(
 // Ensures that the circuit is satisfied
 fn compute_circuit_satisfiability_equation(
     domain: &EvaluationDomain,
+    coset_domain: &EvaluationDomain,
     (
         range_challenge,
         logic_challenge,
@@ -113,7 +210,8 @@ fn compute_circuit_satisfiability_equation(
         var_base_challenge,
     ): (&BlsScalar, &BlsScalar, &BlsScalar, &BlsScalar),
     prover_key: &ProverKey,
-    (a_eval_8n, b_eval_8n, c_eval_8n, d_eval_8n): (
+    (custom_gates, custom_challenge): (&[custom::ProverKey], &BlsScalar),
+    (a_eval_coset, b_eval_coset, c_eval_coset, d_eval_coset): (
         &[BlsScalar],
         &[BlsScalar],
         &[BlsScalar],
@@ -121,25 +219,25 @@ fn compute_circuit_satisfiability_equation(
     ),
     pi_poly: &Polynomial,
 ) -> Vec<BlsScalar> {
-    let domain_8n = EvaluationDomain::new(8 * domain.size()).unwrap();
-    let public_eval_8n = domain_8n.coset_fft(pi_poly);
+    let public_eval_coset = coset_domain.coset_fft(pi_poly);
+    let shift = coset_domain.size() / domain.size();
 
     #[cfg(not(feature = "std"))]
-    let range = (0..domain_8n.size()).into_iter();
+    let range = (0..coset_domain.size()).into_iter();
 
     #[cfg(feature = "std")]
-    let range = (0..domain_8n.size()).into_par_iter();
+    let range = (0..coset_domain.size()).into_par_iter();
 
     let t: Vec<_> = range
         .map(|i| {
-            let a = &a_eval_8n[i];
-            let b = &b_eval_8n[i];
-            let c = &c_eval_8n[i];
-            let d = &d_eval_8n[i];
-            let a_w = &a_eval_8n[i + 8];
-            let b_w = &b_eval_8n[i + 8];
-            let d_w = &d_eval_8n[i + 8];
-            let pi = &public_eval_8n[i];
+            let a = &a_eval_coset[i];
+            let b = &b_eval_coset[i];
+            let c = &c_eval_coset[i];
+            let d = &d_eval_coset[i];
+            let a_w = &a_eval_coset[i + shift];
+            let b_w = &b_eval_coset[i + shift];
+            let d_w = &d_eval_coset[i + shift];
+            let pi = &public_eval_coset[i];
 
             let t_arith =
                 prover_key.arithmetic.compute_quotient_i(i, a, b, c, d);
@@ -190,9 +288,24 @@ fn compute_circuit_satisfiability_equation(
                 d_w,
             );
 
+            let wires = CustomGateWires {
+                a: *a,
+                b: *b,
+                c: *c,
+                d: *d,
+                a_w: *a_w,
+                b_w: *b_w,
+                d_w: *d_w,
+            };
+            let t_custom: BlsScalar = custom_gates
+                .iter()
+                .zip(custom::separation_challenges(*custom_challenge))
+                .map(|(gate, sep)| gate.compute_quotient_i(i, &sep, &wires))
+                .sum();
+
             // Multiplication by selectors and challenges
             // has already been done
-            t_arith + t_range + t_logic + t_fixed + t_var + pi
+            t_arith + t_range + t_logic + t_fixed + t_var + t_custom + pi
         })
         .collect();
     t
@@ -200,37 +313,38 @@ fn compute_circuit_satisfiability_equation(
 
 fn compute_permutation_checks(
     domain: &EvaluationDomain,
+    coset_domain: &EvaluationDomain,
     prover_key: &ProverKey,
-    (a_eval_8n, b_eval_8n, c_eval_8n, d_eval_8n): (
+    (a_eval_coset, b_eval_coset, c_eval_coset, d_eval_coset): (
         &[BlsScalar],
         &[BlsScalar],
         &[BlsScalar],
         &[BlsScalar],
     ),
-    z_eval_8n: &[BlsScalar],
+    z_eval_coset: &[BlsScalar],
     (alpha, beta, gamma): (&BlsScalar, &BlsScalar, &BlsScalar),
 ) -> Vec<BlsScalar> {
-    let domain_8n = EvaluationDomain::new(8 * domain.size()).unwrap();
+    let shift = coset_domain.size() / domain.size();
     let l1_poly_alpha =
         compute_first_lagrange_poly_scaled(domain, alpha.square());
-    let l1_alpha_sq_evals = domain_8n.coset_fft(&l1_poly_alpha);
+    let l1_alpha_sq_evals = coset_domain.coset_fft(&l1_poly_alpha);
 
     #[cfg(not(feature = "std"))]
-    let range = (0..domain_8n.size()).into_iter();
+    let range = (0..coset_domain.size()).into_iter();
 
     #[cfg(feature = "std")]
-    let range = (0..domain_8n.size()).into_par_iter();
+    let range = (0..coset_domain.size()).into_par_iter();
 
     let t: Vec<_> = range
         .map(|i| {
             prover_key.permutation.compute_quotient_i(
                 i,
-                &a_eval_8n[i],
-                &b_eval_8n[i],
-                &c_eval_8n[i],
-                &d_eval_8n[i],
-                &z_eval_8n[i],
-                &z_eval_8n[i + 8],
+                &a_eval_coset[i],
+                &b_eval_coset[i],
+                &c_eval_coset[i],
+                &d_eval_coset[i],
+                &z_eval_coset[i],
+                &z_eval_coset[i + shift],
                 alpha,
                 &l1_alpha_sq_evals[i],
                 beta,
@@ -0,0 +1,358 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Slow reference verifier, used to test the linearized verifier.
+//!
+//! The proof doesn't carry the evaluations of the selectors nor of the
+//! quotient polynomial, so the polynomial identity of the circuit can only be
+//! checked through a commitment to it. Here it is written out in full,
+//! widget by widget, as the commitment to
+//!
+//! ```text
+//! P(X) = gates(X) + α·perm(X) + α²·(z(X) - 1)·L_1(z) - Z_H(z)·t(X)
+//! ```
+//!
+//! where every term that isn't committed to by the verifier key or the proof
+//! is evaluated at `z`. The identity holds iff `P(z) = 0`, which is checked
+//! with its own KZG opening along with the wires and permutation
+//! polynomials, and the shifted evaluations are checked with a second
+//! opening. Neither the shifted opening nor the two pairings are batched
+//! together, and nothing is shared with the linearized verifier besides the
+//! transcript replay.
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::{
+    pairing, BlsScalar, G1Affine, G1Projective, G2Affine, G2Projective,
+};
+use dusk_jubjub::EDWARDS_D;
+
+use crate::commitment_scheme::OpeningKey;
+use crate::composer::permutation::constants::{K1, K2, K3};
+use crate::error::Error;
+use crate::proof_system::widget::custom::CustomGateWires;
+use crate::proof_system::{
+    LinearizationContext, Proof, PublicInputs, VerifierKey,
+};
+use crate::transcript::TranscriptProtocol;
+
+impl Proof {
+    /// Verify the proof without the linearization of the verifier, checking
+    /// the polynomial identity of the circuit at the evaluation challenge and
+    /// each KZG opening with a pairing of its own.
+    pub(crate) fn verify_reference<T: TranscriptProtocol>(
+        &self,
+        verifier_key: &VerifierKey,
+        linearization: &LinearizationContext,
+        transcript: &mut T,
+        opening_key: &OpeningKey,
+        pub_inputs: &PublicInputs,
+    ) -> Result<(), Error> {
+        let domain = linearization.domain();
+        let custom_gates = linearization.custom_gates();
+
+        self.check_quotient_chunks(linearization)?;
+        let challenges =
+            self.derive_challenges(transcript, !custom_gates.is_empty());
+        let z = challenges.z;
+        let (alpha, beta, gamma) =
+            (challenges.alpha, challenges.beta, challenges.gamma);
+
+        let ev = &self.evaluations;
+        let n = domain.size() as u64;
+
+        // Z_H(z) = z^n - 1
+        let z_h = z.pow(&[n, 0, 0, 0]) - BlsScalar::one();
+        if z_h == BlsScalar::zero() {
+            return Err(Error::EvaluationChallengeInDomain);
+        }
+
+        // L_1(z) = (z^n - 1) / (n·(z - 1))
+        let l_1 = z_h
+            * (BlsScalar::from(n) * (z - BlsScalar::one()))
+                .invert()
+                .ok_or(Error::NonInvertibleDenominator)?;
+
+        // PI(z) = sum_i pi_i·L_i(z)
+        let lagrange = domain.evaluate_all_lagrange_coefficients(z);
+        let pi: BlsScalar = pub_inputs
+            .iter()
+            .map(|(index, value)| value * lagrange[index])
+            .sum();
+
+        let mut points: Vec<G1Affine> = Vec::new();
+        let mut scalars: Vec<BlsScalar> = Vec::new();
+        let mut term = |point: G1Affine, scalar: BlsScalar| {
+            points.push(point);
+            scalars.push(scalar);
+        };
+
+        // Arithmetic gate:
+        // q_arith·(q_m·a·b + q_l·a + q_r·b + q_o·c + q_f·d + q_c) + PI
+        let arith = &verifier_key.arithmetic;
+        term(arith.q_m.0, ev.q_arith_eval * ev.a_eval * ev.b_eval);
+        term(arith.q_l.0, ev.q_arith_eval * ev.a_eval);
+        term(arith.q_r.0, ev.q_arith_eval * ev.b_eval);
+        term(arith.q_o.0, ev.q_arith_eval * ev.c_eval);
+        term(arith.q_f.0, ev.q_arith_eval * ev.d_eval);
+        term(arith.q_c.0, ev.q_arith_eval);
+
+        // Range gate: each base-4 quad of the accumulator is in [0, 3]
+        let sep = challenges.range_sep;
+        let quads = [
+            ev.c_eval - BlsScalar::from(4) * ev.d_eval,
+            ev.b_eval - BlsScalar::from(4) * ev.c_eval,
+            ev.a_eval - BlsScalar::from(4) * ev.b_eval,
+            ev.d_w_eval - BlsScalar::from(4) * ev.a_eval,
+        ];
+        let range = combine(sep, quads.iter().map(|quad| quad_check(*quad)));
+        term(verifier_key.range.q_range.0, sep * range);
+
+        // Logic gate: the quads of both operands and of the result are in
+        // [0, 3], the product of the operands is in the output wire and the
+        // result is either their AND or XOR, as selected by q_c
+        let sep = challenges.logic_sep;
+        let a = ev.a_w_eval - BlsScalar::from(4) * ev.a_eval;
+        let b = ev.b_w_eval - BlsScalar::from(4) * ev.b_eval;
+        let d = ev.d_w_eval - BlsScalar::from(4) * ev.d_eval;
+        let w = ev.c_eval;
+        let logic = combine(
+            sep,
+            [
+                quad_check(a),
+                quad_check(b),
+                quad_check(d),
+                w - a * b,
+                xor_and_check(a, b, w, d, ev.q_c_eval),
+            ],
+        );
+        term(verifier_key.logic.q_logic.0, sep * logic);
+
+        // Fixed base scalar multiplication gate: the accumulated point is
+        // added the point selected by the signed bit of the scalar, with the
+        // coordinates of the point in q_l and q_r and its product in q_c
+        let sep = challenges.fixed_base_sep;
+        let bit = ev.d_w_eval - BlsScalar::from(2) * ev.d_eval;
+        let (acc_x, acc_y) = (ev.a_eval, ev.b_eval);
+        let (acc_x_w, acc_y_w) = (ev.a_w_eval, ev.b_w_eval);
+        let xy_alpha = ev.c_eval;
+        let x_alpha = bit * ev.q_l_eval;
+        let y_alpha =
+            bit * bit * (ev.q_r_eval - BlsScalar::one()) + BlsScalar::one();
+        let t = EDWARDS_D * xy_alpha * acc_x * acc_y;
+        let fixed_base = combine(
+            sep,
+            [
+                bit * (bit - BlsScalar::one()) * (bit + BlsScalar::one()),
+                bit * ev.q_c_eval - xy_alpha,
+                acc_x_w * (BlsScalar::one() + t)
+                    - (x_alpha * acc_y + y_alpha * acc_x),
+                acc_y_w * (BlsScalar::one() - t)
+                    - (x_alpha * acc_x + y_alpha * acc_y),
+            ],
+        );
+        term(
+            verifier_key.fixed_base.q_fixed_group_add.0,
+            sep * fixed_base,
+        );
+
+        // Variable base addition gate: (x_3, y_3) = (x_1, y_1) + (x_2, y_2),
+        // with x_1·y_2 in the fourth wire of the next gate
+        let sep = challenges.var_base_sep;
+        let (x_1, y_1, x_2, y_2) = (ev.a_eval, ev.b_eval, ev.c_eval, ev.d_eval);
+        let (x_3, y_3, x1_y2) = (ev.a_w_eval, ev.b_w_eval, ev.d_w_eval);
+        let t = EDWARDS_D * x1_y2 * y_1 * x_2;
+        let variable_base = combine(
+            sep,
+            [
+                x_1 * y_2 - x1_y2,
+                x1_y2 + y_1 * x_2 - x_3 * (BlsScalar::one() + t),
+                y_1 * y_2 + x_1 * x_2 - y_3 * (BlsScalar::one() - t),
+            ],
+        );
+        term(
+            verifier_key.variable_base.q_variable_group_add.0,
+            sep * variable_base,
+        );
+
+        // Custom gates, each with its own power of the separation challenge
+        let wires = CustomGateWires::from_evaluations(ev);
+        let mut sep = challenges.custom_sep;
+        for gate in custom_gates {
+            term(gate.q_custom.0, sep * gate.gate.evaluate(&wires));
+            sep *= challenges.custom_sep;
+        }
+
+        // Permutation:
+        // α·[(a + β·z + γ)(b + β·k1·z + γ)(c + β·k2·z + γ)(d + β·k3·z + γ)·z(X)
+        // - (a + β·σ1 + γ)(b + β·σ2 + γ)(c + β·σ3 + γ)(d + β·σ4(X) + γ)·z(zω)]
+        // + α²·(z(X) - 1)·L_1(z)
+        let identity = (ev.a_eval + beta * z + gamma)
+            * (ev.b_eval + beta * K1 * z + gamma)
+            * (ev.c_eval + beta * K2 * z + gamma)
+            * (ev.d_eval + beta * K3 * z + gamma);
+        let copy = (ev.a_eval + beta * ev.s_sigma_1_eval + gamma)
+            * (ev.b_eval + beta * ev.s_sigma_2_eval + gamma)
+            * (ev.c_eval + beta * ev.s_sigma_3_eval + gamma)
+            * ev.z_eval;
+        term(self.z_comm.0, alpha * identity + alpha.square() * l_1);
+        term(verifier_key.permutation.s_sigma_4.0, -alpha * copy * beta);
+        let constant =
+            pi - alpha * copy * (ev.d_eval + gamma) - alpha.square() * l_1;
+
+        // Quotient: -Z_H(z)·sum_i z^(i·m)·t_i(X), for the chunk size m
+        let m = (linearization.quotient_layout().chunk_factor() as u64) * n;
+        let z_m = z.pow(&[m, 0, 0, 0]);
+        let mut z_im = BlsScalar::one();
+        for t_comm in self.quotient_comms() {
+            term(t_comm.0, -z_h * z_im);
+            z_im *= z_m;
+        }
+
+        // Opening at z of P(X) - P(0)·1 along with the wires and the first
+        // three permutation polynomials, batched with powers of v
+        let v = challenges.v;
+        let mut v_i = v;
+        let mut eval_z = -constant;
+        for (point, eval) in [
+            (self.a_comm.0, ev.a_eval),
+            (self.b_comm.0, ev.b_eval),
+            (self.c_comm.0, ev.c_eval),
+            (self.d_comm.0, ev.d_eval),
+            (verifier_key.permutation.s_sigma_1.0, ev.s_sigma_1_eval),
+            (verifier_key.permutation.s_sigma_2.0, ev.s_sigma_2_eval),
+            (verifier_key.permutation.s_sigma_3.0, ev.s_sigma_3_eval),
+        ] {
+            term(point, v_i);
+            eval_z += v_i * eval;
+            v_i *= v;
+        }
+
+        let commitment_z = linear_combination(&points, &scalars);
+        check_opening(
+            opening_key,
+            commitment_z,
+            eval_z,
+            self.w_z_chall_comm.0,
+            z,
+        )?;
+
+        // Opening at z·ω of the permutation polynomial and the shifted wires,
+        // batched with powers of v_w
+        let v_w = challenges.v_w;
+        let shifted = [
+            (self.z_comm.0, ev.z_eval),
+            (self.a_comm.0, ev.a_w_eval),
+            (self.b_comm.0, ev.b_w_eval),
+            (self.d_comm.0, ev.d_w_eval),
+        ];
+        let mut v_w_i = BlsScalar::one();
+        let mut points_zw = Vec::with_capacity(shifted.len());
+        let mut scalars_zw = Vec::with_capacity(shifted.len());
+        let mut eval_zw = BlsScalar::zero();
+        for (point, eval) in shifted {
+            points_zw.push(point);
+            scalars_zw.push(v_w_i);
+            eval_zw += v_w_i * eval;
+            v_w_i *= v_w;
+        }
+
+        let commitment_zw = linear_combination(&points_zw, &scalars_zw);
+        check_opening(
+            opening_key,
+            commitment_zw,
+            eval_zw,
+            self.w_z_chall_w_comm.0,
+            z * domain.group_gen,
+        )
+    }
+}
+
+/// Combine the checks of a widget with the powers of the square of its
+/// separation challenge, starting at one
+fn combine(
+    separation_challenge: BlsScalar,
+    checks: impl IntoIterator<Item = BlsScalar>,
+) -> BlsScalar {
+    let kappa = separation_challenge.square();
+    let mut kappa_i = BlsScalar::one();
+    let mut sum = BlsScalar::zero();
+
+    for check in checks {
+        sum += kappa_i * check;
+        kappa_i *= kappa;
+    }
+
+    sum
+}
+
+/// Zero iff `quad` is in `[0, 3]`
+fn quad_check(quad: BlsScalar) -> BlsScalar {
+    (0..4).fold(BlsScalar::one(), |acc, i| acc * (quad - BlsScalar::from(i)))
+}
+
+/// Zero iff `d` is the AND, if `q_c = -1`, or the XOR, if `q_c = 1`, of the
+/// quads `a` and `b`, given their product `w`
+fn xor_and_check(
+    a: BlsScalar,
+    b: BlsScalar,
+    w: BlsScalar,
+    d: BlsScalar,
+    q_c: BlsScalar,
+) -> BlsScalar {
+    let a_plus_b = a + b;
+    let f = w
+        * (w * (BlsScalar::from(4) * w - BlsScalar::from(18) * a_plus_b
+            + BlsScalar::from(81))
+            + BlsScalar::from(18) * (a * a + b * b)
+            - BlsScalar::from(81) * a_plus_b
+            + BlsScalar::from(83));
+    let e = BlsScalar::from(3) * (a_plus_b + d) - BlsScalar::from(2) * f;
+
+    q_c * (BlsScalar::from(9) * d - BlsScalar::from(3) * a_plus_b) + e
+}
+
+/// Compute `sum_i scalar_i·point_i` one scalar multiplication at a time
+fn linear_combination(
+    points: &[G1Affine],
+    scalars: &[BlsScalar],
+) -> G1Projective {
+    points
+        .iter()
+        .zip(scalars.iter())
+        .map(|(point, scalar)| G1Projective::from(point) * scalar)
+        .sum()
+}
+
+/// Check that `witness` proves that the polynomial of `commitment` evaluates
+/// to `evaluation` at `point`:
+///
+/// ```text
+/// e([W], [x]_2 - point·[1]_2) = e([C] - evaluation·[1]_1, [1]_2)
+/// ```
+fn check_opening(
+    opening_key: &OpeningKey,
+    commitment: G1Projective,
+    evaluation: BlsScalar,
+    witness: G1Affine,
+    point: BlsScalar,
+) -> Result<(), Error> {
+    let lhs = pairing(
+        &witness,
+        &G2Affine::from(
+            G2Projective::from(opening_key.x_h) - opening_key.h * point,
+        ),
+    );
+    let rhs = pairing(
+        &G1Affine::from(commitment - opening_key.g * evaluation),
+        &opening_key.h,
+    );
+
+    match lhs == rhs {
+        true => Ok(()),
+        false => Err(Error::ProofVerificationError),
+    }
+}
@@ -0,0 +1,133 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Diagnostics collected while verifying a [`Proof`](super::Proof).
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::error::Error;
+
+/// Fiat-Shamir challenges derived by the verifier from the transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenges {
+    /// Permutation challenge `beta`
+    pub beta: BlsScalar,
+    /// Permutation challenge `gamma`
+    pub gamma: BlsScalar,
+    /// Quotient challenge `alpha`
+    pub alpha: BlsScalar,
+    /// Separation challenge of the range widget
+    pub range_sep: BlsScalar,
+    /// Separation challenge of the logic widget
+    pub logic_sep: BlsScalar,
+    /// Separation challenge of the fixed base curve addition widget
+    pub fixed_base_sep: BlsScalar,
+    /// Separation challenge of the variable base curve addition widget
+    pub var_base_sep: BlsScalar,
+    /// Separation challenge of the custom gates, zero if the circuit doesn't
+    /// register any
+    pub custom_sep: BlsScalar,
+    /// Evaluation challenge `z`
+    pub z: BlsScalar,
+    /// Aggregation challenge `v` for the openings at `z`
+    pub v: BlsScalar,
+    /// Aggregation challenge `v_w` for the openings at `z·ω`
+    pub v_w: BlsScalar,
+    /// Batching challenge `u` of the two opening proofs
+    pub u: BlsScalar,
+}
+
+/// Sub-check of the verification that didn't hold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VerificationFailure {
+    /// The verification couldn't be carried out because its inputs are
+    /// inconsistent, e.g. the public inputs don't match the verifier key or
+    /// the evaluation domain can't be constructed, or because a challenge
+    /// lands on a point where the verifier equations are undefined.
+    Setup(Error),
+    /// The final KZG pairing check failed.
+    ///
+    /// The quotient and linearization identities are folded into the
+    /// commitments checked by the pairing, so a wrong quotient evaluation or
+    /// a diverging transcript both surface here.
+    Pairing,
+}
+
+/// Outcome of a proof verification along with the intermediate values the
+/// verifier computed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerificationReport {
+    challenges: Option<Challenges>,
+    pi_eval: Option<BlsScalar>,
+    r_0_eval: Option<BlsScalar>,
+    failure: Option<VerificationFailure>,
+}
+
+impl VerificationReport {
+    pub(crate) const fn setup_failure(err: Error) -> Self {
+        Self {
+            challenges: None,
+            pi_eval: None,
+            r_0_eval: None,
+            failure: Some(VerificationFailure::Setup(err)),
+        }
+    }
+
+    pub(crate) const fn new(
+        challenges: Challenges,
+        pi_eval: BlsScalar,
+        r_0_eval: BlsScalar,
+    ) -> Self {
+        Self {
+            challenges: Some(challenges),
+            pi_eval: Some(pi_eval),
+            r_0_eval: Some(r_0_eval),
+            failure: None,
+        }
+    }
+
+    pub(crate) fn fail(&mut self, failure: VerificationFailure) {
+        self.failure.replace(failure);
+    }
+
+    /// Challenges derived from the transcript, if the verification got far
+    /// enough to derive them.
+    pub const fn challenges(&self) -> Option<&Challenges> {
+        self.challenges.as_ref()
+    }
+
+    /// Evaluation of the public inputs polynomial at the challenge `z`.
+    pub const fn pi_eval(&self) -> Option<&BlsScalar> {
+        self.pi_eval.as_ref()
+    }
+
+    /// Constant term `r_0` of the linearization polynomial recomputed by the
+    /// verifier from the proof evaluations.
+    pub const fn r_0_eval(&self) -> Option<&BlsScalar> {
+        self.r_0_eval.as_ref()
+    }
+
+    /// Sub-check that failed, if any.
+    pub const fn failure(&self) -> Option<&VerificationFailure> {
+        self.failure.as_ref()
+    }
+
+    /// Returns `true` if the proof was successfully verified.
+    pub const fn is_valid(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// Collapse the report into the result of the verification.
+    pub fn result(&self) -> Result<(), Error> {
+        match self.failure {
+            None => Ok(()),
+            Some(VerificationFailure::Setup(err)) => Err(err),
+            Some(VerificationFailure::Pairing) => {
+                Err(Error::ProofVerificationError)
+            }
+        }
+    }
+}
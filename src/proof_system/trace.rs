@@ -0,0 +1,89 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Logging of the stages of the verification of a [`Proof`](super::Proof).
+//!
+//! The stages are logged with the `log` crate only if the `logging` feature
+//! is enabled, and timed only if `std` is enabled as well. Without the
+//! `logging` feature the trace is a zero sized type and every call is a
+//! no-op.
+
+use core::fmt;
+
+#[cfg(all(feature = "logging", feature = "std"))]
+use std::time::Instant;
+
+/// Context threaded through the verification steps to log their outcome
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct VerifyTrace {
+    #[cfg(feature = "logging")]
+    target: &'static str,
+    #[cfg(all(feature = "logging", feature = "std"))]
+    start: Instant,
+    #[cfg(all(feature = "logging", feature = "std"))]
+    last: Instant,
+}
+
+impl VerifyTrace {
+    /// Start tracing the verification named `target`
+    #[allow(unused_variables)]
+    pub(crate) fn new(target: &'static str) -> Self {
+        #[cfg(all(feature = "logging", feature = "std"))]
+        let now = Instant::now();
+
+        Self {
+            #[cfg(feature = "logging")]
+            target,
+            #[cfg(all(feature = "logging", feature = "std"))]
+            start: now,
+            #[cfg(all(feature = "logging", feature = "std"))]
+            last: now,
+        }
+    }
+
+    /// Log at trace level the completion of a step, along with the time
+    /// elapsed since the previous one
+    #[allow(unused_variables)]
+    #[inline]
+    pub(crate) fn step(&mut self, step: &str, details: fmt::Arguments) {
+        #[cfg(all(feature = "logging", feature = "std"))]
+        {
+            let now = Instant::now();
+            log::trace!(
+                "{}: {} in {:?}, {}",
+                self.target,
+                step,
+                now - self.last,
+                details
+            );
+            self.last = now;
+        }
+
+        #[cfg(all(feature = "logging", not(feature = "std")))]
+        log::trace!("{}: {}, {}", self.target, step, details);
+    }
+
+    /// Log at debug level the outcome of the verification, along with its
+    /// total time
+    #[allow(unused_variables)]
+    #[inline]
+    pub(crate) fn finish(&self, valid: bool) {
+        #[cfg(all(feature = "logging", feature = "std"))]
+        log::debug!(
+            "{}: {} in {:?}",
+            self.target,
+            if valid { "valid" } else { "invalid" },
+            self.start.elapsed()
+        );
+
+        #[cfg(all(feature = "logging", not(feature = "std")))]
+        log::debug!(
+            "{}: {}",
+            self.target,
+            if valid { "valid" } else { "invalid" }
+        );
+    }
+}
@@ -8,6 +8,8 @@ use crate::commitment_scheme::Commitment;
 use dusk_bytes::{DeserializableSlice, Serializable};
 
 pub mod arithmetic;
+#[cfg(feature = "alloc")]
+pub mod custom;
 pub mod ecc;
 pub mod logic;
 pub mod permutation;
@@ -87,6 +89,36 @@ impl Serializable<{ 20 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
         let mut writer = &mut buff[..];
 
         writer.write(&(self.n as u64).to_bytes());
+        writer.write(&self.to_compact_bytes());
+
+        buff
+    }
+
+    fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<VerifierKey, Self::Error> {
+        let mut buffer = &buf[..];
+
+        let n = u64::from_reader(&mut buffer)? as usize;
+        let compact =
+            <[u8; Self::COMPACT_SIZE]>::try_from(&buffer[..Self::COMPACT_SIZE])
+                .expect("the buffer is larger than the compact size");
+
+        Self::from_compact_bytes(n, &compact)
+    }
+}
+
+impl VerifierKey {
+    /// Size of the compact serialization, holding only the distinct
+    /// commitments of the key.
+    pub(crate) const COMPACT_SIZE: usize = 15 * Commitment::SIZE;
+
+    /// Serialize the distinct commitments of the key, without the circuit
+    /// size and the padding of [`Serializable::to_bytes`].
+    #[allow(unused_must_use)]
+    pub(crate) fn to_compact_bytes(self) -> [u8; Self::COMPACT_SIZE] {
+        use dusk_bytes::Write;
+        let mut buff = [0u8; Self::COMPACT_SIZE];
+        let mut writer = &mut buff[..];
+
         writer.write(&self.arithmetic.q_m.to_bytes());
         writer.write(&self.arithmetic.q_l.to_bytes());
         writer.write(&self.arithmetic.q_r.to_bytes());
@@ -106,11 +138,16 @@ impl Serializable<{ 20 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
         buff
     }
 
-    fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<VerifierKey, Self::Error> {
+    /// Reconstruct the key of a circuit of size `n` from the commitments
+    /// serialized with [`Self::to_compact_bytes`].
+    pub(crate) fn from_compact_bytes(
+        n: usize,
+        buf: &[u8; Self::COMPACT_SIZE],
+    ) -> Result<Self, dusk_bytes::Error> {
         let mut buffer = &buf[..];
 
         Ok(Self::from_polynomial_commitments(
-            u64::from_reader(&mut buffer)? as usize,
+            n,
             Commitment::from_reader(&mut buffer)?,
             Commitment::from_reader(&mut buffer)?,
             Commitment::from_reader(&mut buffer)?,
@@ -128,9 +165,7 @@ impl Serializable<{ 20 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
             Commitment::from_reader(&mut buffer)?,
         ))
     }
-}
 
-impl VerifierKey {
     /// Constructs a [`VerifierKey`] from the widget VerifierKey's that are
     /// constructed based on the selector polynomial commitments and the
     /// sigma polynomial commitments.
@@ -198,16 +233,23 @@ pub(crate) mod alloc {
     use crate::{
         error::Error,
         fft::{EvaluationDomain, Evaluations, Polynomial},
+        proof_system::quotient_poly::QuotientLayout,
         transcript::TranscriptProtocol,
     };
     #[rustfmt::skip]
     use ::alloc::vec::Vec;
-    use dusk_bls12_381::BlsScalar;
-    use merlin::Transcript;
+    use core::num::NonZeroUsize;
+    use dusk_bls12_381::{
+        multiscalar_mul::msm_variable_base, BlsScalar, G1Affine, G1Projective,
+    };
+    use sha2::{Digest, Sha256};
 
     impl VerifierKey {
         /// Adds the circuit description to the transcript
-        pub(crate) fn seed_transcript(&self, transcript: &mut Transcript) {
+        pub(crate) fn seed_transcript<T: TranscriptProtocol>(
+            &self,
+            transcript: &mut T,
+        ) {
             transcript.append_commitment(b"q_m", &self.arithmetic.q_m);
             transcript.append_commitment(b"q_l", &self.arithmetic.q_l);
             transcript.append_commitment(b"q_r", &self.arithmetic.q_r);
@@ -238,6 +280,133 @@ pub(crate) mod alloc {
             // Append circuit size to transcript
             transcript.circuit_domain_sep(self.n as u64);
         }
+
+        /// Fingerprint of the circuit described by the key along with its
+        /// custom gates.
+        ///
+        /// Hashes the circuit size, every distinct commitment of the key and
+        /// the degree and selector commitment of the custom gates with a
+        /// domain separated SHA-256, so it only depends on the circuit and
+        /// not on the encoding the key was loaded from.
+        pub(crate) fn circuit_hash(
+            &self,
+            custom_gates: &[custom::VerifierKey],
+        ) -> [u8; 32] {
+            let mut hasher = Sha256::new();
+
+            hasher.update(CIRCUIT_HASH_DST);
+            hasher.update((self.n as u64).to_le_bytes());
+            hasher.update(self.to_compact_bytes());
+
+            for gate in custom_gates {
+                hasher.update((gate.gate.degree() as u64).to_le_bytes());
+                hasher.update(gate.q_custom.to_bytes());
+            }
+
+            hasher.finalize().into()
+        }
+    }
+
+    /// Domain separator of [`VerifierKey::circuit_hash`]
+    const CIRCUIT_HASH_DST: &[u8] = b"dusk-plonk-circuit-hash-v1";
+
+    /// Commitments of a [`VerifierKey`] laid out in the order the verifier
+    /// computes the scalars of the linearization commitment, along with the
+    /// evaluation domain of the circuit.
+    ///
+    /// Both only depend on the verifier key, so they're computed once and
+    /// shared by every proof verified against the key, each proof supplying
+    /// only its scalars.
+    ///
+    /// The custom gates of the circuit are appended after the fixed widgets.
+    #[derive(Debug, Clone)]
+    pub(crate) struct LinearizationContext {
+        points: Vec<G1Affine>,
+        custom_gates: Vec<custom::VerifierKey>,
+        domain: EvaluationDomain,
+        quotient_layout: QuotientLayout,
+        msm_batch: Option<NonZeroUsize>,
+    }
+
+    impl LinearizationContext {
+        pub(crate) fn new(
+            verifier_key: &VerifierKey,
+            custom_gates: Vec<custom::VerifierKey>,
+            quotient_chunks: usize,
+        ) -> Result<Self, Error> {
+            EvaluationDomain::check_circuit_size(verifier_key.n)?;
+
+            let domain = EvaluationDomain::new(verifier_key.n)?;
+            let mut points = Vec::with_capacity(11 + custom_gates.len());
+
+            verifier_key.arithmetic.linearization_points(&mut points);
+            verifier_key.range.linearization_points(&mut points);
+            verifier_key.logic.linearization_points(&mut points);
+            verifier_key.fixed_base.linearization_points(&mut points);
+            verifier_key.variable_base.linearization_points(&mut points);
+            verifier_key.permutation.linearization_points(&mut points);
+
+            custom_gates
+                .iter()
+                .for_each(|gate| gate.linearization_points(&mut points));
+
+            let quotient_layout =
+                QuotientLayout::new(custom_gates.iter().map(|key| &key.gate))
+                    .with_chunks(quotient_chunks)?;
+
+            Ok(Self {
+                points,
+                custom_gates,
+                domain,
+                quotient_layout,
+                msm_batch: None,
+            })
+        }
+
+        /// Evaluation domain of the circuit
+        pub(crate) const fn domain(&self) -> &EvaluationDomain {
+            &self.domain
+        }
+
+        /// Layout of the quotient polynomial of the circuit
+        pub(crate) const fn quotient_layout(&self) -> &QuotientLayout {
+            &self.quotient_layout
+        }
+
+        /// Custom gates registered by the circuit
+        pub(crate) fn custom_gates(&self) -> &[custom::VerifierKey] {
+            &self.custom_gates
+        }
+
+        /// Bound the number of points of each multi-scalar multiplication of
+        /// [`Self::commit`], or perform a single one with `None`.
+        pub(crate) fn set_msm_batch(&mut self, batch: Option<NonZeroUsize>) {
+            self.msm_batch = batch;
+        }
+
+        /// Commit to the verifier key part of the linearization polynomial,
+        /// given the scalars of a proof in the layout of the context.
+        ///
+        /// With a batch size set, the commitments are accumulated batch by
+        /// batch, so the buckets of the multi-scalar multiplication only hold
+        /// the points of a single batch at once. The points themselves are
+        /// part of the verifier key, and stay in the context either way.
+        pub(crate) fn commit(&self, scalars: &[BlsScalar]) -> G1Projective {
+            debug_assert_eq!(scalars.len(), self.points.len());
+
+            match self.msm_batch {
+                None => msm_variable_base(&self.points, scalars),
+                Some(batch) => {
+                    let batch = batch.get();
+                    self.points.chunks(batch).zip(scalars.chunks(batch)).fold(
+                        G1Projective::identity(),
+                        |acc, (points, scalars)| {
+                            acc + msm_variable_base(points, scalars)
+                        },
+                    )
+                }
+            }
+        }
     }
 
     /// PLONK circuit Proving Key.
@@ -273,13 +442,13 @@ pub(crate) mod alloc {
         /// ProverKey for permutation checks
         #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
         pub(crate) permutation: permutation::ProverKey,
-        // Pre-processes the 8n Evaluations for the vanishing polynomial, so
+        // Pre-processes the coset Evaluations for the vanishing polynomial, so
         // they do not need to be computed at the proving stage.
         // Note: With this, we can combine all parts of the quotient polynomial
         // in their evaluation phase and divide by the quotient
         // polynomial without having to perform IFFT
         #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
-        pub(crate) v_h_coset_8n: Evaluations,
+        pub(crate) v_h_coset: Evaluations,
     }
 
     impl ProverKey {
@@ -408,7 +577,7 @@ pub(crate) mod alloc {
 
             writer.write(&self.permutation.linear_evaluations.to_var_bytes());
 
-            writer.write(&self.v_h_coset_8n.to_var_bytes());
+            writer.write(&self.v_h_coset.to_var_bytes());
 
             bytes
         }
@@ -512,7 +681,7 @@ pub(crate) mod alloc {
 
             let perm_linear_evaluations = evals_from_reader(&mut buffer)?;
 
-            let v_h_coset_8n = evals_from_reader(&mut buffer)?;
+            let v_h_coset = evals_from_reader(&mut buffer)?;
 
             let arithmetic = arithmetic::ProverKey {
                 q_m,
@@ -558,14 +727,14 @@ pub(crate) mod alloc {
                 fixed_base,
                 variable_base,
                 permutation,
-                v_h_coset_8n,
+                v_h_coset,
             };
 
             Ok(prover_key)
         }
 
-        pub(crate) fn v_h_coset_8n(&self) -> &Evaluations {
-            &self.v_h_coset_8n
+        pub(crate) fn v_h_coset(&self) -> &Evaluations {
+            &self.v_h_coset
         }
     }
 }
@@ -621,7 +790,7 @@ mod test {
         let s_sigma_4 = rand_poly_eval(n);
         let linear_evaluations = rand_evaluations(n);
 
-        let v_h_coset_8n = rand_evaluations(n);
+        let v_h_coset = rand_evaluations(n);
 
         let arithmetic = arithmetic::ProverKey {
             q_m,
@@ -667,7 +836,7 @@ mod test {
             range,
             variable_base,
             permutation,
-            v_h_coset_8n,
+            v_h_coset,
         };
 
         let prover_key_bytes = prover_key.to_var_bytes();
@@ -89,31 +89,30 @@ mod alloc {
     use dusk_bls12_381::{BlsScalar, G1Affine};
 
     impl VerifierKey {
-        pub(crate) fn compute_linearization_commitment(
+        pub(crate) fn compute_linearization_scalars(
             &self,
             scalars: &mut Vec<BlsScalar>,
-            points: &mut Vec<G1Affine>,
             evaluations: &ProofEvaluations,
         ) {
             let q_arith_eval = evaluations.q_arith_eval;
 
             scalars
                 .push(evaluations.a_eval * evaluations.b_eval * q_arith_eval);
-            points.push(self.q_m.0);
-
             scalars.push(evaluations.a_eval * q_arith_eval);
-            points.push(self.q_l.0);
-
             scalars.push(evaluations.b_eval * q_arith_eval);
-            points.push(self.q_r.0);
-
             scalars.push(evaluations.c_eval * q_arith_eval);
-            points.push(self.q_o.0);
-
             scalars.push(evaluations.d_eval * q_arith_eval);
-            points.push(self.q_f.0);
-
             scalars.push(q_arith_eval);
+        }
+
+        /// Commitments matching the scalars computed by
+        /// [`Self::compute_linearization_scalars`]
+        pub(crate) fn linearization_points(&self, points: &mut Vec<G1Affine>) {
+            points.push(self.q_m.0);
+            points.push(self.q_l.0);
+            points.push(self.q_r.0);
+            points.push(self.q_o.0);
+            points.push(self.q_f.0);
             points.push(self.q_c.0);
         }
     }
@@ -0,0 +1,171 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Custom gates registered by a circuit on top of the fixed selector set.
+
+mod proverkey;
+mod verifierkey;
+
+pub(crate) use proverkey::ProverKey;
+pub(crate) use verifierkey::VerifierKey;
+
+use alloc::vec::Vec;
+use core::{fmt, iter};
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::error::Error;
+use crate::proof_system::linearization_poly::ProofEvaluations;
+
+/// Values of the wires the identity of a [`CustomGate`] is evaluated on.
+///
+/// The shifted wires `a_w`, `b_w` and `d_w` hold the values of the wires of
+/// the next gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomGateWires {
+    /// Left wire
+    pub a: BlsScalar,
+    /// Right wire
+    pub b: BlsScalar,
+    /// Output wire
+    pub c: BlsScalar,
+    /// Fourth wire
+    pub d: BlsScalar,
+    /// Left wire of the next gate
+    pub a_w: BlsScalar,
+    /// Right wire of the next gate
+    pub b_w: BlsScalar,
+    /// Fourth wire of the next gate
+    pub d_w: BlsScalar,
+}
+
+impl CustomGateWires {
+    pub(crate) const fn from_evaluations(
+        evaluations: &ProofEvaluations,
+    ) -> Self {
+        Self {
+            a: evaluations.a_eval,
+            b: evaluations.b_eval,
+            c: evaluations.c_eval,
+            d: evaluations.d_eval,
+            a_w: evaluations.a_w_eval,
+            b_w: evaluations.b_w_eval,
+            d_w: evaluations.d_w_eval,
+        }
+    }
+}
+
+/// Gate identity enabled by a selector column of its own.
+///
+/// Custom gates are registered with
+/// [`Circuit::CUSTOM_GATES`](crate::prelude::Circuit::CUSTOM_GATES) and
+/// enabled on a gate with
+/// [`Composer::append_registered_gate`](crate::prelude::Composer::append_registered_gate).
+/// The prover shows that `q_k(X) · identity(X)` vanishes over the domain,
+/// separated from the other widgets by a power of a dedicated challenge.
+///
+/// The identity of a gate created with [`Self::new`] must be a polynomial of
+/// degree at most 4 in the wires, as for the range and logic widgets. Gates
+/// of a higher degree are created with [`Self::with_degree`], and make the
+/// quotient polynomial of the circuit be evaluated over a larger coset and
+/// split into larger chunks.
+#[derive(Clone, Copy)]
+pub struct CustomGate {
+    identity: fn(&CustomGateWires) -> BlsScalar,
+    degree: usize,
+}
+
+impl CustomGate {
+    /// Degree of the identity of the gates created with [`Self::new`], the
+    /// degree of the identities of the fixed widgets
+    pub const DEFAULT_DEGREE: usize = 4;
+
+    /// Create a custom gate from its identity, of degree at most
+    /// [`Self::DEFAULT_DEGREE`] in the wires
+    pub const fn new(identity: fn(&CustomGateWires) -> BlsScalar) -> Self {
+        Self::with_degree(identity, Self::DEFAULT_DEGREE)
+    }
+
+    /// Create a custom gate from its identity, of degree at most `degree` in
+    /// the wires.
+    ///
+    /// A degree lower than [`Self::DEFAULT_DEGREE`] is raised to it, as it
+    /// doesn't change the quotient polynomial. Higher degrees require public
+    /// parameters supporting larger quotient chunks, see
+    /// [`Circuit::CUSTOM_GATES`](crate::prelude::Circuit::CUSTOM_GATES).
+    pub const fn with_degree(
+        identity: fn(&CustomGateWires) -> BlsScalar,
+        degree: usize,
+    ) -> Self {
+        let degree = match degree < Self::DEFAULT_DEGREE {
+            true => Self::DEFAULT_DEGREE,
+            false => degree,
+        };
+
+        Self { identity, degree }
+    }
+
+    /// Degree of the identity of the gate in the wires
+    pub const fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Evaluate the identity of the gate, zero if the wires satisfy it
+    pub fn evaluate(&self, wires: &CustomGateWires) -> BlsScalar {
+        (self.identity)(wires)
+    }
+}
+
+impl fmt::Debug for CustomGate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomGate")
+            .field("degree", &self.degree)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Separation challenges of the registered custom gates, the powers of the
+/// custom separation challenge starting at the first one.
+pub(crate) fn separation_challenges(
+    challenge: BlsScalar,
+) -> impl Iterator<Item = BlsScalar> {
+    iter::successors(Some(challenge), move |sep| Some(sep * challenge))
+}
+
+/// Restore the verifier keys of the custom gates serialized in `bytes`, one
+/// [`VerifierKey::SIZE`] chunk per gate, with the identities of the
+/// `custom_gates` registered by the circuit.
+///
+/// The registered gates have to match the serialized ones, in number and
+/// degree.
+pub(crate) fn verifier_keys_from_bytes(
+    bytes: &[u8],
+    custom_gates: &[CustomGate],
+) -> Result<Vec<VerifierKey>, Error> {
+    let chunks = bytes.chunks_exact(VerifierKey::SIZE);
+    if !chunks.remainder().is_empty() {
+        return Err(Error::NotEnoughBytes);
+    }
+
+    let serialized = chunks.len();
+    if custom_gates.len() < serialized {
+        return Err(Error::UnregisteredCustomGate {
+            id: custom_gates.len(),
+        });
+    }
+    if custom_gates.len() > serialized {
+        return Err(Error::InconsistentCustomGate { id: serialized });
+    }
+
+    chunks
+        .zip(custom_gates)
+        .enumerate()
+        .map(|(id, (bytes, gate))| {
+            let bytes = bytes.try_into().expect("checked len");
+            VerifierKey::from_bytes(id, *gate, bytes)
+        })
+        .collect()
+}
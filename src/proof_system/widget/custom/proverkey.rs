@@ -0,0 +1,43 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::fft::{Evaluations, Polynomial};
+use crate::proof_system::linearization_poly::ProofEvaluations;
+use dusk_bls12_381::BlsScalar;
+
+use super::{CustomGate, CustomGateWires};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ProverKey {
+    pub(crate) gate: CustomGate,
+    pub(crate) q_custom: (Polynomial, Evaluations),
+}
+
+impl ProverKey {
+    pub(crate) fn compute_quotient_i(
+        &self,
+        index: usize,
+        custom_separation_challenge: &BlsScalar,
+        wires: &CustomGateWires,
+    ) -> BlsScalar {
+        let q_custom_i = &self.q_custom.1[index];
+
+        self.gate.evaluate(wires) * q_custom_i * custom_separation_challenge
+    }
+
+    pub(crate) fn compute_linearization(
+        &self,
+        custom_separation_challenge: &BlsScalar,
+        evaluations: &ProofEvaluations,
+    ) -> Polynomial {
+        let q_custom_poly = &self.q_custom.0;
+
+        let wires = CustomGateWires::from_evaluations(evaluations);
+        let t = self.gate.evaluate(&wires) * custom_separation_challenge;
+
+        q_custom_poly * &t
+    }
+}
@@ -0,0 +1,84 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::commitment_scheme::Commitment;
+use crate::error::Error;
+use crate::proof_system::linearization_poly::ProofEvaluations;
+use crate::transcript::TranscriptProtocol;
+use alloc::vec::Vec;
+use dusk_bls12_381::{BlsScalar, G1Affine};
+use dusk_bytes::{DeserializableSlice, Serializable};
+
+use super::{CustomGate, CustomGateWires};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct VerifierKey {
+    pub(crate) gate: CustomGate,
+    pub(crate) q_custom: Commitment,
+}
+
+impl VerifierKey {
+    /// Size in bytes of the serialized key, the degree of the gate followed by
+    /// the commitment to its selector
+    pub(crate) const SIZE: usize = u64::SIZE + Commitment::SIZE;
+
+    /// Serialize the degree of the gate and the commitment to its selector.
+    ///
+    /// The identity of the gate is a function of the circuit, so it isn't
+    /// serialized.
+    pub(crate) fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+
+        let degree = self.gate.degree() as u64;
+        bytes[..u64::SIZE].copy_from_slice(&degree.to_be_bytes());
+        bytes[u64::SIZE..].copy_from_slice(&self.q_custom.to_bytes());
+
+        bytes
+    }
+
+    /// Restore the key of the custom gate `id` from bytes generated via
+    /// [`Self::to_bytes`], along with the `gate` registered by the circuit
+    pub(crate) fn from_bytes(
+        id: usize,
+        gate: CustomGate,
+        bytes: &[u8; Self::SIZE],
+    ) -> Result<Self, Error> {
+        let degree =
+            <[u8; 8]>::try_from(&bytes[..u64::SIZE]).expect("checked len");
+        if u64::from_be_bytes(degree) != gate.degree() as u64 {
+            return Err(Error::InconsistentCustomGate { id });
+        }
+
+        let q_custom = Commitment::from_slice(&bytes[u64::SIZE..])?;
+
+        Ok(Self { gate, q_custom })
+    }
+
+    /// Adds the selector commitment of the gate to the transcript
+    pub(crate) fn seed_transcript<T: TranscriptProtocol>(
+        &self,
+        transcript: &mut T,
+    ) {
+        transcript.append_commitment(b"q_custom", &self.q_custom);
+    }
+
+    pub(crate) fn compute_linearization_scalars(
+        &self,
+        custom_separation_challenge: &BlsScalar,
+        scalars: &mut Vec<BlsScalar>,
+        evaluations: &ProofEvaluations,
+    ) {
+        let wires = CustomGateWires::from_evaluations(evaluations);
+
+        scalars.push(self.gate.evaluate(&wires) * custom_separation_challenge);
+    }
+
+    /// Commitments matching the scalars computed by
+    /// [`Self::compute_linearization_scalars`]
+    pub(crate) fn linearization_points(&self, points: &mut Vec<G1Affine>) {
+        points.push(self.q_custom.0);
+    }
+}
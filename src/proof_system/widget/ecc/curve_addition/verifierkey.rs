@@ -36,11 +36,10 @@ mod alloc {
     use dusk_jubjub::EDWARDS_D;
 
     impl VerifierKey {
-        pub(crate) fn compute_linearization_commitment(
+        pub(crate) fn compute_linearization_scalars(
             &self,
             curve_add_separation_challenge: &BlsScalar,
             scalars: &mut Vec<BlsScalar>,
-            points: &mut Vec<G1Affine>,
             evaluations: &ProofEvaluations,
         ) {
             let kappa = curve_add_separation_challenge.square();
@@ -75,6 +74,11 @@ mod alloc {
             let identity = xy_consistency + x3_consistency + y3_consistency;
 
             scalars.push(identity * curve_add_separation_challenge);
+        }
+
+        /// Commitments matching the scalars computed by
+        /// [`Self::compute_linearization_scalars`]
+        pub(crate) fn linearization_points(&self, points: &mut Vec<G1Affine>) {
             points.push(self.q_variable_group_add.0);
         }
     }
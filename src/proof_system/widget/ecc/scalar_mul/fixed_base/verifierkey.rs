@@ -43,11 +43,10 @@ mod alloc {
     use dusk_jubjub::EDWARDS_D;
 
     impl VerifierKey {
-        pub(crate) fn compute_linearization_commitment(
+        pub(crate) fn compute_linearization_scalars(
             &self,
             ecc_separation_challenge: &BlsScalar,
             scalars: &mut Vec<BlsScalar>,
-            points: &mut Vec<G1Affine>,
             evaluations: &ProofEvaluations,
         ) {
             let kappa = ecc_separation_challenge.square();
@@ -98,6 +97,11 @@ mod alloc {
                 + xy_consistency;
 
             scalars.push(a * ecc_separation_challenge);
+        }
+
+        /// Commitments matching the scalars computed by
+        /// [`Self::compute_linearization_scalars`]
+        pub(crate) fn linearization_points(&self, points: &mut Vec<G1Affine>) {
             points.push(self.q_fixed_group_add.0);
         }
     }
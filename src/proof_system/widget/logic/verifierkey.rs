@@ -38,11 +38,10 @@ mod alloc {
     use dusk_bls12_381::{BlsScalar, G1Affine};
 
     impl VerifierKey {
-        pub(crate) fn compute_linearization_commitment(
+        pub(crate) fn compute_linearization_scalars(
             &self,
             logic_separation_challenge: &BlsScalar,
             scalars: &mut Vec<BlsScalar>,
-            points: &mut Vec<G1Affine>,
             evaluations: &ProofEvaluations,
         ) {
             let four = BlsScalar::from(4);
@@ -69,6 +68,11 @@ mod alloc {
             scalars.push(
                 (c_0 + c_1 + c_2 + c_3 + c_4) * logic_separation_challenge,
             );
+        }
+
+        /// Commitments matching the scalars computed by
+        /// [`Self::compute_linearization_scalars`]
+        pub(crate) fn linearization_points(&self, points: &mut Vec<G1Affine>) {
             points.push(self.q_logic.0);
         }
     }
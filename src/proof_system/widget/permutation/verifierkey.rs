@@ -42,17 +42,15 @@ mod alloc {
     use dusk_bls12_381::{BlsScalar, G1Affine};
 
     impl VerifierKey {
-        pub(crate) fn compute_linearization_commitment(
+        pub(crate) fn compute_linearization_scalars(
             &self,
             scalars: &mut Vec<BlsScalar>,
-            points: &mut Vec<G1Affine>,
             evaluations: &ProofEvaluations,
             z_challenge: &BlsScalar,
             u_challenge: &BlsScalar,
             (alpha, beta, gamma): (&BlsScalar, &BlsScalar, &BlsScalar),
             l1_eval: &BlsScalar,
-            z_comm: G1Affine,
-        ) {
+        ) -> BlsScalar {
             let alpha_sq = alpha.square();
 
             // (a_eval + beta * z + gamma)
@@ -78,9 +76,6 @@ mod alloc {
             // l1(z) * alpha^2
             let r = l1_eval * alpha_sq;
 
-            scalars.push(x + r + u_challenge);
-            points.push(z_comm);
-
             // -1 * (a_eval + beta * sigma_1_eval + gamma)
             // * (b_eval + beta * sigma_2_eval + gamma)
             // * (c_eval + beta * sigma_3_eval + gamma)
@@ -100,6 +95,14 @@ mod alloc {
                 -(q_0 * q_1 * q_2 * q_3)
             };
             scalars.push(y);
+
+            // the scalar of the proof commitment `z`
+            x + r + u_challenge
+        }
+
+        /// Commitments matching the scalars computed by
+        /// [`Self::compute_linearization_scalars`]
+        pub(crate) fn linearization_points(&self, points: &mut Vec<G1Affine>) {
             points.push(self.s_sigma_4.0);
         }
     }
@@ -36,11 +36,10 @@ mod alloc {
     use dusk_bls12_381::{BlsScalar, G1Affine};
 
     impl VerifierKey {
-        pub(crate) fn compute_linearization_commitment(
+        pub(crate) fn compute_linearization_scalars(
             &self,
             range_separation_challenge: &BlsScalar,
             scalars: &mut Vec<BlsScalar>,
-            points: &mut Vec<G1Affine>,
             evaluations: &ProofEvaluations,
         ) {
             let four = BlsScalar::from(4);
@@ -58,6 +57,11 @@ mod alloc {
                 * kappa_cu;
 
             scalars.push((b_1 + b_2 + b_3 + b_4) * range_separation_challenge);
+        }
+
+        /// Commitments matching the scalars computed by
+        /// [`Self::compute_linearization_scalars`]
+        pub(crate) fn linearization_points(&self, points: &mut Vec<G1Affine>) {
             points.push(self.q_range.0);
         }
     }
@@ -6,6 +6,10 @@
 
 //! PLONK runtime controller
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+
 use dusk_bls12_381::BlsScalar;
 
 use crate::prelude::{Constraint, Witness};
@@ -33,6 +37,59 @@ pub enum RuntimeEvent {
 
     /// The proof construction was finished
     ProofFinished,
+
+    /// A gate was evaluated by the satisfaction check of the composer
+    GateEvaluated {
+        /// Index of the gate in the circuit
+        index: usize,
+        /// Whether the witnesses satisfy the gate
+        satisfied: bool,
+    },
+}
+
+/// Listener of the [`RuntimeEvent`]s emitted by the composer, such as a
+/// profiler or a metrics sink.
+///
+/// Listeners are cloned along with the composer, and into the composer of
+/// every proof when registered to a prover, so a listener collecting state
+/// should share it with its clones, e.g. behind an `Arc`.
+pub trait RuntimeListener: ListenerClone + Send + Sync {
+    /// Handle an event emitted by the composer
+    fn on_event(&mut self, event: &RuntimeEvent);
+}
+
+/// Cloning of a boxed [`RuntimeListener`], implemented for every listener
+/// that is [`Clone`]
+pub trait ListenerClone {
+    /// Clone the listener into a new box
+    fn clone_box(&self) -> Box<dyn RuntimeListener>;
+}
+
+impl<L> ListenerClone for L
+where
+    L: 'static + RuntimeListener + Clone,
+{
+    fn clone_box(&self) -> Box<dyn RuntimeListener> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn RuntimeListener> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl fmt::Debug for dyn RuntimeListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RuntimeListener")
+    }
+}
+
+impl RuntimeListener for fn(&RuntimeEvent) {
+    fn on_event(&mut self, event: &RuntimeEvent) {
+        self(event)
+    }
 }
 
 /// Runtime structure with debugger
@@ -40,6 +97,7 @@ pub enum RuntimeEvent {
 pub struct Runtime {
     #[cfg(feature = "debug")]
     debugger: Debugger,
+    listeners: Vec<Box<dyn RuntimeListener>>,
 }
 
 impl Default for Runtime {
@@ -55,12 +113,42 @@ impl Runtime {
         Self {
             #[cfg(feature = "debug")]
             debugger: Debugger::new(),
+            listeners: Vec::new(),
         }
     }
 
+    /// Register a listener to be notified of every event, after the debugger
+    pub(crate) fn add_listener(&mut self, listener: Box<dyn RuntimeListener>) {
+        self.listeners.push(listener);
+    }
+
+    /// PLONK debugger
+    #[cfg(feature = "debug")]
+    pub(crate) const fn debugger(&self) -> &Debugger {
+        &self.debugger
+    }
+
+    /// Discard the witnesses and constraints recorded by the debugger after
+    /// the given number of each
+    #[cfg(feature = "debug")]
+    pub(crate) fn truncate_debugger(&mut self, len: (usize, usize)) {
+        self.debugger.truncate(len);
+    }
+
+    /// Discard the constraints recorded by the debugger for the gates that
+    /// aren't flagged in `gates`
+    #[cfg(feature = "debug")]
+    pub(crate) fn retain_debugger(&mut self, gates: &[bool]) {
+        self.debugger.retain_constraints(gates);
+    }
+
     #[allow(unused_variables)]
     pub(crate) fn event(&mut self, event: RuntimeEvent) {
         #[cfg(feature = "debug")]
-        self.debugger.event(event);
+        self.debugger.on_event(&event);
+
+        self.listeners
+            .iter_mut()
+            .for_each(|listener| listener.on_event(&event));
     }
 }
@@ -9,78 +9,209 @@
 
 use core::mem;
 
-use dusk_bls12_381::BlsScalar;
+use dusk_bls12_381::{BlsScalar, G1Affine};
 use dusk_bytes::Serializable;
 use merlin::Transcript;
 
 use crate::commitment_scheme::Commitment;
+use crate::proof_system::widget::custom;
 use crate::proof_system::VerifierKey;
 
-/// Transcript adds an abstraction over the Merlin transcript
-/// For convenience
-pub(crate) trait TranscriptProtocol {
-    /// Append a `commitment` with the given `label`.
-    fn append_commitment(&mut self, label: &'static [u8], comm: &Commitment);
+mod context;
+mod resumable;
+
+pub use context::ContextTranscript;
+pub use resumable::ResumableTranscript;
+
+/// Hash backing the Fiat-Shamir transcript of the protocol.
+///
+/// Proofs are generated and verified with the [Merlin Transcript](Transcript)
+/// by default. Another backend, such as one built over an algebraic hash that
+/// is cheap to recompute inside a circuit, can be used with
+/// [`Prover::prove_with_transcript`] and [`Verifier::verify_with_transcript`].
+/// A proof is only valid for the backend it was generated with.
+///
+/// [`Prover::prove_with_transcript`]:
+/// crate::prelude::Prover::prove_with_transcript
+/// [`Verifier::verify_with_transcript`]:
+/// crate::prelude::Verifier::verify_with_transcript
+pub trait TranscriptBackend {
+    /// Create a new transcript for the protocol `label`.
+    ///
+    /// The label isn't required to outlive the transcript, so a backend
+    /// retaining it has to copy it.
+    fn new(label: &[u8]) -> Self;
+
+    /// Append a `message` with the given `label`.
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+
+    /// Append a `point` with the given `label`.
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine);
 
     /// Append a `BlsScalar` with the given `label`.
     fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar);
 
     /// Compute a `label`ed challenge variable.
     fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar;
+}
+
+impl TranscriptBackend for Transcript {
+    fn new(label: &[u8]) -> Self {
+        // Safety: static lifetime is a pointless requirement from merlin that
+        // doesn't add any security but instead restricts a lot the
+        // serialization and deserialization of transcripts. The label is only
+        // absorbed as the domain separator of the transcript, and never
+        // retained by it.
+        let label = unsafe { mem::transmute::<&[u8], &'static [u8]>(label) };
+
+        Transcript::new(label)
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        Transcript::append_message(self, label, message);
+    }
+
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        Transcript::append_message(self, label, &point.to_bytes());
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        Transcript::append_message(self, label, &s.to_bytes())
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        let mut buf = [0u8; 64];
+        self.challenge_bytes(label, &mut buf);
+
+        BlsScalar::from_bytes_wide(&buf)
+    }
+}
+
+/// Transcript adds an abstraction over the transcript backend
+/// For convenience
+pub(crate) trait TranscriptProtocol: TranscriptBackend {
+    /// Append a `commitment` with the given `label`.
+    fn append_commitment(&mut self, label: &'static [u8], comm: &Commitment);
 
     /// Append domain separator for the circuit size.
     fn circuit_domain_sep(&mut self, n: u64);
 
-    /// Create a new instance of the base transcript of the protocol
+    /// Append a caller-supplied domain separator for the application.
+    fn application_domain_sep(&mut self, dst: &[u8]);
+
+    /// Append the circuit description and its custom gates, bound to the
+    /// circuit hash
+    fn append_circuit(
+        &mut self,
+        verifier_key: &VerifierKey,
+        custom_gates: &[custom::VerifierKey],
+        constraints: usize,
+    );
+
+    /// Append the circuit `label` and description to a transcript seeded by
+    /// the caller with the context of a larger protocol
+    fn append_circuit_in_context(
+        &mut self,
+        label: &[u8],
+        verifier_key: &VerifierKey,
+        custom_gates: &[custom::VerifierKey],
+        constraints: usize,
+    );
+
+    /// Append the opening commitments of a proof, the last round of the
+    /// protocol, and draw the challenge `u` batching its two openings in the
+    /// pairing check
+    fn opening_challenge(
+        &mut self,
+        w_z_chall_comm: &Commitment,
+        w_z_chall_w_comm: &Commitment,
+    ) -> BlsScalar;
+
+    /// Create a new instance of the base transcript of the protocol, seeded
+    /// with the circuit description and its custom gates, bound to the
+    /// circuit hash
     fn base(
         label: &[u8],
         verifier_key: &VerifierKey,
+        custom_gates: &[custom::VerifierKey],
         constraints: usize,
     ) -> Self;
 }
 
-impl TranscriptProtocol for Transcript {
+impl<T: TranscriptBackend> TranscriptProtocol for T {
     fn append_commitment(&mut self, label: &'static [u8], comm: &Commitment) {
-        self.append_message(label, &comm.0.to_bytes());
+        self.append_point(label, &comm.0);
     }
 
-    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
-        self.append_message(label, &s.to_bytes())
+    fn circuit_domain_sep(&mut self, n: u64) {
+        self.append_message(b"dom-sep", b"circuit_size");
+        self.append_message(b"n", &n.to_le_bytes());
     }
 
-    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
-        let mut buf = [0u8; 64];
-        self.challenge_bytes(label, &mut buf);
-
-        BlsScalar::from_bytes_wide(&buf)
+    fn application_domain_sep(&mut self, dst: &[u8]) {
+        self.append_message(b"dom-sep", b"application");
+        self.append_message(b"dst", dst);
     }
 
-    fn circuit_domain_sep(&mut self, n: u64) {
-        self.append_message(b"dom-sep", b"circuit_size");
-        self.append_u64(b"n", n);
+    fn opening_challenge(
+        &mut self,
+        w_z_chall_comm: &Commitment,
+        w_z_chall_w_comm: &Commitment,
+    ) -> BlsScalar {
+        self.append_commitment(b"w_z_chall_comm", w_z_chall_comm);
+        self.append_commitment(b"w_z_chall_w_comm", w_z_chall_w_comm);
+
+        self.challenge_scalar(b"u_challenge")
     }
 
     fn base(
         label: &[u8],
         verifier_key: &VerifierKey,
+        custom_gates: &[custom::VerifierKey],
         constraints: usize,
     ) -> Self {
         // Transcript can't be serialized/deserialized. One alternative is to
         // fork merlin and implement these functionalities, so we can use custom
         // transcripts for provers and verifiers. However, we don't have a use
         // case for this feature in Dusk.
+        let mut transcript = T::new(label);
+        transcript.append_circuit(verifier_key, custom_gates, constraints);
 
-        // Safety: static lifetime is a pointless requirement from merlin that
-        // doesn't add any security but instead restricts a lot the
-        // serialization and deserialization of transcripts
-        let label = unsafe { mem::transmute(label) };
+        transcript
+    }
+
+    fn append_circuit(
+        &mut self,
+        verifier_key: &VerifierKey,
+        custom_gates: &[custom::VerifierKey],
+        constraints: usize,
+    ) {
+        self.circuit_domain_sep(constraints as u64);
 
-        let mut transcript = Transcript::new(label);
+        // Bind the proof to the exact circuit before any commitment, so a
+        // proof can't be verified against the key of another circuit
+        let circuit_hash = verifier_key.circuit_hash(custom_gates);
+        self.append_message(b"circuit_hash", &circuit_hash);
 
-        transcript.circuit_domain_sep(constraints as u64);
+        verifier_key.seed_transcript(self);
 
-        verifier_key.seed_transcript(&mut transcript);
+        custom_gates
+            .iter()
+            .for_each(|gate| gate.seed_transcript(self));
+    }
 
-        transcript
+    fn append_circuit_in_context(
+        &mut self,
+        label: &[u8],
+        verifier_key: &VerifierKey,
+        custom_gates: &[custom::VerifierKey],
+        constraints: usize,
+    ) {
+        // the label of the circuit takes the place of the protocol label of
+        // the base transcript, which is the one of the caller
+        self.append_message(b"dom-sep", b"plonk");
+        self.append_message(b"label", label);
+
+        self.append_circuit(verifier_key, custom_gates, constraints);
     }
 }
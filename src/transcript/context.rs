@@ -0,0 +1,78 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Transcript of a larger protocol embedding PLONK proofs.
+
+use dusk_bls12_381::{BlsScalar, G1Affine};
+use merlin::Transcript;
+
+use super::TranscriptBackend;
+
+/// [Merlin Transcript](Transcript) of a larger protocol, seeded by the caller
+/// before generating or verifying a PLONK proof within it with
+/// [`Prover::prove_in_context`] and [`Verifier::verify_in_context`].
+///
+/// The transcript has to be created with the label of the protocol, and
+/// every message of the protocol preceding the proof has to be appended to
+/// it, in the same order on both sides. The label of the circuit, its
+/// description and the public inputs are appended on top of them, followed
+/// by the rounds of the proof. Once the proof is generated or verified, the
+/// transcripts of the prover and the verifier are left in the same state, so
+/// the protocol can keep deriving challenges bound to the proof.
+///
+/// The appends performed against the transcript are counted, so proving or
+/// verifying with a transcript that wasn't seeded panics in debug builds:
+/// such a proof isn't bound to any context, and could be replayed in every
+/// instance of the protocol.
+///
+/// [`Prover::prove_in_context`]: crate::prelude::Prover::prove_in_context
+/// [`Verifier::verify_in_context`]:
+/// crate::prelude::Verifier::verify_in_context
+#[derive(Clone)]
+pub struct ContextTranscript {
+    transcript: Transcript,
+    appends: usize,
+}
+
+impl ContextTranscript {
+    /// Whether anything was appended to the transcript since its creation
+    pub const fn is_seeded(&self) -> bool {
+        self.appends > 0
+    }
+
+    /// Underlying Merlin transcript, to continue the protocol with it
+    pub fn into_inner(self) -> Transcript {
+        self.transcript
+    }
+}
+
+impl TranscriptBackend for ContextTranscript {
+    fn new(label: &[u8]) -> Self {
+        Self {
+            transcript: TranscriptBackend::new(label),
+            appends: 0,
+        }
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.appends += 1;
+        TranscriptBackend::append_message(&mut self.transcript, label, message);
+    }
+
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        self.appends += 1;
+        self.transcript.append_point(label, point);
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        self.appends += 1;
+        self.transcript.append_scalar(label, s);
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        self.transcript.challenge_scalar(label)
+    }
+}
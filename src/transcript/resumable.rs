@@ -0,0 +1,318 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Transcript compatible with the [Merlin Transcript](merlin::Transcript)
+//! whose state can be exported and resumed.
+//!
+//! Merlin keeps its Strobe state private, so the Strobe-128 operations it
+//! relies on, `meta-AD`, `AD` and `PRF`, are reimplemented over the same
+//! Keccak-f\[1600\] permutation.
+
+use dusk_bls12_381::{BlsScalar, G1Affine};
+use dusk_bytes::Serializable;
+
+use super::TranscriptBackend;
+
+/// Strobe rate for the 128-bit security level
+const STROBE_R: u8 = 166;
+
+const FLAG_I: u8 = 1;
+const FLAG_A: u8 = 1 << 1;
+const FLAG_C: u8 = 1 << 2;
+const FLAG_M: u8 = 1 << 4;
+
+/// Protocol label of the Merlin transcripts
+const MERLIN_PROTOCOL_LABEL: &[u8] = b"Merlin v1.0";
+
+/// Strobe-128 context supporting only the operations used by Merlin
+#[derive(Clone, PartialEq, Eq)]
+struct Strobe128 {
+    state: [u64; 25],
+    pos: u8,
+    pos_begin: u8,
+    cur_flags: u8,
+}
+
+impl Strobe128 {
+    fn new(protocol_label: &[u8]) -> Self {
+        let mut strobe = Self {
+            state: [0; 25],
+            pos: 0,
+            pos_begin: 0,
+            cur_flags: 0,
+        };
+
+        let domain = [1, STROBE_R + 2, 1, 0, 1, 96];
+        domain
+            .iter()
+            .chain(b"STROBEv1.0.2")
+            .enumerate()
+            .for_each(|(i, byte)| strobe.set_byte(i, *byte));
+        keccak::f1600(&mut strobe.state);
+
+        strobe.meta_ad(protocol_label, false);
+
+        strobe
+    }
+
+    /// Byte `i` of the state, with the lanes of the permutation laid out in
+    /// little endian
+    fn byte(&self, i: usize) -> u8 {
+        (self.state[i / 8] >> (8 * (i % 8))) as u8
+    }
+
+    fn set_byte(&mut self, i: usize, byte: u8) {
+        let shift = 8 * (i % 8);
+        let lane = &mut self.state[i / 8];
+
+        *lane = (*lane & !(0xff << shift)) | ((byte as u64) << shift);
+    }
+
+    fn xor_byte(&mut self, i: usize, byte: u8) {
+        self.state[i / 8] ^= (byte as u64) << (8 * (i % 8));
+    }
+
+    fn meta_ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_A, more);
+        self.absorb(data);
+    }
+
+    fn ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_A, more);
+        self.absorb(data);
+    }
+
+    fn prf(&mut self, data: &mut [u8], more: bool) {
+        self.begin_op(FLAG_I | FLAG_A | FLAG_C, more);
+        self.squeeze(data);
+    }
+
+    fn run_f(&mut self) {
+        self.xor_byte(self.pos as usize, self.pos_begin);
+        self.xor_byte(self.pos as usize + 1, 0x04);
+        self.xor_byte(STROBE_R as usize + 1, 0x80);
+        keccak::f1600(&mut self.state);
+        self.pos = 0;
+        self.pos_begin = 0;
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for byte in data {
+            self.xor_byte(self.pos as usize, *byte);
+            self.pos += 1;
+            if self.pos == STROBE_R {
+                self.run_f();
+            }
+        }
+    }
+
+    fn squeeze(&mut self, data: &mut [u8]) {
+        for byte in data {
+            *byte = self.byte(self.pos as usize);
+            self.set_byte(self.pos as usize, 0);
+            self.pos += 1;
+            if self.pos == STROBE_R {
+                self.run_f();
+            }
+        }
+    }
+
+    fn begin_op(&mut self, flags: u8, more: bool) {
+        // continuing an operation keeps the flags, which are only set by the
+        // transcript itself
+        if more {
+            return;
+        }
+
+        let old_begin = self.pos_begin;
+        self.pos_begin = self.pos + 1;
+        self.cur_flags = flags;
+
+        self.absorb(&[old_begin, flags]);
+
+        // force running F if C is set, the K flag is never used
+        if flags & FLAG_C != 0 && self.pos != 0 {
+            self.run_f();
+        }
+    }
+}
+
+/// Fiat-Shamir transcript producing the same challenges as the
+/// [Merlin Transcript](merlin::Transcript), whose state can be exported at
+/// any point and resumed later.
+///
+/// This allows, for instance, a recursive verifier to resume the transcript
+/// of a proof from the state obtained with
+/// [`Verifier::evaluation_transcript`], instead of absorbing every
+/// commitment again. As the challenges match, the proofs generated with
+/// either backend are interchangeable.
+///
+/// The state is serialized as the 200 bytes of the Keccak state, followed
+/// by the position, the beginning of the current operation and its flags.
+///
+/// [`Verifier::evaluation_transcript`]:
+/// crate::prelude::Verifier::evaluation_transcript
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResumableTranscript {
+    strobe: Strobe128,
+}
+
+impl core::fmt::Debug for ResumableTranscript {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // the state of the transcript would leak the messages appended to it
+        f.debug_struct("ResumableTranscript")
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResumableTranscript {
+    /// Append a `message` with the given `label`, framed as done by Merlin
+    fn append_framed(&mut self, label: &[u8], message: &[u8]) {
+        let len = (message.len() as u32).to_le_bytes();
+
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&len, true);
+        self.strobe.ad(message, false);
+    }
+
+    /// Fill `dest` with the challenge bytes of the given `label`
+    fn challenge_bytes(&mut self, label: &[u8], dest: &mut [u8]) {
+        let len = (dest.len() as u32).to_le_bytes();
+
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&len, true);
+        self.strobe.prf(dest, false);
+    }
+}
+
+impl TranscriptBackend for ResumableTranscript {
+    fn new(label: &[u8]) -> Self {
+        let mut transcript = Self {
+            strobe: Strobe128::new(MERLIN_PROTOCOL_LABEL),
+        };
+        transcript.append_framed(b"dom-sep", label);
+
+        transcript
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.append_framed(label, message);
+    }
+
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        self.append_framed(label, &point.to_bytes());
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        self.append_framed(label, &s.to_bytes());
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        let mut buf = [0u8; 64];
+        self.challenge_bytes(label, &mut buf);
+
+        BlsScalar::from_bytes_wide(&buf)
+    }
+}
+
+impl Serializable<203> for ResumableTranscript {
+    type Error = dusk_bytes::Error;
+
+    fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut buf = [0u8; Self::SIZE];
+
+        buf[..200]
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, byte)| *byte = self.strobe.byte(i));
+        buf[200] = self.strobe.pos;
+        buf[201] = self.strobe.pos_begin;
+        buf[202] = self.strobe.cur_flags;
+
+        buf
+    }
+
+    fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
+        let (pos, pos_begin, cur_flags) = (buf[200], buf[201], buf[202]);
+
+        // the position is always within the rate, as the permutation is run
+        // once it's reached, and only the flags used by Merlin are valid
+        let valid_flags =
+            [0, FLAG_M | FLAG_A, FLAG_A, FLAG_I | FLAG_A | FLAG_C];
+        if pos >= STROBE_R
+            || pos_begin > STROBE_R
+            || !valid_flags.contains(&cur_flags)
+        {
+            return Err(dusk_bytes::Error::InvalidData);
+        }
+
+        let mut strobe = Strobe128 {
+            state: [0; 25],
+            pos,
+            pos_begin,
+            cur_flags,
+        };
+        buf[..200]
+            .iter()
+            .enumerate()
+            .for_each(|(i, byte)| strobe.set_byte(i, *byte));
+
+        Ok(Self { strobe })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+    use merlin::Transcript;
+    use rand_core::OsRng;
+
+    #[test]
+    fn merlin_compatible() {
+        let mut merlin = Transcript::new(b"resumable");
+        let mut resumable = ResumableTranscript::new(b"resumable");
+
+        // messages longer than the rate run the permutation several times
+        let long = [0x5a; 1000];
+        for message in [&b"message"[..], &[], &long] {
+            TranscriptBackend::append_message(&mut merlin, b"msg", message);
+            resumable.append_message(b"msg", message);
+        }
+
+        for _ in 0..8 {
+            let s = BlsScalar::random(&mut OsRng);
+            merlin.append_scalar(b"s", &s);
+            resumable.append_scalar(b"s", &s);
+
+            assert_eq!(
+                merlin.challenge_scalar(b"challenge"),
+                resumable.challenge_scalar(b"challenge")
+            );
+        }
+    }
+
+    #[test]
+    fn resume() {
+        let mut transcript = ResumableTranscript::new(b"resumable");
+        transcript.append_scalar(b"s", &BlsScalar::from(7));
+
+        let bytes = transcript.to_bytes();
+        let mut resumed = ResumableTranscript::from_bytes(&bytes)
+            .expect("the state should deserialize");
+        assert_eq!(resumed, transcript);
+
+        assert_eq!(
+            transcript.challenge_scalar(b"challenge"),
+            resumed.challenge_scalar(b"challenge")
+        );
+
+        // the position must be within the rate
+        let mut bytes = transcript.to_bytes();
+        bytes[200] = STROBE_R;
+        assert!(ResumableTranscript::from_bytes(&bytes).is_err());
+    }
+}
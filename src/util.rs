@@ -69,6 +69,11 @@ pub(crate) fn slow_multiscalar_mul_single_base(
 // while we do not have batch inversion for scalars
 use core::ops::MulAssign;
 
+/// Invert every element of `v` in place.
+///
+/// Zero elements have no inverse and are left untouched, which hides them
+/// from the caller. Use [`batch_inversion_checked`] where a zero element is an
+/// error.
 pub fn batch_inversion(v: &mut [BlsScalar]) {
     // Montgomery’s Trick and Fast Implementation of Masked AES
     // Genelle, Prouff and Quisquater
@@ -101,9 +106,115 @@ pub fn batch_inversion(v: &mut [BlsScalar]) {
         tmp = new_tmp;
     }
 }
+
+/// Invert every element of `v` in place, unless one of them is zero.
+///
+/// Returns the index of the first zero element, leaving `v` unchanged.
+pub(crate) fn batch_inversion_checked(
+    v: &mut [BlsScalar],
+) -> Result<(), usize> {
+    match v.iter().position(|f| f == &BlsScalar::zero()) {
+        Some(index) => Err(index),
+        None => {
+            batch_inversion(v);
+            Ok(())
+        }
+    }
+}
+
+/// Sum the products of the pairs of scalars with a single modular reduction.
+///
+/// The products of the Montgomery representations are accumulated as wide
+/// integers, each below `2^510`, so the 576 bits of the accumulator hold up
+/// to `2^66` of them. Since every representation carries a factor `R`, the
+/// accumulated sum is reduced modulo `p` and then divided by `R^2` with two
+/// Montgomery reductions.
+pub(crate) fn sum_of_products<'a, I>(pairs: I) -> BlsScalar
+where
+    I: IntoIterator<Item = (&'a BlsScalar, &'a BlsScalar)>,
+{
+    let mut acc = [0u64; 9];
+
+    for (a, b) in pairs {
+        let (a, b) = (a.internal_repr(), b.internal_repr());
+
+        // schoolbook multiplication, without the reduction
+        let mut product = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let t = product[i + j] as u128
+                    + a[i] as u128 * b[j] as u128
+                    + carry;
+                product[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            product[i + 4] = carry as u64;
+        }
+
+        let mut carry = 0u128;
+        for (limb, p) in acc.iter_mut().zip(product.into_iter().chain(Some(0)))
+        {
+            let t = *limb as u128 + p as u128 + carry;
+            *limb = t as u64;
+            carry = t >> 64;
+        }
+    }
+
+    let mut wide = [0u8; 64];
+    wide.chunks_exact_mut(8)
+        .zip(acc.iter())
+        .for_each(|(bytes, limb)| bytes.copy_from_slice(&limb.to_le_bytes()));
+
+    let mut sum = BlsScalar::from_bytes_wide(&wide);
+    if acc[8] != 0 {
+        sum += BlsScalar::pow_of_2(512) * BlsScalar::from(acc[8]);
+    }
+
+    sum.reduce().reduce()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_powers_of() {
+        let scalar = BlsScalar::random(&mut OsRng);
+        let n = 1 << 10;
+        let z_n = scalar.pow(&[n, 0, 0, 0]);
+
+        let powers = powers_of(&z_n, 3);
+        assert_eq!(powers.len(), 4);
+        for (i, power) in powers.iter().enumerate() {
+            assert_eq!(*power, scalar.pow(&[i as u64 * n, 0, 0, 0]));
+        }
+
+        assert_eq!(powers_of(&scalar, 0), vec![BlsScalar::one()]);
+    }
+
+    #[test]
+    fn test_sum_of_products() {
+        let naive = |a: &[BlsScalar], b: &[BlsScalar]| -> BlsScalar {
+            a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+        };
+
+        for len in [0, 1, 2, 63, 256] {
+            let a: Vec<BlsScalar> =
+                (0..len).map(|_| BlsScalar::random(&mut OsRng)).collect();
+            let b: Vec<BlsScalar> =
+                (0..len).map(|_| BlsScalar::random(&mut OsRng)).collect();
+
+            assert_eq!(sum_of_products(a.iter().zip(b.iter())), naive(&a, &b));
+        }
+
+        // sums beyond 512 bits are reduced as well
+        let max = -BlsScalar::one();
+        let a = vec![max; 1 << 12];
+        assert_eq!(sum_of_products(a.iter().zip(a.iter())), naive(&a, &a));
+    }
+
     #[test]
     fn test_batch_inversion() {
         let one = BlsScalar::from(1);
@@ -120,4 +231,27 @@ mod test {
             assert_eq!(x.invert().unwrap(), *x_inv);
         }
     }
+
+    #[test]
+    fn test_batch_inversion_checked() {
+        let scalars: Vec<BlsScalar> = (1..=5u64).map(BlsScalar::from).collect();
+
+        let mut inverted_scalars = scalars.clone();
+        assert_eq!(batch_inversion_checked(&mut inverted_scalars), Ok(()));
+        for (x, x_inv) in scalars.iter().zip(inverted_scalars.iter()) {
+            assert_eq!(x.invert().unwrap(), *x_inv);
+        }
+
+        // a zero element is reported without touching the slice
+        let mut with_zero = scalars.clone();
+        with_zero[2] = BlsScalar::zero();
+        let original = with_zero.clone();
+        assert_eq!(batch_inversion_checked(&mut with_zero), Err(2));
+        assert_eq!(with_zero, original);
+
+        // while the unchecked inversion skips it
+        batch_inversion(&mut with_zero);
+        assert_eq!(with_zero[2], BlsScalar::zero());
+        assert_eq!(with_zero[3], BlsScalar::from(4).invert().unwrap());
+    }
 }
@@ -88,3 +88,72 @@ fn component_boolean() {
     let circuit = TestCircuit::new(bit);
     check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
 }
+
+#[test]
+fn component_booleans() {
+    pub struct TestCircuit {
+        bits: [BlsScalar; 4],
+    }
+
+    impl TestCircuit {
+        pub fn new(bits: [BlsScalar; 4]) -> Self {
+            Self { bits }
+        }
+    }
+
+    impl Default for TestCircuit {
+        fn default() -> Self {
+            Self::new([BlsScalar::zero(); 4])
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_bits: Vec<Witness> = self
+                .bits
+                .iter()
+                .map(|bit| composer.append_witness(*bit))
+                .collect();
+
+            composer.component_booleans(&w_bits);
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_booleans";
+    let mut rng = StdRng::seed_from_u64(0xb001);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // public inputs to be used by all tests
+    let pi = vec![];
+
+    // Test default works:
+    let msg = "Default circuit verification should pass";
+    let circuit = TestCircuit::default();
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // Test mixed bits work
+    let msg = "Circuit with bits = [1, 0, 1, 1] should pass";
+    let one = BlsScalar::one();
+    let zero = BlsScalar::zero();
+    let circuit = TestCircuit::new([one, zero, one, one]);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // Test a single non-bit fails
+    let msg = "Circuit with bits = [1, 0, 2, 1] shouldn't pass";
+    let circuit = TestCircuit::new([one, zero, BlsScalar::from(2), one]);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+
+    // Test a random last bit fails
+    let msg = "Circuit with a random last bit shouldn't pass";
+    let circuit =
+        TestCircuit::new([one, zero, one, BlsScalar::random(&mut rng)]);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+}
@@ -124,3 +124,563 @@ fn circuit_with_all_gates() {
         .verify(&proof, &public_inputs)
         .expect("failed to verify proof");
 }
+
+#[test]
+fn witness_assignments() {
+    let mut composer = Composer::initialized();
+    let offset = composer.witness_assignments().len();
+
+    let a = composer.append_witness(BlsScalar::from(2u64));
+    let b = composer.append_witness(BlsScalar::from(3u64));
+    let c = composer.gate_mul(Constraint::new().mult(1).a(a).b(b));
+
+    let assignments: Vec<_> =
+        composer.witness_assignments().skip(offset).collect();
+
+    assert_eq!(
+        assignments,
+        vec![
+            (a, BlsScalar::from(2u64)),
+            (b, BlsScalar::from(3u64)),
+            (c, BlsScalar::from(6u64)),
+        ]
+    );
+
+    let (zero, value) = composer
+        .witness_assignments()
+        .next()
+        .expect("the composer is initialized");
+    assert_eq!(zero, Composer::ZERO);
+    assert_eq!(value, BlsScalar::zero());
+}
+
+#[test]
+fn gate_accessors() {
+    let mut composer = Composer::initialized();
+
+    let a = composer.append_witness(BlsScalar::from(2u64));
+    let b = composer.append_witness(BlsScalar::from(3u64));
+    let constraint = Constraint::new()
+        .mult(1)
+        .left(5)
+        .constant(7)
+        .public(-BlsScalar::from(19u64))
+        .a(a)
+        .b(b);
+    composer.append_gate(constraint);
+
+    assert_eq!(composer.gates().len(), composer.constraints());
+
+    let gate = composer.gates().last().expect("a gate was appended");
+
+    assert_eq!(gate.selector(Selector::Multiplication), BlsScalar::one());
+    assert_eq!(gate.selector(Selector::Left), BlsScalar::from(5u64));
+    assert_eq!(gate.selector(Selector::Right), BlsScalar::zero());
+    assert_eq!(gate.selector(Selector::Constant), BlsScalar::from(7u64));
+    assert_eq!(gate.selector(Selector::PublicInput), BlsScalar::zero());
+    assert_eq!(gate.selector(Selector::Arithmetic), BlsScalar::one());
+    assert_eq!(gate.selector(Selector::Range), BlsScalar::zero());
+
+    assert_eq!(gate.wire(WiredWitness::A), a);
+    assert_eq!(gate.wire(WiredWitness::B), b);
+    assert_eq!(gate.wire(WiredWitness::C), Composer::ZERO);
+    assert_eq!(gate.wire(WiredWitness::D), Composer::ZERO);
+}
+
+#[test]
+fn gate_constraint_conversion() {
+    let mut composer = Composer::initialized();
+
+    let a = composer.append_witness(BlsScalar::from(0xf0u64));
+    let b = composer.append_witness(BlsScalar::from(0x3cu64));
+    composer.append_gate(Constraint::new().mult(1).left(2).a(a).b(b));
+    composer.component_range::<4>(a);
+    composer.append_logic_xor::<2>(a, b);
+
+    // every gate survives the round trip, internal selectors included
+    composer.gates().iter().for_each(|gate| {
+        let constraint = Constraint::from(gate);
+        assert_eq!(Gate::from(&constraint), *gate);
+    });
+
+    // the selectors and wires of a constraint are kept, but its public input
+    // is dropped
+    let constraint = Constraint::new()
+        .mult(3)
+        .output(-BlsScalar::one())
+        .fourth(5)
+        .constant(7)
+        .a(a)
+        .b(b)
+        .c(a)
+        .d(b);
+    let gate = Gate::from(&constraint);
+    assert_eq!(gate.selector(Selector::Fourth), BlsScalar::from(5u64));
+    assert_eq!(gate.wire(WiredWitness::C), a);
+    assert_eq!(Constraint::from(&gate), constraint);
+
+    let public = constraint.public(BlsScalar::one());
+    assert_eq!(Gate::from(&public), gate);
+    assert_eq!(Constraint::from(&Gate::from(&public)), constraint);
+}
+
+#[test]
+fn stats() {
+    let mut composer = Composer::initialized();
+    let base = composer.stats();
+
+    assert_eq!(base.gates, composer.constraints());
+    assert_eq!(base.arithmetic, base.gates);
+
+    let a = composer.append_witness(BlsScalar::from(0xf0u64));
+    let b = composer.append_witness(BlsScalar::from(0x3cu64));
+
+    composer.component_range::<4>(a);
+    composer.append_logic_xor::<4>(a, b);
+    let stats = composer.stats();
+
+    assert_eq!(stats.gates, composer.constraints());
+    assert_eq!(stats.range, 1);
+    assert_eq!(stats.logic, 4);
+    assert_eq!(stats.fixed_base, 0);
+    assert_eq!(stats.variable_base, 0);
+    assert_eq!(stats.custom, 0);
+    assert!(stats.arithmetic > base.arithmetic);
+    assert!(stats.witnesses > base.witnesses);
+    assert!(stats.max_fan_out > base.max_fan_out);
+}
+
+#[test]
+fn padding() {
+    let mut composer = Composer::initialized();
+
+    let a = composer.append_witness(BlsScalar::one());
+    while composer.constraints() < 9 {
+        composer.assert_equal_constant(a, BlsScalar::one(), None);
+    }
+
+    assert_eq!(composer.padded_size(), 16);
+    assert_eq!(composer.padding_overhead(), 7);
+
+    while composer.constraints() < 16 {
+        composer.assert_equal_constant(a, BlsScalar::one(), None);
+    }
+
+    assert_eq!(composer.padded_size(), 16);
+    assert_eq!(composer.padding_overhead(), 0);
+}
+
+#[test]
+fn estimate_proving_memory() {
+    let mut composer = Composer::initialized();
+
+    let a = composer.append_witness(BlsScalar::one());
+    while composer.constraints() < 9 {
+        composer.assert_equal_constant(a, BlsScalar::one(), None);
+    }
+    let estimate = composer.estimate_proving_memory();
+    assert!(estimate > 0);
+
+    // the estimate depends on the padded size only
+    while composer.constraints() < 16 {
+        composer.assert_equal_constant(a, BlsScalar::one(), None);
+    }
+    assert_eq!(composer.estimate_proving_memory(), estimate);
+
+    // and is linear in it
+    composer.assert_equal_constant(a, BlsScalar::one(), None);
+    assert_eq!(composer.padded_size(), 32);
+    assert_eq!(composer.estimate_proving_memory(), 2 * estimate);
+
+    // every registered custom gate adds its selector to the prover key
+    let estimate = composer.estimate_proving_memory();
+    composer.append_registered_gate(1, Constraint::new().a(a));
+    assert!(composer.estimate_proving_memory() > estimate);
+}
+
+#[test]
+fn checkpoint_rollback() {
+    #[derive(Default)]
+    struct TestCircuit {
+        rollback: bool,
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let a = composer.append_witness(BlsScalar::from(2u64));
+            let b = composer.append_witness(BlsScalar::from(3u64));
+            let constraint = Constraint::new().mult(1).a(a).b(b);
+            let c = composer.gate_mul(constraint);
+
+            if self.rollback {
+                let checkpoint = composer.checkpoint();
+
+                // a gadget wiring new and existing witnesses, with a public
+                // input, that is discarded
+                let d = composer.append_public(BlsScalar::from(6u64));
+                composer.assert_equal(c, d);
+                composer.component_range::<8>(a);
+                let e = composer.gate_add(Constraint::new().left(1).a(b));
+                composer.assert_equal_constant(e, BlsScalar::from(3u64), None);
+
+                composer.rollback(checkpoint);
+                assert_eq!(composer.checkpoint(), checkpoint);
+            }
+
+            composer.assert_equal_constant(
+                c,
+                BlsScalar::zero(),
+                Some(BlsScalar::from(6u64)),
+            );
+
+            Ok(())
+        }
+    }
+
+    let rng = &mut StdRng::seed_from_u64(0xc4ec);
+    let pp = PublicParameters::setup(1 << 8, rng).expect("failed to create pp");
+    let label = b"checkpoint_rollback";
+
+    let (_, verifier) = Compiler::compile_with_circuit(
+        &pp,
+        label,
+        &TestCircuit { rollback: false },
+    )
+    .expect("failed to compile circuit");
+    let circuit = TestCircuit { rollback: true };
+    let (prover, rolled_back) =
+        Compiler::compile_with_circuit(&pp, label, &circuit)
+            .expect("failed to compile circuit");
+
+    // the rolled back circuit is the one without the gadget
+    assert_eq!(verifier.circuit_hash(), rolled_back.circuit_hash());
+    assert_eq!(
+        verifier.public_input_indexes(),
+        rolled_back.public_input_indexes()
+    );
+
+    let (proof, public_inputs) =
+        prover.prove(rng, &circuit).expect("failed to prove");
+    assert_eq!(public_inputs, vec![BlsScalar::from(6u64)]);
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // rolling back to a later checkpoint has no effect
+    let mut composer = Composer::initialized();
+    let checkpoint = composer.checkpoint();
+    composer.append_witness(BlsScalar::one());
+    let later = composer.checkpoint();
+    composer.rollback(checkpoint);
+    composer.rollback(later);
+    assert_eq!(composer.checkpoint(), checkpoint);
+}
+
+#[test]
+fn append_circuit() {
+    // a · b = c, with c public
+    fn mul_gadget(a: BlsScalar, b: BlsScalar) -> (Composer, Witness) {
+        let mut gadget = Composer::initialized();
+        let w_a = gadget.append_witness(a);
+        let w_b = gadget.append_witness(b);
+        let constraint =
+            Constraint::new().mult(1).public(-(a * b)).a(w_a).b(w_b);
+        gadget.append_gate(constraint);
+        (gadget, w_a)
+    }
+
+    #[derive(Default)]
+    pub struct ComposedCircuit {
+        x: BlsScalar,
+        a: BlsScalar,
+        b: BlsScalar,
+    }
+
+    impl Circuit for ComposedCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_x = composer.append_witness(self.x);
+            composer.component_range::<4>(w_x);
+
+            // the input of the gadget is wired to the range checked witness
+            let (gadget, w_a) = mul_gadget(self.a, self.b);
+            composer.append_circuit(&gadget, &[(w_a, w_x)]);
+
+            Ok(())
+        }
+    }
+
+    let rng = &mut StdRng::seed_from_u64(0xa99);
+    let label = b"append_circuit";
+    let pp = PublicParameters::setup(1 << 7, rng).expect("failed to create pp");
+    let (prover, verifier) = Compiler::compile::<ComposedCircuit>(&pp, label)
+        .expect("failed to compile circuit");
+
+    // every gate of the gadget is appended, and only its unmapped
+    // witnesses but the constants
+    let (gadget, w_a) = mul_gadget(BlsScalar::one(), BlsScalar::one());
+    let mut composer = Composer::initialized();
+    let w_x = composer.append_witness(BlsScalar::one());
+    let constraints = composer.constraints();
+    let witnesses = composer.witness_assignments().len();
+    composer.append_circuit(&gadget, &[(w_a, w_x)]);
+    assert_eq!(composer.constraints(), constraints + gadget.constraints());
+    assert_eq!(
+        composer.witness_assignments().len(),
+        witnesses + gadget.witness_assignments().len() - 3
+    );
+    assert_eq!(composer.gates().last().unwrap().wire(WiredWitness::A), w_x);
+
+    let circuit = ComposedCircuit {
+        x: BlsScalar::from(7u64),
+        a: BlsScalar::from(7u64),
+        b: BlsScalar::from(3u64),
+    };
+    let (proof, public_inputs) =
+        prover.prove(rng, &circuit).expect("failed to prove");
+    assert_eq!(public_inputs, vec![-BlsScalar::from(21u64)]);
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // the gadget input takes the value of the wired witness, so the
+    // multiplication doesn't hold
+    let circuit = ComposedCircuit {
+        x: BlsScalar::from(5u64),
+        a: BlsScalar::from(7u64),
+        b: BlsScalar::from(3u64),
+    };
+    let mut composer = Composer::initialized();
+    circuit.circuit(&mut composer).unwrap();
+    assert!(matches!(
+        composer.check_satisfied(),
+        Err(Error::UnsatisfiedConstraint {
+            selector: "arithmetic",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn sigma_permutation() {
+    let mut composer = Composer::initialized();
+
+    let x = composer.append_witness(BlsScalar::from(3u64));
+    let y = composer.append_witness(BlsScalar::from(5u64));
+    let first = composer.constraints();
+    composer.append_gate(Constraint::new().left(1).a(x).c(y));
+    composer.append_gate(Constraint::new().right(1).b(x));
+
+    let n = composer.padded_size();
+    let sigma = composer.sigma_permutation();
+    assert!(sigma.iter().all(|column| column.len() == n));
+
+    // the mappings form a permutation of the wires
+    let mut wires: Vec<usize> = sigma.iter().flatten().copied().collect();
+    wires.sort_unstable();
+    assert_eq!(wires, (0..4 * n).collect::<Vec<_>>());
+
+    // the cycle of `x` goes through the left and right wires of the gates
+    let left = first;
+    let right = n + first + 1;
+    assert_eq!(sigma[0][left], right);
+    assert_eq!(sigma[1][first + 1], left);
+
+    // while the output wire of `y` isn't shared
+    let output = 2 * n + first;
+    assert_eq!(sigma[2][first], output);
+
+    // nor are the wires of the padding
+    let padding = composer.constraints();
+    if padding < n {
+        assert_eq!(sigma[3][padding], 3 * n + padding);
+    }
+}
+
+#[test]
+fn optimize() {
+    #[derive(Default)]
+    struct TestCircuit {
+        degenerate: bool,
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let a = composer.append_witness(BlsScalar::from(2u64));
+            let b = composer.append_witness(BlsScalar::from(3u64));
+            let product = Constraint::new().mult(1).a(a).b(b);
+            let c = composer.gate_mul(product);
+
+            if self.degenerate {
+                // a gate without coefficients, and a repeated gate
+                composer.append_gate(Constraint::new().a(a).b(c));
+                let repeated =
+                    Constraint::new().mult(1).output(-BlsScalar::one());
+                composer.append_gate(repeated.a(a).b(b).c(c));
+                composer.append_gate(repeated.a(a).b(b).c(c));
+            }
+
+            // the gates holding the shifted wires of the range gates are kept
+            composer.component_range::<4>(a);
+
+            let d = composer.append_public(BlsScalar::from(6u64));
+            composer.assert_equal(c, d);
+
+            composer.optimize();
+
+            Ok(())
+        }
+    }
+
+    let rng = &mut StdRng::seed_from_u64(0x0971);
+    let pp = PublicParameters::setup(1 << 8, rng).expect("failed to create pp");
+    let label = b"optimize";
+
+    let (_, verifier) = Compiler::compile_with_circuit(
+        &pp,
+        label,
+        &TestCircuit { degenerate: false },
+    )
+    .expect("failed to compile circuit");
+    let circuit = TestCircuit { degenerate: true };
+    let (prover, optimized) =
+        Compiler::compile_with_circuit(&pp, label, &circuit)
+            .expect("failed to compile circuit");
+
+    // the optimized circuit is the one without the degenerate gates, with
+    // the public input moved along with its gate
+    assert_eq!(verifier.circuit_hash(), optimized.circuit_hash());
+    assert_eq!(
+        verifier.public_input_indexes(),
+        optimized.public_input_indexes()
+    );
+
+    let (proof, public_inputs) =
+        prover.prove(rng, &circuit).expect("failed to prove");
+    assert_eq!(public_inputs, vec![BlsScalar::from(6u64)]);
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // only the gates that can't fail and the repetitions are removed
+    let mut composer = Composer::initialized();
+    let a = composer.append_witness(BlsScalar::from(2u64));
+    let constraints = composer.constraints();
+    composer.append_gate(Constraint::new().a(a));
+    composer.append_gate(Constraint::new().left(1).a(a));
+    composer.append_gate(Constraint::new().left(1).a(a));
+    composer.append_gate(Constraint::new().constant(1).a(a));
+    assert_eq!(composer.optimize(), 2);
+    assert_eq!(composer.constraints(), constraints + 2);
+    assert_eq!(composer.optimize(), 0);
+}
+
+#[test]
+fn serialize() {
+    // a^4 = c
+    fn quartic(wires: &CustomGateWires) -> BlsScalar {
+        wires.a.square().square() - wires.c
+    }
+
+    #[derive(Default)]
+    struct TestCircuit {
+        a: BlsScalar,
+    }
+
+    impl Circuit for TestCircuit {
+        const CUSTOM_GATES: &'static [CustomGate] = &[CustomGate::new(quartic)];
+
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let a = composer.append_witness(self.a);
+            let quartic = composer.append_witness(self.a.square().square());
+
+            composer
+                .append_registered_gate(0, Constraint::new().a(a).c(quartic));
+            composer.component_range::<4>(a);
+
+            let pi = composer.append_public(self.a.square().square());
+            composer.assert_equal(quartic, pi);
+
+            Ok(())
+        }
+    }
+
+    let rng = &mut StdRng::seed_from_u64(0x5e71);
+    let pp = PublicParameters::setup(1 << 8, rng).expect("failed to create pp");
+    let label = b"serialize";
+
+    let mut composer = Composer::initialized();
+    TestCircuit::default()
+        .circuit(&mut composer)
+        .expect("failed to build circuit");
+
+    let bytes = composer.to_bytes();
+    let reloaded =
+        Composer::from_bytes(&bytes).expect("failed to deserialize composer");
+
+    assert_eq!(reloaded.gates(), composer.gates());
+    assert_eq!(reloaded.sigma_permutation(), composer.sigma_permutation());
+    assert_eq!(reloaded.to_bytes(), bytes);
+
+    // the reloaded composer compiles to the same prover and verifier
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("failed to compile circuit");
+    let (reloaded_prover, reloaded_verifier) =
+        Compiler::compile_with_composer::<TestCircuit>(&pp, label, &reloaded)
+            .expect("failed to compile reloaded circuit");
+
+    assert_eq!(reloaded_prover.to_bytes(), prover.to_bytes());
+    assert_eq!(reloaded_verifier.to_bytes(), verifier.to_bytes());
+
+    // and so to the same proofs
+    let circuit = TestCircuit {
+        a: BlsScalar::from(3u64),
+    };
+    let (proof, public_inputs) = prover
+        .prove(&mut StdRng::seed_from_u64(0x9f0), &circuit)
+        .expect("failed to prove");
+    let (reloaded_proof, _) = reloaded_prover
+        .prove(&mut StdRng::seed_from_u64(0x9f0), &circuit)
+        .expect("failed to prove");
+
+    assert_eq!(reloaded_proof, proof);
+    reloaded_verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // truncated bytes are rejected
+    assert_eq!(
+        Composer::from_bytes(&bytes[..bytes.len() - 1]).err(),
+        Some(Error::InvalidSerializedCircuit)
+    );
+
+    // the witnesses past the last wired one aren't serialized
+    let mut unwired = Composer::initialized();
+    TestCircuit::default()
+        .circuit(&mut unwired)
+        .expect("failed to build circuit");
+    unwired.append_witness(BlsScalar::one());
+    assert_eq!(unwired.to_bytes(), bytes);
+
+    // the number of witnesses is encoded first, and can't exceed the
+    // witnesses wired to the gates
+    let (witnesses, len) = match bytes[0] {
+        0xcc => (bytes[1] as u64, 2),
+        0xcd => (u16::from_be_bytes([bytes[1], bytes[2]]) as u64, 3),
+        n => (n as u64, 1),
+    };
+    let with_witnesses = |witnesses: u64| {
+        let mut with = vec![0xcf];
+        with.extend(witnesses.to_be_bytes());
+        with.extend(&bytes[len..]);
+        Composer::from_bytes(&with).err()
+    };
+    assert_eq!(with_witnesses(witnesses), None);
+    assert_eq!(
+        with_witnesses(witnesses + 1),
+        Some(Error::InvalidSerializedCircuit)
+    );
+    assert_eq!(
+        with_witnesses(u64::MAX),
+        Some(Error::InvalidSerializedCircuit)
+    );
+}
@@ -0,0 +1,435 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+// a^4 = c
+fn quartic(wires: &CustomGateWires) -> BlsScalar {
+    wires.a.square().square() - wires.c
+}
+
+// a · b = d of the next gate
+fn shifted_mul(wires: &CustomGateWires) -> BlsScalar {
+    wires.a * wires.b - wires.d_w
+}
+
+// a^8 = c
+fn octic(wires: &CustomGateWires) -> BlsScalar {
+    wires.a.square().square().square() - wires.c
+}
+
+// b^5 = d
+fn quintic(wires: &CustomGateWires) -> BlsScalar {
+    wires.b.square().square() * wires.b - wires.d
+}
+
+#[test]
+fn custom_gate() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        quartic: BlsScalar,
+        product: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(
+            a: BlsScalar,
+            b: BlsScalar,
+            quartic: BlsScalar,
+            product: BlsScalar,
+        ) -> Self {
+            Self {
+                a,
+                b,
+                quartic,
+                product,
+            }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        const CUSTOM_GATES: &'static [CustomGate] =
+            &[CustomGate::new(quartic), CustomGate::new(shifted_mul)];
+
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_quartic = composer.append_witness(self.quartic);
+            let w_product = composer.append_witness(self.product);
+
+            let constraint = Constraint::new().a(w_a).c(w_quartic);
+            composer.append_registered_gate(0, constraint);
+
+            let constraint = Constraint::new().a(w_a).b(w_b);
+            composer.append_registered_gate(1, constraint);
+            composer.append_gate(Constraint::new().d(w_product));
+
+            Ok(())
+        }
+    }
+
+    let label = b"custom_gate";
+    let mut rng = StdRng::seed_from_u64(0xc057);
+    let capacity = 1 << 5;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let pi = vec![];
+
+    // Test default works
+    let msg = "Default circuit verification should pass";
+    let circuit = TestCircuit::default();
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // Test both identities hold
+    let msg = "Verification of satisfied circuit should pass";
+    let a = BlsScalar::from(3);
+    let b = BlsScalar::from(5);
+    let circuit = TestCircuit::new(a, b, BlsScalar::from(81), a * b);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // Test the first identity doesn't hold
+    let msg = "Proof creation of unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a, b, BlsScalar::from(27), a * b);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+
+    // Test the second identity doesn't hold
+    let msg = "Proof creation of unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a, b, BlsScalar::from(81), a + b);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+}
+
+#[test]
+fn custom_gate_high_degree() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        octic: BlsScalar,
+        quintic: BlsScalar,
+    }
+
+    impl Circuit for TestCircuit {
+        const CUSTOM_GATES: &'static [CustomGate] = &[
+            CustomGate::with_degree(octic, 8),
+            CustomGate::with_degree(quintic, 5),
+        ];
+
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_octic = composer.append_witness(self.octic);
+            let w_quintic = composer.append_witness(self.quintic);
+
+            let constraint = Constraint::new().a(w_a).c(w_octic);
+            composer.append_registered_gate(0, constraint);
+
+            let constraint = Constraint::new().b(w_b).d(w_quintic);
+            composer.append_registered_gate(1, constraint);
+
+            Ok(())
+        }
+    }
+
+    assert_eq!(TestCircuit::CUSTOM_GATES[0].degree(), 8);
+    assert_eq!(
+        CustomGate::with_degree(quintic, 2).degree(),
+        CustomGate::DEFAULT_DEGREE
+    );
+
+    // the quotient chunks of a degree 8 gate are twice as large
+    let label = b"custom_gate_high_degree";
+    let mut rng = StdRng::seed_from_u64(0xde9);
+    let pp = PublicParameters::setup(1 << 5, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    assert!(Compiler::compile::<TestCircuit>(&pp, label).is_err());
+
+    let pp = PublicParameters::setup(1 << 6, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let pi = vec![];
+
+    // Test both identities hold
+    let msg = "Verification of satisfied circuit should pass";
+    let a = BlsScalar::from(3);
+    let b = BlsScalar::from(2);
+    let circuit = TestCircuit {
+        a,
+        b,
+        octic: BlsScalar::from(6561),
+        quintic: BlsScalar::from(32),
+    };
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // Test the degree 8 identity doesn't hold
+    let msg = "Proof creation of unsatisfied circuit should fail";
+    let circuit = TestCircuit {
+        a,
+        b,
+        octic: BlsScalar::from(81),
+        quintic: BlsScalar::from(32),
+    };
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+
+    // Test the degree 5 identity doesn't hold
+    let msg = "Proof creation of unsatisfied circuit should fail";
+    let circuit = TestCircuit {
+        a,
+        b,
+        octic: BlsScalar::from(6561),
+        quintic: BlsScalar::from(16),
+    };
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+}
+
+#[test]
+fn custom_gate_unregistered() {
+    #[derive(Default)]
+    pub struct TestCircuit;
+
+    impl Circuit for TestCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_a = composer.append_witness(BlsScalar::one());
+            composer.append_registered_gate(0, Constraint::new().a(w_a));
+
+            Ok(())
+        }
+    }
+
+    let label = b"custom_gate";
+    let mut rng = StdRng::seed_from_u64(0xc057);
+    let pp = PublicParameters::setup(1 << 4, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let err = Compiler::compile::<TestCircuit>(&pp, label)
+        .err()
+        .expect("Circuit with an unregistered custom gate shouldn't compile");
+    assert_eq!(err, Error::UnregisteredCustomGate { id: 0 });
+
+    let err = TestCircuit::compress()
+        .expect_err("Circuit with custom gates shouldn't be compressed");
+    assert_eq!(err, Error::CustomGatesNotCompressible);
+}
+
+#[test]
+fn custom_gate_serialization() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        a: BlsScalar,
+        quartic: BlsScalar,
+    }
+
+    impl Circuit for TestCircuit {
+        const CUSTOM_GATES: &'static [CustomGate] = &[CustomGate::new(quartic)];
+
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_quartic = composer.append_witness(self.quartic);
+
+            let constraint = Constraint::new().a(w_a).c(w_quartic);
+            composer.append_registered_gate(0, constraint);
+
+            Ok(())
+        }
+    }
+
+    let label = b"custom_gate";
+    let mut rng = StdRng::seed_from_u64(0xc057);
+    let pp = PublicParameters::setup(1 << 5, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let circuit = TestCircuit {
+        a: BlsScalar::from(3),
+        quartic: BlsScalar::from(81),
+    };
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+
+    let gates = TestCircuit::CUSTOM_GATES;
+    let restored = [
+        (
+            Verifier::try_from_bytes(verifier.to_bytes()),
+            Verifier::try_from_bytes_with_custom_gates(
+                verifier.to_bytes(),
+                gates,
+            ),
+        ),
+        (
+            Verifier::try_from_versioned_bytes(verifier.to_versioned_bytes()),
+            Verifier::try_from_versioned_bytes_with_custom_gates(
+                verifier.to_versioned_bytes(),
+                gates,
+            ),
+        ),
+        (
+            Verifier::try_from_compact_bytes(verifier.to_compact_bytes()),
+            Verifier::try_from_compact_bytes_with_custom_gates(
+                verifier.to_compact_bytes(),
+                gates,
+            ),
+        ),
+    ];
+
+    for (without_gates, with_gates) in restored {
+        // the identities of the gates aren't serialized
+        assert_eq!(
+            without_gates.err(),
+            Some(Error::UnregisteredCustomGate { id: 0 })
+        );
+
+        let restored = with_gates.expect("The verifier should deserialize");
+        assert_eq!(restored.custom_gates_len(), 1);
+        restored
+            .verify(&proof, &public_inputs)
+            .expect("The restored verifier should accept the proof");
+    }
+
+    // the registered gates have to match the serialized ones
+    let bytes = verifier.to_bytes();
+    let higher = [CustomGate::with_degree(quartic, 8)];
+    assert_eq!(
+        Verifier::try_from_bytes_with_custom_gates(&bytes, &higher).err(),
+        Some(Error::InconsistentCustomGate { id: 0 })
+    );
+    let more = [CustomGate::new(quartic), CustomGate::new(shifted_mul)];
+    assert_eq!(
+        Verifier::try_from_bytes_with_custom_gates(&bytes, &more).err(),
+        Some(Error::InconsistentCustomGate { id: 1 })
+    );
+
+    let view =
+        VerifierKeyRef::try_from_bytes(&bytes).expect("The view should load");
+    assert_eq!(view.custom_gates_len(), 1);
+    view.to_verifier_with_custom_gates(gates)
+        .expect("The verifier should deserialize")
+        .verify(&proof, &public_inputs)
+        .expect("The restored verifier should accept the proof");
+}
+
+#[test]
+fn custom_gate_quotient_chunks() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        a: BlsScalar,
+        octic: BlsScalar,
+    }
+
+    impl Circuit for TestCircuit {
+        const CUSTOM_GATES: &'static [CustomGate] =
+            &[CustomGate::with_degree(octic, 8)];
+
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_octic = composer.append_witness(self.octic);
+
+            let constraint = Constraint::new().a(w_a).c(w_octic);
+            composer.append_registered_gate(0, constraint);
+
+            Ok(())
+        }
+    }
+
+    let label = b"custom_gate_quotient_chunks";
+    let mut rng = StdRng::seed_from_u64(0xc4a2);
+    let pp = PublicParameters::setup(1 << 5, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    // splitting the quotient of a degree 8 gate into four chunks doesn't fit
+    // the parameters, but splitting it into eight does
+    assert!(Compiler::compile::<TestCircuit>(&pp, label).is_err());
+    let (prover, verifier) =
+        Compiler::compile_with_quotient_chunks::<TestCircuit>(&pp, label, 8)
+            .expect("Circuit should compile");
+
+    // a degree 8 gate supports at most eight chunks, and fewer than four
+    // are never supported
+    for chunks in [3, 9] {
+        assert_eq!(
+            Compiler::compile_with_quotient_chunks::<TestCircuit>(
+                &pp, label, chunks
+            )
+            .err(),
+            Some(Error::UnsupportedQuotientChunks { chunks, max: 8 })
+        );
+    }
+
+    let circuit = TestCircuit {
+        a: BlsScalar::from(3),
+        octic: BlsScalar::from(6561),
+    };
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    assert_eq!(proof.quotient_chunks(), 8);
+    assert_eq!(proof.commitments().count(), 15);
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verification should succeed");
+
+    // the extra chunks are serialized along with the proof
+    let bytes = proof.to_var_bytes();
+    assert_eq!(bytes.len(), Proof::MAX_VAR_SIZE - 8 * 48);
+    let restored =
+        Proof::from_var_bytes(&bytes).expect("The proof should deserialize");
+    assert_eq!(restored, proof);
+    verifier
+        .verify(&restored, &public_inputs)
+        .expect("Verification should succeed");
+
+    assert!(Proof::from_var_bytes(&bytes[..bytes.len() - 1]).is_err());
+    let mut unknown = bytes.clone();
+    unknown[0] = 0x02;
+    assert_eq!(
+        Proof::from_var_bytes(&unknown).err(),
+        Some(Error::UnsupportedProofVersion { version: 0x02 })
+    );
+
+    // a proof declaring fewer chunks than its circuit is rejected, the extra
+    // chunks following the 9 commitments up to `t_fourth_comm`
+    let extras = 2 + 9 * 48;
+    let mut fewer = bytes[..extras + 48].to_vec();
+    fewer[1] = 1;
+    fewer.extend(&bytes[extras + 4 * 48..]);
+    let fewer =
+        Proof::from_var_bytes(&fewer).expect("The proof should deserialize");
+    assert_eq!(fewer.quotient_chunks(), 5);
+    assert_eq!(
+        verifier.verify(&fewer, &public_inputs).err(),
+        Some(Error::InconsistentQuotientChunks {
+            expected: 8,
+            got: 5
+        })
+    );
+
+    // the number of chunks is serialized with the verifier
+    let gates = TestCircuit::CUSTOM_GATES;
+    for restored in [
+        Verifier::try_from_bytes_with_custom_gates(verifier.to_bytes(), gates),
+        Verifier::try_from_compact_bytes_with_custom_gates(
+            verifier.to_compact_bytes(),
+            gates,
+        ),
+    ] {
+        restored
+            .expect("The verifier should deserialize")
+            .verify(&proof, &public_inputs)
+            .expect("The restored verifier should accept the proof");
+    }
+}
@@ -42,3 +42,128 @@ fn generate_cdf_works() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn cdf_to_vec_works() -> io::Result<()> {
+    let mut composer = Composer::initialized();
+
+    let a = composer.append_witness(BlsScalar::from(3u64));
+    let b = composer.append_witness(BlsScalar::from(4u64));
+    let constraint = Constraint::new().mult(1).public(-BlsScalar::from(12u64));
+    let constraint = constraint.a(a).b(b);
+    composer.append_gate(constraint);
+
+    let bytes = composer.cdf_to_vec()?;
+    let mut cdf = CircuitDescription::from_reader(io::Cursor::new(bytes))?;
+
+    let preamble = *cdf.preamble();
+    assert_eq!(preamble.constraints, composer.constraints());
+
+    let last = cdf.fetch_constraint(preamble.constraints - 1)?;
+    assert!(last.polynomial().evaluation);
+
+    Ok(())
+}
+
+#[test]
+fn cdf_evaluates_range_and_logic_gates() -> io::Result<()> {
+    let mut composer = Composer::initialized();
+
+    let a = composer.append_witness(BlsScalar::from(0xf0u64));
+    let b = composer.append_witness(BlsScalar::from(0x3cu64));
+    let r = composer.append_witness(BlsScalar::from(0xdead_beefu64));
+
+    composer.component_range::<16>(r);
+    composer.append_logic_and::<4>(a, b);
+    composer.append_logic_xor::<4>(a, b);
+
+    let bytes = composer.cdf_to_vec()?;
+    let mut cdf = CircuitDescription::from_reader(io::Cursor::new(bytes))?;
+
+    for idx in 0..cdf.preamble().constraints {
+        let constraint = cdf.fetch_constraint(idx)?;
+        assert!(constraint.polynomial().evaluation, "constraint {}", idx);
+    }
+
+    // unsatisfied arithmetic gate
+    let constraint = Constraint::new().mult(1).public(-BlsScalar::from(1u64));
+    composer.append_gate(constraint.a(a).b(b));
+
+    let bytes = composer.cdf_to_vec()?;
+    let mut cdf = CircuitDescription::from_reader(io::Cursor::new(bytes))?;
+
+    let last = cdf.fetch_constraint(cdf.preamble().constraints - 1)?;
+    assert!(!last.polynomial().evaluation);
+
+    Ok(())
+}
+
+#[test]
+fn cdf_resolves_repeated_call_sites() -> io::Result<()> {
+    let mut composer = Composer::initialized();
+    let offset = composer.constraints();
+
+    let a = composer.append_witness(BlsScalar::from(3u64));
+    for _ in 0..8 {
+        let constraint =
+            Constraint::new().left(1).constant(-BlsScalar::from(3));
+        composer.append_gate(constraint.a(a));
+    }
+
+    let bytes = composer.cdf_to_vec()?;
+    let mut cdf = CircuitDescription::from_reader(io::Cursor::new(bytes))?;
+
+    let first = cdf.fetch_constraint(offset)?;
+    let (line, col) = (first.line(), first.col());
+    assert!(first.name().ends_with("debugger.rs"));
+
+    for idx in offset + 1..offset + 8 {
+        let constraint = cdf.fetch_constraint(idx)?;
+        assert_eq!((constraint.line(), constraint.col()), (line, col));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn failing_cone_follows_the_wiring() {
+    let mut composer = Composer::initialized();
+    let offset = composer.constraints();
+
+    let a = composer.append_witness(BlsScalar::from(3u64));
+    let b = composer.append_witness(BlsScalar::from(4u64));
+    let c = composer.gate_mul(Constraint::new().mult(1).a(a).b(b));
+
+    // unrelated gate
+    let x = composer.append_witness(BlsScalar::from(5u64));
+    composer.gate_add(Constraint::new().left(1).constant(1).a(x));
+
+    let d = composer.gate_add(Constraint::new().left(1).constant(1).a(c));
+
+    // unsatisfied gate, as d = 13
+    let failing = composer.constraints();
+    let constraint = Constraint::new().left(1).right(-BlsScalar::one());
+    composer.append_gate(constraint.a(d).b(a));
+
+    // the gates assigning the constant zero, a · b and d
+    assert_eq!(
+        composer.failing_cone(failing),
+        vec![0, offset, offset + 2, failing]
+    );
+    assert_eq!(composer.failing_cone(offset), vec![0, offset]);
+    assert!(composer.failing_cone(composer.constraints()).is_empty());
+
+    // a range gadget is included as a whole
+    let r = composer.append_witness(BlsScalar::from(0xbeefu64));
+    let start = composer.constraints();
+    composer.component_range::<8>(r);
+    let end = composer.constraints();
+
+    let failing = composer.constraints();
+    let constraint = Constraint::new().left(1).constant(1);
+    composer.append_gate(constraint.a(r));
+
+    let cone = composer.failing_cone(failing);
+    assert!((start..end).all(|gate| cone.contains(&gate)));
+    assert!(!cone.contains(&offset));
+}
@@ -0,0 +1,52 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::{env, io};
+
+use dusk_cdf::CircuitDescription;
+use dusk_plonk::prelude::*;
+
+#[test]
+fn sampled_cdf_keeps_unsatisfied_constraints() -> io::Result<()> {
+    env::set_var("CDF_SAMPLE_RATE", "4");
+
+    let mut composer = Composer::initialized();
+
+    let a = composer.append_witness(BlsScalar::from(3u64));
+    let b = composer.append_witness(BlsScalar::from(4u64));
+
+    for _ in 0..16 {
+        let constraint = Constraint::new().mult(1).public(-BlsScalar::from(12));
+        composer.append_gate(constraint.a(a).b(b));
+    }
+
+    // unsatisfied gate that isn't sampled
+    while composer.constraints() % 4 == 0 {
+        composer.append_gate(Constraint::new());
+    }
+    let unsatisfied = composer.constraints();
+    let constraint = Constraint::new().mult(1).public(-BlsScalar::from(13));
+    composer.append_gate(constraint.a(a).b(b));
+
+    let bytes = composer.cdf_to_vec()?;
+    let mut cdf = CircuitDescription::from_reader(io::Cursor::new(bytes))?;
+
+    let encoded = cdf.preamble().constraints;
+    let total = composer.constraints();
+    assert_eq!(encoded, (total + 3) / 4 + 1);
+
+    let last = cdf.fetch_constraint(encoded - 1)?;
+    assert_eq!(last.id(), unsatisfied);
+    assert!(!last.polynomial().evaluation);
+
+    for idx in 0..encoded - 1 {
+        let constraint = cdf.fetch_constraint(idx)?;
+        assert_eq!(constraint.id() % 4, 0);
+        assert!(constraint.polynomial().evaluation);
+    }
+
+    Ok(())
+}
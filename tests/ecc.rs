@@ -317,3 +317,107 @@ fn component_mul_point() {
     let circuit = TestCircuit::new(scalar, point, result);
     check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
 }
+
+#[test]
+fn component_msm() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        scalars: [JubJubScalar; 2],
+        points: [JubJubExtended; 2],
+        result: JubJubExtended,
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_scalars = self.scalars.map(|s| composer.append_witness(s));
+            let w_points = self.points.map(|p| composer.append_point(p));
+            let w_result = composer.append_point(self.result);
+
+            let result_circuit = composer.component_msm(&w_scalars, &w_points);
+
+            composer.assert_equal_point(w_result, result_circuit);
+
+            Ok(())
+        }
+    }
+
+    let label = b"component_msm";
+    let mut rng = StdRng::seed_from_u64(0x6d5);
+    let capacity = 1 << 12;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let pi = vec![];
+    let random_point = |rng: &mut StdRng| {
+        dusk_jubjub::GENERATOR_EXTENDED * JubJubScalar::random(rng)
+    };
+
+    // Test: random works
+    let msg = "Circuit with random multi-scalar multiplication should pass";
+    let scalars = [
+        JubJubScalar::random(&mut rng),
+        JubJubScalar::random(&mut rng),
+    ];
+    let points = [random_point(&mut rng), random_point(&mut rng)];
+    let result = points[0] * scalars[0] + points[1] * scalars[1];
+    let circuit = TestCircuit {
+        scalars,
+        points,
+        result,
+    };
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // Test: every value of a window works
+    let msg = "Circuit with every value of a window should pass";
+    let scalars = [JubJubScalar::from(0b11_10_01_00u64), -JubJubScalar::one()];
+    let points = [random_point(&mut rng), random_point(&mut rng)];
+    let result = points[0] * scalars[0] + points[1] * scalars[1];
+    let circuit = TestCircuit {
+        scalars,
+        points,
+        result,
+    };
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // Unsatisfied circuit
+    let msg = "Circuit with a single product as result should not pass";
+    let scalars = [
+        JubJubScalar::random(&mut rng),
+        JubJubScalar::random(&mut rng),
+    ];
+    let points = [random_point(&mut rng), random_point(&mut rng)];
+    let result = points[0] * scalars[0];
+    let circuit = TestCircuit {
+        scalars,
+        points,
+        result,
+    };
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+
+    // the doublings are shared and the points are added once per window, so
+    // the multi-scalar multiplication takes fewer gates than multiplying the
+    // points one by one
+    let mut msm = Composer::initialized();
+    let scalars = [msm.append_witness(BlsScalar::one()); 2];
+    let points = [msm.append_point(dusk_jubjub::GENERATOR_EXTENDED); 2];
+    let offset = msm.constraints();
+    msm.component_msm(&scalars, &points);
+    let msm_gates = msm.constraints() - offset;
+
+    let mut muls = Composer::initialized();
+    let scalar = muls.append_witness(BlsScalar::one());
+    let point = muls.append_point(dusk_jubjub::GENERATOR_EXTENDED);
+    let offset = muls.constraints();
+    let a = muls.component_mul_point(scalar, point);
+    let b = muls.component_mul_point(scalar, point);
+    muls.component_add_point(a, b);
+    let muls_gates = muls.constraints() - offset;
+
+    assert!(msm_gates < muls_gates);
+
+    let empty = Composer::initialized().component_msm(&[], &[]);
+    assert_eq!(empty.x(), Composer::IDENTITY.x());
+    assert_eq!(empty.y(), Composer::IDENTITY.y());
+}
@@ -364,3 +364,107 @@ fn append_logic_xor() {
         &"Sanity check should pass",
     );
 }
+
+#[test]
+fn xor_and_gates() {
+    #[derive(Default)]
+    pub struct TestCircuit<const BITS: usize, const XOR: bool> {
+        a: BlsScalar,
+        b: BlsScalar,
+        result: BlsScalar,
+    }
+
+    impl<const BITS: usize, const XOR: bool> Circuit for TestCircuit<BITS, XOR> {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_result = match XOR {
+                true => composer.xor_gate(w_a, w_b, BITS)?,
+                false => composer.and_gate(w_a, w_b, BITS)?,
+            };
+            composer.assert_equal_constant(w_result, 0, Some(self.result));
+
+            Ok(())
+        }
+    }
+
+    // whether the circuit of the given inputs is satisfied
+    fn satisfied<const BITS: usize, const XOR: bool>(
+        a: u64,
+        b: u64,
+        result: u64,
+    ) -> bool {
+        let circuit = TestCircuit::<BITS, XOR> {
+            a: BlsScalar::from(a),
+            b: BlsScalar::from(b),
+            result: BlsScalar::from(result),
+        };
+        let mut composer = Composer::initialized();
+        circuit.circuit(&mut composer).unwrap();
+        composer.check_satisfied().is_ok()
+    }
+
+    // Test the truth tables of a single bit
+    for (a, b) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+        assert!(satisfied::<1, true>(a, b, a ^ b));
+        assert!(satisfied::<1, false>(a, b, a & b));
+        assert!(!satisfied::<1, true>(a, b, 1 - (a ^ b)));
+        assert!(!satisfied::<1, false>(a, b, 1 - (a & b)));
+    }
+
+    // Test widths that aren't a multiple of the quads
+    for (a, b) in [(0b10110, 0b01101), (0b11111, 0b11111), (0, 0b10001)] {
+        assert!(satisfied::<5, true>(a, b, a ^ b));
+        assert!(satisfied::<5, false>(a, b, a & b));
+        assert!(satisfied::<13, true>(a << 8, b, (a << 8) ^ b));
+        assert!(satisfied::<13, false>(a << 8, b << 8, (a & b) << 8));
+    }
+
+    // Test inputs wider than the bit length are rejected
+    assert!(!satisfied::<5, true>(0b100000, 1, 0b100001));
+    assert!(!satisfied::<5, false>(1, 0b100001, 1));
+    assert!(!satisfied::<8, true>(0x100, 0, 0));
+    assert!(!satisfied::<0, false>(1, 1, 0));
+    assert!(satisfied::<0, false>(0, 0, 0));
+
+    // Test the bit length is bounded
+    let mut composer = Composer::initialized();
+    let w_a = composer.append_witness(BlsScalar::one());
+    assert_eq!(
+        composer.xor_gate(w_a, w_a, Composer::MAX_RANGE_BITS + 1),
+        Err(Error::BitLengthTooLarge {
+            num_bits: Composer::MAX_RANGE_BITS + 1,
+            max: Composer::MAX_RANGE_BITS,
+        })
+    );
+
+    // Test the proofs verify
+    let label = b"xor_and_gates";
+    let mut rng = StdRng::seed_from_u64(0x10c);
+    let pp = PublicParameters::setup(1 << 7, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (a, b) = (0b1011001, 0b0110111);
+
+    let (prover, verifier) =
+        Compiler::compile::<TestCircuit<7, true>>(&pp, label)
+            .expect("Circuit should compile");
+    let msg = "Verification of satisfied circuit should pass";
+    let circuit = TestCircuit::<7, true> {
+        a: BlsScalar::from(a),
+        b: BlsScalar::from(b),
+        result: BlsScalar::from(a ^ b),
+    };
+    let pi = vec![BlsScalar::from(a ^ b)];
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    let (prover, verifier) =
+        Compiler::compile::<TestCircuit<7, false>>(&pp, label)
+            .expect("Circuit should compile");
+    let circuit = TestCircuit::<7, false> {
+        a: BlsScalar::from(a),
+        b: BlsScalar::from(b),
+        result: BlsScalar::from(a & b),
+    };
+    let pi = vec![BlsScalar::from(a & b)];
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+}
@@ -0,0 +1,93 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::{DeserializableSlice, Serializable};
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Default)]
+struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    c: BlsScalar,
+}
+
+impl Circuit for TestCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let constraint =
+            Constraint::new().left(1).right(1).public(-self.c).a(a).b(b);
+        composer.append_gate(constraint);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn proof_evaluations_builder() {
+    let label = b"proof_evaluations_builder";
+    let mut rng = StdRng::seed_from_u64(0xe7a1);
+    let pp = PublicParameters::setup(1 << 6, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let circuit = TestCircuit {
+        a: BlsScalar::from(2),
+        b: BlsScalar::from(3),
+        c: BlsScalar::from(5),
+    };
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+
+    // the evaluations are serialized at the end of the proof
+    let mut bytes = proof.to_bytes();
+    let offset = Proof::SIZE - ProofEvaluations::SIZE;
+    let scalars: Vec<BlsScalar> = bytes[offset..]
+        .chunks_exact(BlsScalar::SIZE)
+        .map(|s| BlsScalar::from_slice(s).expect("valid scalar"))
+        .collect();
+
+    let builder = ProofEvaluations::builder()
+        .a_eval(scalars[0])
+        .b_eval(scalars[1])
+        .c_eval(scalars[2])
+        .d_eval(scalars[3])
+        .a_w_eval(scalars[4])
+        .b_w_eval(scalars[5])
+        .d_w_eval(scalars[6])
+        .q_arith_eval(scalars[7])
+        .q_c_eval(scalars[8])
+        .q_l_eval(scalars[9])
+        .q_r_eval(scalars[10])
+        .s_sigma_1_eval(scalars[11])
+        .s_sigma_2_eval(scalars[12])
+        .s_sigma_3_eval(scalars[13]);
+
+    assert_eq!(
+        builder.clone().build(),
+        Err(Error::MissingProofEvaluation { name: "z_eval" })
+    );
+    assert_eq!(
+        ProofEvaluations::builder().b_eval(scalars[1]).build(),
+        Err(Error::MissingProofEvaluation { name: "a_eval" })
+    );
+
+    // the built evaluations serialize back into a valid proof
+    let evaluations = builder
+        .z_eval(scalars[14])
+        .build()
+        .expect("every evaluation is set");
+    bytes[offset..].copy_from_slice(&evaluations.to_bytes());
+    let proof = Proof::from_bytes(&bytes).expect("valid proof");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verifying should succeed");
+}
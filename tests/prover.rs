@@ -0,0 +1,425 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use dusk_bytes::Serializable;
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Default)]
+struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    c: BlsScalar,
+}
+
+impl Circuit for TestCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let constraint =
+            Constraint::new().left(1).right(1).public(-self.c).a(a).b(b);
+        composer.append_gate(constraint);
+
+        composer.component_range::<4>(a);
+        composer.append_logic_xor::<4>(a, b);
+
+        Ok(())
+    }
+}
+
+impl TestCircuit {
+    /// Circuit satisfied by `2 + 3 = 5`
+    fn valid() -> Self {
+        Self {
+            a: BlsScalar::from(2),
+            b: BlsScalar::from(3),
+            c: BlsScalar::from(5),
+        }
+    }
+}
+
+/// Compile the `TestCircuit` with public parameters drawn from `seed`,
+/// returning the generator to keep proving with
+fn compile(label: &[u8], seed: u64) -> (Prover, Verifier, StdRng) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let pp = PublicParameters::setup(1 << 6, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    (prover, verifier, rng)
+}
+
+#[test]
+fn blinding() {
+    let label = b"blinding";
+    let mut rng = StdRng::seed_from_u64(0xb11d);
+    let pp = PublicParameters::setup(1 << 6, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let circuit = TestCircuit::valid();
+
+    // the default compilation keeps the default hiding degree
+    let (prover, _) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    assert_eq!(prover.hiding_degree(), Prover::HIDING_DEGREE);
+
+    let (prover, _) =
+        Compiler::compile_with_blinding::<TestCircuit>(&pp, label, 0)
+            .expect("Circuit should compile");
+    assert_eq!(prover.hiding_degree(), Prover::HIDING_DEGREE);
+
+    // higher blinding still verifies, also after serialization
+    for hiding_degree in 2..=6 {
+        let (prover, verifier) =
+            Compiler::compile_with_blinding::<TestCircuit>(
+                &pp,
+                label,
+                hiding_degree,
+            )
+            .expect("Circuit should compile");
+        assert_eq!(prover.hiding_degree(), hiding_degree);
+
+        let (proof, public_inputs) = prover
+            .prove(&mut rng, &circuit)
+            .expect("Proving should succeed");
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("Verifying should succeed");
+
+        let prover = Prover::try_from_bytes(prover.to_bytes())
+            .expect("The prover should deserialize");
+        assert_eq!(prover.hiding_degree(), hiding_degree);
+    }
+
+    // the blinding is bound by the size of the public parameters
+    assert!(
+        Compiler::compile_with_blinding::<TestCircuit>(&pp, label, 16).is_err()
+    );
+
+    // and by the size of the circuit
+    assert!(matches!(
+        Compiler::compile_with_blinding::<TestCircuit>(&pp, label, usize::MAX),
+        Err(Error::HidingDegreeTooLarge { .. })
+    ));
+
+    // a prover serialized before the hiding degree was configurable lacks
+    // its tagged trailer, and restores the default
+    let (prover, _) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let mut bytes = prover.to_bytes();
+    assert_eq!(bytes.len(), prover.serialized_size());
+    bytes.truncate(bytes.len() - 10);
+    let prover =
+        Prover::try_from_bytes(&bytes).expect("The prover should deserialize");
+    assert_eq!(prover.hiding_degree(), Prover::HIDING_DEGREE);
+
+    // a hiding degree out of the circuit is rejected when deserializing
+    let mut bytes = prover.to_bytes();
+    let at = bytes.len() - 8;
+    bytes[at..].copy_from_slice(&u64::MAX.to_be_bytes());
+    assert!(matches!(
+        Prover::try_from_bytes(&bytes),
+        Err(Error::HidingDegreeTooLarge { .. })
+    ));
+}
+
+#[test]
+fn deterministic() {
+    let (prover, verifier, _) = compile(b"deterministic", 0xde7);
+
+    let circuit = TestCircuit::valid();
+    let prove = |seed| {
+        prover
+            .prove(&mut StdRng::seed_from_u64(seed), &circuit)
+            .expect("Proving should succeed")
+    };
+
+    // the same seed yields byte-identical proofs
+    let (proof, public_inputs) = prove(0xfeed);
+    let (same, _) = prove(0xfeed);
+    assert_eq!(proof.to_bytes(), same.to_bytes());
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verifying should succeed");
+
+    // while a different seed changes the blinding
+    let (other, _) = prove(0xbeef);
+    assert_ne!(proof.to_bytes(), other.to_bytes());
+    verifier
+        .verify(&other, &public_inputs)
+        .expect("Verifying should succeed");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn deterministic_threads() {
+    let label = b"deterministic_threads";
+    let mut rng = StdRng::seed_from_u64(0x7ead);
+    let pp = PublicParameters::setup(1 << 9, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let circuit = TestCircuit::valid();
+
+    // the blinding scalars are drawn in the same order regardless of how
+    // the parallel work is scheduled
+    let prove = |threads| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("The thread pool should build")
+            .install(|| {
+                prover
+                    .prove(&mut StdRng::seed_from_u64(0xfeed), &circuit)
+                    .expect("Proving should succeed")
+            })
+    };
+
+    let (proof, public_inputs) = prove(1);
+    let (parallel, _) = prove(8);
+    assert_eq!(proof.to_bytes(), parallel.to_bytes());
+    verifier
+        .verify(&parallel, &public_inputs)
+        .expect("Verifying should succeed");
+}
+
+#[test]
+fn insecure_setup() {
+    let label = b"insecure_setup";
+    let pp = PublicParameters::insecure_setup(1 << 6, 0x5eed)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let circuit = TestCircuit::valid();
+    let (proof, public_inputs) = prover
+        .prove(&mut StdRng::seed_from_u64(0x5eed), &circuit)
+        .expect("Proving should succeed");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verifying should succeed");
+
+    // the same seed yields the same keys on every machine
+    let pp = PublicParameters::insecure_setup(1 << 6, 0x5eed)
+        .expect("Creation of public parameter shouldn't fail");
+    let (_, same) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    assert_eq!(verifier.to_bytes(), same.to_bytes());
+}
+
+#[test]
+fn insufficient_srs() {
+    let label = b"insufficient_srs";
+    let mut rng = StdRng::seed_from_u64(0x5a5);
+
+    // public parameters too small for the circuit are rejected on compilation
+    let pp = PublicParameters::setup(1 << 3, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    match Compiler::compile::<TestCircuit>(&pp, label) {
+        Err(Error::InsufficientSRS { needed, available }) => {
+            assert_eq!(available, pp.max_degree());
+            assert!(needed > available);
+        }
+        _ => panic!("Compiling with undersized public parameters should fail"),
+    }
+
+    let pp = PublicParameters::setup(1 << 6, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, _) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // shrink the serialized commit key to its first powers
+    let mut bytes = prover.to_bytes();
+    let len = |at: usize| {
+        u64::from_be_bytes(bytes[at..at + 8].try_into().unwrap()) as usize
+    };
+    let commit_key = 48 + len(0) + len(8);
+    bytes[commit_key..commit_key + 8].copy_from_slice(&4u64.to_le_bytes());
+    let prover =
+        Prover::try_from_bytes(bytes).expect("The prover should deserialize");
+
+    // the prover fails up front instead of deep in a commitment
+    let circuit = TestCircuit::valid();
+    match prover.prove(&mut rng, &circuit) {
+        Err(Error::InsufficientSRS { needed, available }) => {
+            assert_eq!(available, 3);
+            assert!(needed > available);
+        }
+        _ => panic!("Proving with an undersized commit key should fail"),
+    }
+}
+
+#[test]
+fn prove_checked() {
+    let (prover, verifier, mut rng) = compile(b"prove_checked", 0xc4ec);
+
+    // a satisfied circuit is proved as usual
+    let circuit = TestCircuit::valid();
+    let (proof, public_inputs) = prover
+        .prove_checked(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verifying should succeed");
+
+    // a wrong public input breaks the arithmetic gate
+    let circuit = TestCircuit {
+        a: BlsScalar::from(2),
+        b: BlsScalar::from(3),
+        c: BlsScalar::from(6),
+    };
+    assert!(matches!(
+        prover.prove_checked(&mut rng, &circuit),
+        Err(Error::UnsatisfiedConstraint {
+            selector: "arithmetic",
+            ..
+        })
+    ));
+
+    // a witness wider than the 8 bits of the range gadget
+    let circuit = TestCircuit {
+        a: BlsScalar::from(1 << 9),
+        b: BlsScalar::from(3),
+        c: BlsScalar::from((1 << 9) + 3),
+    };
+    let err = prover.prove_checked(&mut rng, &circuit).unwrap_err();
+    let Error::UnsatisfiedConstraint {
+        gate_index,
+        selector,
+    } = err
+    else {
+        panic!("unexpected error {:?}", err);
+    };
+
+    // while the unchecked prover can't produce a valid proof
+    if let Ok((proof, public_inputs)) = prover.prove(&mut rng, &circuit) {
+        assert_eq!(
+            verifier.verify(&proof, &public_inputs),
+            Err(Error::ProofVerificationError)
+        );
+    }
+
+    // the first failing gate is the one the composer reports
+    let mut composer = Composer::initialized();
+    circuit.circuit(&mut composer).unwrap();
+    assert_eq!(
+        composer.check_satisfied(),
+        Err(Error::UnsatisfiedConstraint {
+            gate_index,
+            selector
+        })
+    );
+}
+
+static SATISFIED: AtomicUsize = AtomicUsize::new(0);
+static UNSATISFIED: AtomicUsize = AtomicUsize::new(0);
+
+fn count_gates(event: &RuntimeEvent) {
+    if let RuntimeEvent::GateEvaluated { satisfied, .. } = event {
+        match satisfied {
+            true => SATISFIED.fetch_add(1, Ordering::SeqCst),
+            false => UNSATISFIED.fetch_add(1, Ordering::SeqCst),
+        };
+    }
+}
+
+#[test]
+fn gate_evaluated_events() {
+    let (mut prover, _, mut rng) = compile(b"gate_evaluated_events", 0xe7e);
+    prover.on_event(count_gates);
+
+    // every gate of a satisfied circuit is evaluated
+    let circuit = TestCircuit::valid();
+    prover
+        .prove_checked(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    let mut composer = Composer::initialized();
+    circuit.circuit(&mut composer).unwrap();
+    assert_eq!(SATISFIED.load(Ordering::SeqCst), composer.constraints());
+    assert_eq!(UNSATISFIED.load(Ordering::SeqCst), 0);
+
+    // the check stops at the first unsatisfied gate
+    SATISFIED.store(0, Ordering::SeqCst);
+    let circuit = TestCircuit {
+        a: BlsScalar::from(2),
+        b: BlsScalar::from(3),
+        c: BlsScalar::from(6),
+    };
+    let Err(Error::UnsatisfiedConstraint { gate_index, .. }) =
+        prover.prove_checked(&mut rng, &circuit)
+    else {
+        panic!("the circuit should be unsatisfied");
+    };
+    assert_eq!(SATISFIED.load(Ordering::SeqCst), gate_index);
+    assert_eq!(UNSATISFIED.load(Ordering::SeqCst), 1);
+
+    // no gate is evaluated by the unchecked prover
+    SATISFIED.store(0, Ordering::SeqCst);
+    let circuit = TestCircuit::valid();
+    prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    assert_eq!(SATISFIED.load(Ordering::SeqCst), 0);
+}
+
+#[derive(Clone, Default)]
+struct CountingListener {
+    witnesses: Arc<AtomicUsize>,
+    constraints: Arc<AtomicUsize>,
+    proofs: Arc<AtomicUsize>,
+}
+
+impl RuntimeListener for CountingListener {
+    fn on_event(&mut self, event: &RuntimeEvent) {
+        let counter = match event {
+            RuntimeEvent::WitnessAppended { .. } => &self.witnesses,
+            RuntimeEvent::ConstraintAppended { .. } => &self.constraints,
+            RuntimeEvent::ProofFinished => &self.proofs,
+            _ => return,
+        };
+        counter.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn runtime_listener() {
+    let (mut prover, verifier, mut rng) = compile(b"runtime_listener", 0x115);
+
+    let circuit = TestCircuit::valid();
+
+    // a listener of the composer sees every event from its registration
+    let listener = CountingListener::default();
+    let mut composer = Composer::initialized();
+    let initialized = composer.constraints();
+    composer.add_listener(listener.clone());
+    circuit.circuit(&mut composer).unwrap();
+    let witnesses = listener.witnesses.load(Ordering::SeqCst);
+    let constraints = listener.constraints.load(Ordering::SeqCst);
+    assert!(witnesses >= 2);
+    assert_eq!(constraints, composer.constraints() - initialized);
+
+    // the clones registered to the prover share the counters
+    let listener = CountingListener::default();
+    prover.add_listener(listener.clone());
+    for _ in 0..2 {
+        let (proof, public_inputs) = prover
+            .prove(&mut rng, &circuit)
+            .expect("Proving should succeed");
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("Verifying should succeed");
+    }
+    assert_eq!(listener.proofs.load(Ordering::SeqCst), 2);
+    assert!(listener.witnesses.load(Ordering::SeqCst) > 2 * witnesses);
+    assert!(listener.constraints.load(Ordering::SeqCst) > 2 * constraints);
+}
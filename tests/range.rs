@@ -144,3 +144,65 @@ fn range() {
     let circuit: TestCircuit<BIT_PAIRS_128> = TestCircuit::new(a);
     check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
 }
+
+#[test]
+fn range_gate() {
+    #[derive(Default)]
+    pub struct TestCircuit<const BITS: usize> {
+        a: BlsScalar,
+    }
+
+    impl<const BITS: usize> Circuit for TestCircuit<BITS> {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+
+            composer.range_gate(w_a, BITS)
+        }
+    }
+
+    fn check<const BITS: usize>(pp: &PublicParameters, rng: &mut StdRng) {
+        let label = b"range_gate";
+        let (prover, verifier) =
+            Compiler::compile::<TestCircuit<BITS>>(pp, label)
+                .expect("Circuit should compile");
+        let pi = vec![];
+
+        // Test the highest value in range passes
+        let msg = "Verification of satisfied circuit should pass";
+        let a = BlsScalar::from((1u64 << BITS) - 1);
+        let circuit = TestCircuit::<BITS> { a };
+        check_satisfied_circuit(&prover, &verifier, &pi, &circuit, rng, msg);
+
+        // Test the lowest value out of range fails
+        let circuit = TestCircuit::<BITS> {
+            a: BlsScalar::from(1u64 << BITS),
+        };
+        assert!(prover.prove_checked(rng, &circuit).is_err());
+        if let Ok((proof, public_inputs)) = prover.prove(rng, &circuit) {
+            assert!(verifier.verify(&proof, &public_inputs).is_err());
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(0x5a9e);
+    let pp = PublicParameters::setup(1 << 8, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    check::<0>(&pp, &mut rng);
+    check::<1>(&pp, &mut rng);
+    check::<5>(&pp, &mut rng);
+    check::<8>(&pp, &mut rng);
+    check::<13>(&pp, &mut rng);
+    check::<63>(&pp, &mut rng);
+
+    // Test the bit length is bounded
+    let mut composer = Composer::initialized();
+    let w_a = composer.append_witness(BlsScalar::one());
+    assert_eq!(
+        composer.range_gate(w_a, Composer::MAX_RANGE_BITS + 1),
+        Err(Error::BitLengthTooLarge {
+            num_bits: Composer::MAX_RANGE_BITS + 1,
+            max: Composer::MAX_RANGE_BITS,
+        })
+    );
+    assert!(composer.range_gate(w_a, Composer::MAX_RANGE_BITS).is_ok());
+}
@@ -0,0 +1,111 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::Serializable;
+use dusk_jubjub::GENERATOR_EXTENDED;
+use dusk_plonk::prelude::*;
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+// a^8 = c
+fn octic(wires: &CustomGateWires) -> BlsScalar {
+    wires.a.square().square().square() - wires.c
+}
+
+// Circuit using every widget of the verifier, along with a custom gate of a
+// degree higher than the fixed widgets
+#[derive(Default)]
+struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    scalar: JubJubScalar,
+}
+
+impl Circuit for TestCircuit {
+    const CUSTOM_GATES: &'static [CustomGate] =
+        &[CustomGate::with_degree(octic, 8)];
+
+    fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        // arithmetic and public inputs
+        let constraint = Constraint::new()
+            .mult(1)
+            .left(1)
+            .public(-(self.a * self.b + self.a))
+            .a(a)
+            .b(b);
+        composer.append_gate(constraint);
+
+        // range and logic
+        composer.component_range::<16>(a);
+        let xor = composer.append_logic_xor::<8>(a, b);
+        composer.append_logic_and::<8>(xor, b);
+
+        // fixed and variable base
+        let scalar = composer.append_witness(self.scalar);
+        let point =
+            composer.component_mul_generator(scalar, GENERATOR_EXTENDED)?;
+        composer.component_add_point(point, point);
+
+        // custom gate
+        let octic = composer.append_witness(self.a.pow(&[8, 0, 0, 0]));
+        composer.append_registered_gate(0, Constraint::new().a(a).c(octic));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn reference_verifier() {
+    let label = b"reference_verifier";
+    let mut rng = StdRng::seed_from_u64(0x4ef);
+    let pp = PublicParameters::setup(1 << 14, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    for _ in 0..3 {
+        let circuit = TestCircuit {
+            a: BlsScalar::from(rng.next_u32() as u64 & 0xffff),
+            b: BlsScalar::from(rng.next_u32() as u64 & 0xffff),
+            scalar: JubJubScalar::random(&mut rng),
+        };
+        let (proof, public_inputs) = prover
+            .prove(&mut rng, &circuit)
+            .expect("Proving should succeed");
+
+        // both verifiers accept a valid proof
+        assert_eq!(verifier.verify(&proof, &public_inputs), Ok(()));
+        assert_eq!(verifier.verify_reference(&proof, &public_inputs), Ok(()));
+
+        // and reject it with wrong public inputs
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs[0] += BlsScalar::one();
+        assert!(verifier.verify(&proof, &wrong_inputs).is_err());
+        assert!(verifier.verify_reference(&proof, &wrong_inputs).is_err());
+
+        // as well as a proof with any of its evaluations tampered with
+        let bytes = proof.to_bytes();
+        let evaluations = Proof::SIZE - 15 * BlsScalar::SIZE;
+        for i in 0..15 {
+            let mut tampered = bytes;
+            let offset = evaluations + i * BlsScalar::SIZE;
+            tampered[offset..offset + BlsScalar::SIZE]
+                .copy_from_slice(&BlsScalar::random(&mut rng).to_bytes());
+            let tampered =
+                Proof::from_bytes(&tampered).expect("Proof should deserialize");
+
+            let fast = verifier.verify(&tampered, &public_inputs);
+            let reference =
+                verifier.verify_reference(&tampered, &public_inputs);
+            assert!(fast.is_err(), "evaluation {i} should be rejected");
+            assert_eq!(fast, reference, "evaluation {i}");
+        }
+    }
+}
@@ -185,6 +185,98 @@ fn component_select() {
     check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
 }
 
+#[test]
+fn conditional_select() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        cond: BlsScalar,
+        a: BlsScalar,
+        b: BlsScalar,
+        result: BlsScalar,
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let w_cond = composer.append_witness(self.cond);
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_result = composer.append_witness(self.result);
+
+            let result_circuit = composer.conditional_select(w_cond, w_a, w_b);
+
+            composer.assert_equal(w_result, result_circuit);
+
+            Ok(())
+        }
+    }
+
+    let label = b"conditional_select";
+    let mut rng = StdRng::seed_from_u64(0x5e1);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let pi = vec![];
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+
+    // Test both branches
+    let msg = "Circuit with cond = 1 that selects a should pass";
+    let cond = BlsScalar::one();
+    let circuit = TestCircuit {
+        cond,
+        a,
+        b,
+        result: a,
+    };
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    let msg = "Circuit with cond = 0 that selects b should pass";
+    let cond = BlsScalar::zero();
+    let circuit = TestCircuit {
+        cond,
+        a,
+        b,
+        result: b,
+    };
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // Test the wrong branches fail
+    let msg = "Circuit with cond = 1 that selects b shouldn't pass";
+    let cond = BlsScalar::one();
+    let circuit = TestCircuit {
+        cond,
+        a,
+        b,
+        result: b,
+    };
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+
+    let msg = "Circuit with cond = 0 that selects a shouldn't pass";
+    let cond = BlsScalar::zero();
+    let circuit = TestCircuit {
+        cond,
+        a,
+        b,
+        result: a,
+    };
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+
+    // Test an unconstrained cond passes with an affine combination of the
+    // inputs (cond should be constrained outside of the gadget)
+    let msg = "Circuit with a non-boolean cond passes";
+    let cond = BlsScalar::from(2u64);
+    let circuit = TestCircuit {
+        cond,
+        a,
+        b,
+        result: b + cond * (a - b),
+    };
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+}
+
 #[test]
 fn component_select_one() {
     pub struct TestCircuit {
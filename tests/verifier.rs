@@ -0,0 +1,1338 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::cell::RefCell;
+use std::num::NonZeroUsize;
+
+use dusk_bls12_381::G1Affine;
+use dusk_bytes::Serializable;
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+#[derive(Default)]
+struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    c: BlsScalar,
+}
+
+impl Circuit for TestCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let constraint =
+            Constraint::new().left(1).right(1).public(-self.c).a(a).b(b);
+        composer.append_gate(constraint);
+
+        Ok(())
+    }
+}
+
+impl TestCircuit {
+    /// Circuit satisfied by `2 + 3 = 5`
+    fn valid() -> Self {
+        Self {
+            a: BlsScalar::from(2),
+            b: BlsScalar::from(3),
+            c: BlsScalar::from(5),
+        }
+    }
+}
+
+/// Compile the `TestCircuit` with public parameters drawn from `seed`,
+/// returning the generator to keep proving with
+fn compile(label: &[u8], seed: u64) -> (Prover, Verifier, StdRng) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let pp = PublicParameters::setup(1 << 4, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    (prover, verifier, rng)
+}
+
+/// Compile the `TestCircuit` and prove its valid assignment
+fn setup(label: &[u8], seed: u64) -> (Prover, Verifier, Proof, Vec<BlsScalar>) {
+    let (prover, verifier, mut rng) = compile(label, seed);
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &TestCircuit::valid())
+        .expect("Proving should succeed");
+
+    (prover, verifier, proof, public_inputs)
+}
+
+#[test]
+fn verify_with_report() {
+    let (_, verifier, proof, public_inputs) =
+        setup(b"verify_with_report", 0xbeef);
+
+    // a valid proof produces a report without failures
+    let report = verifier.verify_with_report(&proof, &public_inputs);
+    assert!(report.is_valid());
+    assert_eq!(report.failure(), None);
+    assert_eq!(report.result(), Ok(()));
+    assert!(report.challenges().is_some());
+    assert!(report.pi_eval().is_some());
+    assert!(report.r_0_eval().is_some());
+
+    // a wrong public input is only caught by the pairing check
+    let wrong_inputs = [BlsScalar::from(6)];
+    let report = verifier.verify_with_report(&proof, &wrong_inputs);
+    assert_eq!(report.failure(), Some(&VerificationFailure::Pairing));
+    assert_eq!(report.result(), Err(Error::ProofVerificationError));
+
+    // the transcript diverges when the public inputs change
+    let valid = verifier.verify_with_report(&proof, &public_inputs);
+    assert_ne!(
+        valid.challenges().map(|c| c.z),
+        report.challenges().map(|c| c.z)
+    );
+
+    // a wrong number of public inputs fails before deriving any challenge
+    let report = verifier.verify_with_report(&proof, &[]);
+    assert_eq!(
+        report.failure(),
+        Some(&VerificationFailure::Setup(
+            Error::InconsistentPublicInputsLen {
+                expected: 1,
+                provided: 0,
+            }
+        ))
+    );
+    assert!(report.challenges().is_none());
+    assert_eq!(report.result(), verifier.verify(&proof, &[]));
+}
+
+#[test]
+fn batch_verify() {
+    let (prover, verifier, mut rng) = compile(b"batch_verify", 0xba7c);
+
+    let mut proofs = Vec::new();
+    let mut public_inputs = Vec::new();
+    for a in 0..4u64 {
+        let circuit = TestCircuit {
+            a: BlsScalar::from(a),
+            b: BlsScalar::from(3),
+            c: BlsScalar::from(a + 3),
+        };
+        let (proof, pi) = prover
+            .prove(&mut rng, &circuit)
+            .expect("Proving should succeed");
+        proofs.push(proof);
+        public_inputs.push(pi);
+    }
+    let pi_slices: Vec<&[BlsScalar]> =
+        public_inputs.iter().map(Vec::as_slice).collect();
+
+    // a batch of valid proofs passes
+    assert_eq!(verifier.batch_verify(&proofs, &pi_slices), Ok(()));
+
+    // an empty batch passes
+    assert_eq!(verifier.batch_verify(&[], &[]), Ok(()));
+
+    // a single wrong public input fails the whole batch
+    let wrong_pi = [BlsScalar::from(42)];
+    let mut wrong_slices = pi_slices.clone();
+    wrong_slices[2] = &wrong_pi;
+    assert_eq!(
+        verifier.batch_verify(&proofs, &wrong_slices),
+        Err(Error::ProofVerificationError)
+    );
+
+    // swapping two proofs fails the batch
+    let mut swapped = proofs.clone();
+    swapped.swap(0, 1);
+    assert_eq!(
+        verifier.batch_verify(&swapped, &pi_slices),
+        Err(Error::ProofVerificationError)
+    );
+
+    // mismatched lengths are rejected early
+    assert_eq!(
+        verifier.batch_verify(&proofs, &pi_slices[..3]),
+        Err(Error::InconsistentBatchLen {
+            expected: 4,
+            provided: 3,
+        })
+    );
+}
+
+#[test]
+fn opening_points() {
+    let (_, verifier, proof, public_inputs) = setup(b"opening_points", 0x0be);
+
+    // the witnesses are the last two commitments of the proof
+    let (w_z, w_zw) = proof.opening_witnesses();
+    let commitments: Vec<_> = proof.commitments().collect();
+    assert_eq!(commitments[9], ("w_z_chall_comm", &w_z));
+    assert_eq!(commitments[10], ("w_z_chall_w_comm", &w_zw));
+
+    // opened at the evaluation challenge and its shift by a root of unity
+    let (z, z_w) = verifier
+        .opening_points(&proof, &public_inputs)
+        .expect("The public inputs should match");
+    let challenges = verifier
+        .derive_challenges(&proof, &public_inputs)
+        .expect("The public inputs should match");
+    assert_eq!(z, challenges.z);
+
+    let omega = z_w * z.invert().unwrap();
+    assert_ne!(omega, BlsScalar::one());
+    assert_eq!(omega.pow(&[1 << 10, 0, 0, 0]), BlsScalar::one());
+
+    assert!(verifier.opening_points(&proof, &[]).is_err());
+}
+
+#[test]
+fn circuit_hash() {
+    #[derive(Default)]
+    struct OtherCircuit {
+        a: BlsScalar,
+    }
+
+    impl Circuit for OtherCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let a = composer.append_witness(self.a);
+            composer.assert_equal_constant(a, 0, Some(self.a));
+
+            Ok(())
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(0xa54);
+    let pp = PublicParameters::setup(1 << 4, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (_, verifier) = Compiler::compile::<TestCircuit>(&pp, b"circuit_hash")
+        .expect("Circuit should compile");
+    let hash = verifier.circuit_hash();
+
+    // the hash doesn't depend on the label of the circuit
+    let (_, other_label) = Compiler::compile::<TestCircuit>(&pp, b"other")
+        .expect("Circuit should compile");
+    assert_eq!(other_label.circuit_hash(), hash);
+
+    // nor on the encoding the verifier was loaded from
+    let loaded = Verifier::try_from_bytes(verifier.to_bytes())
+        .expect("The verifier should deserialize");
+    assert_eq!(loaded.circuit_hash(), hash);
+    let loaded =
+        Verifier::try_from_versioned_bytes(verifier.to_versioned_bytes())
+            .expect("The verifier should deserialize");
+    assert_eq!(loaded.circuit_hash(), hash);
+
+    // while different circuits have different hashes
+    let (_, other) = Compiler::compile::<OtherCircuit>(&pp, b"circuit_hash")
+        .expect("Circuit should compile");
+    assert_ne!(other.circuit_hash(), hash);
+}
+
+#[test]
+fn key_substitution() {
+    // same shape as the test circuit, but `a + 2b = c`
+    #[derive(Default)]
+    struct SubstitutedCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        c: BlsScalar,
+    }
+
+    impl Circuit for SubstitutedCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let a = composer.append_witness(self.a);
+            let b = composer.append_witness(self.b);
+
+            let constraint =
+                Constraint::new().left(1).right(2).public(-self.c).a(a).b(b);
+            composer.append_gate(constraint);
+
+            Ok(())
+        }
+    }
+
+    let label = b"key_substitution";
+    let mut rng = StdRng::seed_from_u64(0x5b5);
+    let pp = PublicParameters::setup(1 << 4, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let (_, substituted) = Compiler::compile::<SubstitutedCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    assert_ne!(verifier.circuit_hash(), substituted.circuit_hash());
+
+    let circuit = TestCircuit::valid();
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verifying should succeed");
+
+    // the circuit hash is absorbed before any commitment, so the key of
+    // another circuit derives other challenges from the first round
+    let challenges = verifier
+        .derive_challenges(&proof, &public_inputs)
+        .expect("Challenges should be derived");
+    let substituted_challenges = substituted
+        .derive_challenges(&proof, &public_inputs)
+        .expect("Challenges should be derived");
+    assert_ne!(challenges.beta, substituted_challenges.beta);
+
+    assert_eq!(
+        substituted.verify(&proof, &public_inputs),
+        Err(Error::ProofVerificationError)
+    );
+}
+
+#[test]
+fn evaluation_transcript() {
+    let (prover, verifier, mut rng) = compile(b"evaluation_transcript", 0x2e5);
+
+    let circuit = TestCircuit::valid();
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    let challenges = verifier
+        .derive_challenges(&proof, &public_inputs)
+        .expect("Challenges should be derived");
+
+    // the transcript resumed from the exported state draws the evaluation
+    // challenge of the verifier
+    let transcript = verifier
+        .evaluation_transcript(&proof, &public_inputs)
+        .expect("The transcript should be replayed");
+    let state = transcript.to_bytes();
+    let mut resumed = ResumableTranscript::from_bytes(&state)
+        .expect("The transcript state should deserialize");
+    assert_eq!(resumed.challenge_scalar(b"z_challenge"), challenges.z);
+
+    // proofs generated with the resumable transcript match the default one
+    let (proof, public_inputs) = prover
+        .prove_with_transcript::<ResumableTranscript, _, _>(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verifying should succeed");
+}
+
+#[test]
+fn derive_challenges() {
+    let (_, verifier, proof, public_inputs) =
+        setup(b"derive_challenges", 0xc4a1);
+
+    // the challenges are the same the verifier derives
+    let challenges = verifier
+        .derive_challenges(&proof, &public_inputs)
+        .expect("Challenges should be derived");
+    let report = verifier.verify_with_report(&proof, &public_inputs);
+    assert_eq!(report.challenges(), Some(&challenges));
+
+    // the derivation is deterministic
+    assert_eq!(
+        verifier.derive_challenges(&proof, &public_inputs),
+        Ok(challenges)
+    );
+
+    // every challenge depends on the public inputs
+    let other = verifier
+        .derive_challenges(&proof, &[BlsScalar::from(6)])
+        .expect("Challenges should be derived");
+    assert_ne!(other.beta, challenges.beta);
+    assert_ne!(other.u, challenges.u);
+
+    assert_eq!(
+        verifier.derive_challenges(&proof, &[]),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 1,
+            provided: 0,
+        })
+    );
+}
+
+#[test]
+fn verify_compact() {
+    let (_, verifier, proof, public_inputs) = setup(b"verify_compact", 0xcafe);
+
+    let compact = verifier.to_compact_bytes();
+    assert!(compact.len() < verifier.to_bytes().len());
+
+    proof
+        .verify_compact(&compact, &public_inputs)
+        .expect("Verifying with the compact verifier should succeed");
+    assert_eq!(
+        proof.verify_compact(&compact, &[BlsScalar::from(6)]),
+        Err(Error::ProofVerificationError)
+    );
+
+    let restored = Verifier::try_from_compact_bytes(&compact)
+        .expect("The compact verifier should deserialize");
+    assert_eq!(restored.to_bytes(), verifier.to_bytes());
+
+    assert_eq!(
+        proof.verify_compact(&compact[..compact.len() - 1], &public_inputs),
+        Err(Error::NotEnoughBytes)
+    );
+}
+
+#[test]
+fn compact_bytes_malformed() {
+    let label = b"compact_bytes_malformed";
+    let (_, verifier, mut rng) = compile(label, 0xf022);
+
+    let compact = verifier.to_compact_bytes();
+
+    // every truncation is rejected without panicking, but the ones dropping
+    // the trailing quotient chunks and custom gates count, that are decoded
+    // as the layouts preceding them
+    let legacy = [compact.len() - 16, compact.len() - 8];
+    for len in 0..compact.len() {
+        let restored = Verifier::try_from_compact_bytes(&compact[..len]);
+        assert_eq!(restored.is_ok(), legacy.contains(&len));
+    }
+
+    // the decoded lengths of the label and the public inputs can't overflow
+    // when checked against the remaining bytes
+    for (offset, len) in [
+        (8, u64::MAX),
+        (8, u64::MAX - 7),
+        (16, u64::MAX),
+        (16, u64::MAX / 8 + 1),
+        (16, 1 << 61),
+    ] {
+        let mut bytes = compact.clone();
+        bytes[offset..offset + 8].copy_from_slice(&len.to_be_bytes());
+        assert_eq!(
+            Verifier::try_from_compact_bytes(&bytes).err(),
+            Some(Error::NotEnoughBytes)
+        );
+    }
+
+    let mut bytes = compact.clone();
+    bytes[..8].copy_from_slice(&u64::MAX.to_be_bytes());
+    assert_eq!(
+        Verifier::try_from_compact_bytes(&bytes).err(),
+        Some(Error::CircuitTooLarge {
+            requested: usize::MAX,
+            max_supported: EvaluationDomain::MAX_SIZE,
+        })
+    );
+
+    // random length prefixes and trailing bytes are rejected or decoded,
+    // but never panic
+    for _ in 0..256 {
+        let mut bytes = compact.clone();
+        let offset = 8 * (rng.next_u32() as usize % 3);
+        bytes[offset..offset + 8]
+            .copy_from_slice(&rng.next_u64().to_be_bytes());
+        let mut tail = [0u8; 16];
+        rng.fill_bytes(&mut tail);
+        bytes.extend(&tail[..rng.next_u32() as usize % 16]);
+        let _ = Verifier::try_from_compact_bytes(&bytes);
+    }
+}
+
+#[test]
+fn verifier_versioned_bytes() {
+    let (_, verifier, proof, public_inputs) =
+        setup(b"verifier_versioned_bytes", 0xcafe);
+
+    let bytes = verifier.to_versioned_bytes();
+    assert_eq!(
+        bytes.len(),
+        Verifier::HEADER_SIZE + verifier.serialized_size()
+    );
+
+    let restored = Verifier::try_from_versioned_bytes(&bytes)
+        .expect("The versioned verifier should deserialize");
+    restored
+        .verify(&proof, &public_inputs)
+        .expect("Verifying should succeed");
+
+    let mut other = bytes.clone();
+    other[Verifier::HEADER_SIZE - 1] ^= 0xff;
+    assert_eq!(
+        Verifier::try_from_versioned_bytes(&other).err(),
+        Some(Error::IncompatibleKeyVersion {
+            found: Verifier::VERSION ^ 0xff,
+            expected: Verifier::VERSION,
+        })
+    );
+
+    assert_eq!(
+        Verifier::try_from_versioned_bytes(verifier.to_bytes()).err(),
+        Some(Error::InvalidKeyHeader)
+    );
+    assert_eq!(
+        Verifier::try_from_versioned_bytes(&bytes[..3]).err(),
+        Some(Error::NotEnoughBytes)
+    );
+}
+
+#[test]
+fn verifier_rejects_invalid_domain() {
+    let label = b"verifier_rejects_invalid_domain";
+    let (_, verifier, _) = compile(label, 0xcafe);
+
+    // the evaluation domain is built once along with the verifier, so a
+    // circuit size out of the two-adicity of the field is rejected upfront
+    let mut compact = verifier.to_compact_bytes();
+    compact[..8].copy_from_slice(&(1u64 << 40).to_be_bytes());
+
+    let too_large = Some(Error::CircuitTooLarge {
+        requested: 1 << 40,
+        max_supported: EvaluationDomain::MAX_SIZE,
+    });
+    assert_eq!(Verifier::try_from_compact_bytes(&compact).err(), too_large);
+
+    // the size of the verifier key follows the six length prefixes and the
+    // label of the full serialization
+    let mut bytes = verifier.to_bytes();
+    let offset = 48 + label.len();
+    bytes[offset..offset + 8].copy_from_slice(&(1u64 << 40).to_le_bytes());
+
+    assert_eq!(Verifier::try_from_bytes(&bytes).err(), too_large);
+}
+
+#[test]
+fn verify_domain_separated() {
+    let (prover, verifier, mut rng) =
+        compile(b"verify_domain_separated", 0xd57);
+
+    let circuit = TestCircuit::valid();
+    let (proof, public_inputs) = prover
+        .prove_domain_separated(&mut rng, &circuit, b"application-a")
+        .expect("Proving should succeed");
+
+    // the proof is only valid for the separator it was created with
+    verifier
+        .verify_domain_separated(&proof, &public_inputs, b"application-a")
+        .expect("Verifying with the same separator should succeed");
+    assert_eq!(
+        verifier.verify_domain_separated(
+            &proof,
+            &public_inputs,
+            b"application-b"
+        ),
+        Err(Error::ProofVerificationError)
+    );
+    assert_eq!(
+        verifier.verify(&proof, &public_inputs),
+        Err(Error::ProofVerificationError)
+    );
+
+    // and a proof without separator is rejected by any separator
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verifying without separator should succeed");
+    assert_eq!(
+        verifier.verify_domain_separated(&proof, &public_inputs, b""),
+        Err(Error::ProofVerificationError)
+    );
+}
+
+#[test]
+fn multi_proof_aggregation() {
+    let mut rng = StdRng::seed_from_u64(0xa99);
+    let pp = PublicParameters::setup(1 << 4, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover_a, verifier_a) =
+        Compiler::compile::<TestCircuit>(&pp, b"aggregation_a")
+            .expect("Circuit should compile");
+    let (prover_b, verifier_b) =
+        Compiler::compile::<TestCircuit>(&pp, b"aggregation_b")
+            .expect("Circuit should compile");
+
+    let mut aggregator = MultiProofAggregator::new(&verifier_a);
+    assert!(aggregator.is_empty());
+    assert_eq!(aggregator.verify(), Ok(()));
+
+    // proofs of different verifiers sharing the same parameters aggregate
+    for a in 0..3u64 {
+        let circuit = TestCircuit {
+            a: BlsScalar::from(a),
+            b: BlsScalar::from(3),
+            c: BlsScalar::from(a + 3),
+        };
+        let (proof, pi) = prover_a
+            .prove(&mut rng, &circuit)
+            .expect("Proving should succeed");
+        aggregator
+            .push(&verifier_a, &proof, &pi)
+            .expect("The proof should be aggregated");
+
+        let (proof, pi) = prover_b
+            .prove(&mut rng, &circuit)
+            .expect("Proving should succeed");
+        aggregator
+            .push(&verifier_b, &proof, &pi)
+            .expect("The proof should be aggregated");
+    }
+    assert_eq!(aggregator.len(), 6);
+    assert_eq!(aggregator.verify(), Ok(()));
+
+    // a single invalid proof fails the whole aggregation
+    let circuit = TestCircuit::valid();
+    let (proof, _) = prover_a
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    let mut invalid = aggregator.clone();
+    invalid
+        .push(&verifier_a, &proof, &[BlsScalar::from(6)])
+        .expect("The proof should be aggregated");
+    assert_eq!(invalid.verify(), Err(Error::ProofVerificationError));
+    assert_eq!(aggregator.verify_indexed(), Ok(()));
+    assert_eq!(
+        invalid.verify_indexed(),
+        Err(Error::InvalidBatchItem { index: 6 })
+    );
+
+    // a proof checked by a verifier of other parameters is rejected
+    let other_pp = PublicParameters::setup(1 << 4, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (other_prover, other_verifier) =
+        Compiler::compile::<TestCircuit>(&other_pp, b"aggregation_a")
+            .expect("Circuit should compile");
+    let (proof, pi) = other_prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    assert_eq!(
+        aggregator.push(&other_verifier, &proof, &pi),
+        Err(Error::InconsistentOpeningKey)
+    );
+    assert_eq!(
+        aggregator.push(&verifier_a, &proof, &[]),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 1,
+            provided: 0,
+        })
+    );
+    assert_eq!(aggregator.len(), 6);
+}
+
+#[test]
+fn verify_with_ref() {
+    let label = b"verify_with_ref";
+    let (_, verifier, proof, public_inputs) = setup(label, 0x4ef);
+
+    let bytes = verifier.to_bytes();
+    let verifier_ref = VerifierKeyRef::try_from_bytes(&bytes)
+        .expect("The view should be created");
+    assert_eq!(verifier_ref.public_inputs_len(), 1);
+
+    proof
+        .verify_with_ref(&verifier_ref, &public_inputs)
+        .expect("Verifying with the view should succeed");
+    assert_eq!(
+        proof.verify_with_ref(&verifier_ref, &[BlsScalar::from(6)]),
+        Err(Error::ProofVerificationError)
+    );
+    assert_eq!(
+        proof.verify_with_ref(&verifier_ref, &[public_inputs[0]; 2]),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 1,
+            provided: 2,
+        })
+    );
+
+    // a truncated buffer is rejected when the view is created
+    assert_eq!(
+        VerifierKeyRef::try_from_bytes(&bytes[..bytes.len() - 1]),
+        Err(Error::NotEnoughBytes)
+    );
+
+    // as are section lengths overflowing once added up
+    for at in [0, 24] {
+        let mut overflowing = bytes.clone();
+        overflowing[at..at + 8].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert_eq!(
+            VerifierKeyRef::try_from_bytes(&overflowing),
+            Err(Error::NotEnoughBytes)
+        );
+    }
+
+    // while invalid points are only caught when verifying
+    let mut corrupted = bytes.clone();
+    corrupted[48 + label.len()] ^= 0xff;
+    let corrupted_ref = VerifierKeyRef::try_from_bytes(&corrupted)
+        .expect("The view should be created");
+    assert!(proof
+        .verify_with_ref(&corrupted_ref, &public_inputs)
+        .is_err());
+}
+
+#[test]
+fn msm_batch_size() {
+    let (_, verifier, proof, public_inputs) = setup(b"msm_batch_size", 0x3b5);
+
+    // the batched commitments are accumulated into the same linearization
+    // commitment, whatever the size of the batches
+    for batch in [1, 2, 5, 64] {
+        let batch = NonZeroUsize::new(batch).expect("non-zero batch");
+        let verifier = Verifier::try_from_bytes(&verifier.to_bytes())
+            .expect("The verifier should deserialize")
+            .with_msm_batch_size(batch);
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("Verifying with batched MSMs should succeed");
+        assert_eq!(
+            verifier.verify(&proof, &[BlsScalar::from(6)]),
+            Err(Error::ProofVerificationError)
+        );
+    }
+}
+
+#[test]
+fn verify_in_context() {
+    let (prover, verifier, mut rng) = compile(b"verify_in_context", 0xc07e);
+
+    let circuit = TestCircuit::valid();
+
+    let context = |session: &[u8]| {
+        let mut transcript = ContextTranscript::new(b"protocol");
+        transcript.append_message(b"session", session);
+        transcript
+    };
+
+    let mut prover_transcript = context(b"session 1");
+    let (proof, public_inputs) = prover
+        .prove_in_context(&mut rng, &circuit, &mut prover_transcript)
+        .expect("Proving should succeed");
+
+    let mut verifier_transcript = context(b"session 1");
+    verifier
+        .verify_in_context(&proof, &public_inputs, &mut verifier_transcript)
+        .expect("Verifying in the same context should succeed");
+
+    // the protocol continues from the same state on both sides
+    assert_eq!(
+        prover_transcript.challenge_scalar(b"next"),
+        verifier_transcript.challenge_scalar(b"next")
+    );
+
+    // the proof is bound to its context
+    assert_eq!(
+        verifier.verify_in_context(
+            &proof,
+            &public_inputs,
+            &mut context(b"session 2")
+        ),
+        Err(Error::ProofVerificationError)
+    );
+    assert_eq!(
+        verifier.verify(&proof, &public_inputs),
+        Err(Error::ProofVerificationError)
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "seeded with the context of the protocol")]
+fn verify_in_context_unseeded() {
+    let (_, verifier, proof, public_inputs) =
+        setup(b"verify_in_context", 0xc07f);
+
+    let mut transcript = ContextTranscript::new(b"protocol");
+    let _ = verifier.verify_in_context(&proof, &public_inputs, &mut transcript);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "seeded with the context of the protocol")]
+fn prove_in_context_unseeded() {
+    let (prover, _, mut rng) = compile(b"prove_in_context", 0xc080);
+
+    let circuit = TestCircuit::valid();
+
+    let mut transcript = ContextTranscript::new(b"protocol");
+    let _ = prover.prove_in_context(&mut rng, &circuit, &mut transcript);
+}
+
+#[test]
+fn public_inputs_len() {
+    let (_, verifier, proof, public_inputs) =
+        setup(b"public_inputs_len", 0x91e);
+
+    // under-supplied public inputs
+    assert_eq!(
+        verifier.verify(&proof, &[]),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 1,
+            provided: 0,
+        })
+    );
+
+    // over-supplied public inputs, even if the expected one is correct
+    let over = [public_inputs[0], BlsScalar::one()];
+    assert_eq!(
+        verifier.verify(&proof, &over),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 1,
+            provided: 2,
+        })
+    );
+    assert_eq!(
+        verifier.batch_verify(&[proof], &[&over]),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 1,
+            provided: 2,
+        })
+    );
+
+    // a verifier declaring a public input out of its domain is rejected
+    // the last public input is followed by the number of custom gates and
+    // of quotient chunks
+    let mut compact = verifier.to_compact_bytes();
+    let at = compact.len() - 24;
+    compact[at..at + 8].copy_from_slice(&(1u64 << 20).to_be_bytes());
+    assert!(matches!(
+        Verifier::try_from_compact_bytes(&compact),
+        Err(Error::PublicInputOutOfDomain {
+            index: 0x100000,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn verifier_display() {
+    let (_, verifier, _) = compile(b"verifier_display", 0xd15);
+
+    assert_eq!(verifier.public_inputs_len(), 1);
+    assert_eq!(verifier.custom_gates_len(), 0);
+    assert_eq!(verifier.size(), verifier.constraints().next_power_of_two());
+
+    // the summary survives the serialization of the verifier
+    let verifier = Verifier::try_from_bytes(verifier.to_bytes())
+        .expect("Verifier should deserialize");
+    assert_eq!(
+        verifier.to_string(),
+        format!(
+            "Verifier {{ size: {}, constraints: {}, public inputs: 1, custom gates: 0 }}",
+            verifier.size(),
+            verifier.constraints()
+        )
+    );
+}
+
+#[test]
+fn verify_public_inputs() {
+    #[derive(Default)]
+    struct SparseCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+    }
+
+    impl Circuit for SparseCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let a = composer.append_public(self.a);
+            let mut c = a;
+            for _ in 0..20 {
+                c = composer.gate_add(Constraint::new().left(1).a(c));
+            }
+            let b = composer.append_public(self.b);
+            composer.assert_equal(c, b);
+
+            Ok(())
+        }
+    }
+
+    let label = b"verify_public_inputs";
+    let mut rng = StdRng::seed_from_u64(0x905);
+    let pp = PublicParameters::setup(1 << 6, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<SparseCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let circuit = SparseCircuit {
+        a: BlsScalar::from(7),
+        b: BlsScalar::from(7),
+    };
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+
+    // the public inputs are declared at sparse gates
+    let indexes = verifier.public_input_indexes().to_vec();
+    assert_eq!(indexes.len(), 2);
+    assert!(indexes[1] - indexes[0] > 1);
+
+    let positional = verifier
+        .public_inputs(&public_inputs)
+        .expect("The public inputs should be placed");
+    assert_eq!(positional.indexes().collect::<Vec<_>>(), indexes);
+    assert!(positional.values().eq(public_inputs.iter()));
+    verifier
+        .verify_public_inputs(&proof, &positional)
+        .expect("Verifying with positional public inputs should succeed");
+
+    // the same values at other positions don't verify
+    let shifted: PublicInputs = positional
+        .iter()
+        .map(|(index, value)| (index + 1, *value))
+        .collect();
+    assert_eq!(
+        verifier.verify_public_inputs(&proof, &shifted),
+        Err(Error::PublicInputNotFound { index: indexes[0] })
+    );
+
+    let mut wrong = positional.clone();
+    wrong.insert(indexes[1], BlsScalar::from(8));
+    assert_eq!(
+        verifier.verify_public_inputs(&proof, &wrong),
+        Err(Error::ProofVerificationError)
+    );
+
+    let mut extra = positional.clone();
+    extra.insert(indexes[1] + 1, BlsScalar::one());
+    assert_eq!(
+        verifier.verify_public_inputs(&proof, &extra),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 2,
+            provided: 3,
+        })
+    );
+}
+
+#[test]
+fn verify_with_constants() {
+    const CONSTANT: u64 = 42;
+
+    #[derive(Default)]
+    struct ConstantCircuit {
+        x: BlsScalar,
+        y: BlsScalar,
+    }
+
+    impl Circuit for ConstantCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let x = composer.append_public(self.x);
+            let k = composer.append_public(BlsScalar::from(CONSTANT));
+            let y = composer.append_public(self.y);
+
+            let sum =
+                composer.gate_add(Constraint::new().left(1).right(1).a(x).b(k));
+            composer.assert_equal(sum, y);
+
+            Ok(())
+        }
+    }
+
+    let label = b"verify_with_constants";
+    let mut rng = StdRng::seed_from_u64(0xc0c);
+    let pp = PublicParameters::setup(1 << 5, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<ConstantCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let circuit = ConstantCircuit {
+        x: BlsScalar::from(3),
+        y: BlsScalar::from(CONSTANT + 3),
+    };
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    assert_eq!(public_inputs.len(), 3);
+
+    // the second public input is fixed by the circuit
+    let indexes = verifier.public_input_indexes().to_vec();
+    let mut fixed = PublicInputs::new();
+    fixed.insert(indexes[1], public_inputs[1]);
+    let constants = verifier
+        .constant_public_inputs(fixed)
+        .expect("The constant should be declared by the circuit");
+    assert_eq!(constants.inputs().len(), 1);
+
+    let variable = [public_inputs[0], public_inputs[2]];
+    verifier
+        .verify_with_constants(&proof, &constants, &variable)
+        .expect("Verifying with constant public inputs should succeed");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("The same proof should verify with every public input");
+
+    // the constants are bound to the proof as any other public input
+    let wrong = [public_inputs[0], public_inputs[0]];
+    assert_eq!(
+        verifier.verify_with_constants(&proof, &constants, &wrong),
+        Err(Error::ProofVerificationError)
+    );
+
+    let mut fixed = PublicInputs::new();
+    fixed.insert(indexes[1], public_inputs[1] + BlsScalar::one());
+    let wrong_constants = verifier
+        .constant_public_inputs(fixed)
+        .expect("The constant should be declared by the circuit");
+    assert_eq!(
+        verifier.verify_with_constants(&proof, &wrong_constants, &variable),
+        Err(Error::ProofVerificationError)
+    );
+
+    // the variable public inputs skip the positions of the constants
+    assert_eq!(
+        verifier.verify_with_constants(&proof, &constants, &public_inputs),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 2,
+            provided: 3,
+        })
+    );
+
+    // a constant has to be placed at the gate of a public input
+    let mut undeclared = PublicInputs::new();
+    undeclared.insert(indexes[2] + 1, BlsScalar::one());
+    assert_eq!(
+        verifier.constant_public_inputs(undeclared),
+        Err(Error::UndeclaredPublicInput {
+            index: indexes[2] + 1
+        })
+    );
+
+    // the constants are only accepted by the verifiers of their circuit
+    let (_, other) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let mut fixed = PublicInputs::new();
+    fixed.insert(other.public_input_indexes()[0], public_inputs[1]);
+    let other_constants = other
+        .constant_public_inputs(fixed)
+        .expect("The constant should be declared by the circuit");
+    assert_eq!(
+        verifier.verify_with_constants(&proof, &other_constants, &variable),
+        Err(Error::InconsistentConstantPublicInputs)
+    );
+}
+
+#[test]
+fn verify_from_bytes_pi() {
+    let (_, verifier, proof, public_inputs) =
+        setup(b"verify_from_bytes_pi", 0xb17e);
+
+    let pi_bytes: Vec<[u8; BlsScalar::SIZE]> =
+        public_inputs.iter().map(BlsScalar::to_bytes).collect();
+    proof
+        .verify_from_bytes_pi(&verifier, &pi_bytes)
+        .expect("Verifying with the encoded public inputs should succeed");
+
+    let wrong = [BlsScalar::from(6).to_bytes()];
+    assert_eq!(
+        proof.verify_from_bytes_pi(&verifier, &wrong),
+        Err(Error::ProofVerificationError)
+    );
+
+    // the modulus isn't the canonical encoding of zero
+    let mut modulus = (-BlsScalar::one()).to_bytes();
+    modulus[0] += 1;
+    assert_eq!(
+        proof.verify_from_bytes_pi(&verifier, &[pi_bytes[0], modulus]),
+        Err(Error::InvalidPublicInputEncoding { index: 1 })
+    );
+    assert_eq!(
+        proof.verify_from_bytes_pi(&verifier, &[[0xff; BlsScalar::SIZE]]),
+        Err(Error::InvalidPublicInputEncoding { index: 0 })
+    );
+}
+
+/// Toy algebraic transcript absorbing every message into a single scalar
+struct ScalarTranscript(BlsScalar);
+
+impl ScalarTranscript {
+    fn absorb(&mut self, bytes: &[u8]) {
+        bytes.chunks(32).for_each(|chunk| {
+            let mut wide = [0u8; 64];
+            wide[..chunk.len()].copy_from_slice(chunk);
+            let message = BlsScalar::from_bytes_wide(&wide);
+            self.0 = (self.0 + message).pow(&[5, 0, 0, 0]) + BlsScalar::one();
+        });
+    }
+}
+
+impl TranscriptBackend for ScalarTranscript {
+    fn new(label: &[u8]) -> Self {
+        let mut transcript = Self(BlsScalar::zero());
+        transcript.absorb(label);
+        transcript
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.absorb(label);
+        self.absorb(message);
+    }
+
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        self.absorb(label);
+        self.absorb(&point.to_bytes());
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        self.absorb(label);
+        self.absorb(&s.to_bytes());
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        self.absorb(label);
+        self.0
+    }
+}
+
+#[test]
+fn verify_with_transcript() {
+    let (prover, verifier, mut rng) = compile(b"verify_with_transcript", 0x7a5);
+
+    let circuit = TestCircuit::valid();
+
+    // a proof is only valid for the transcript backend it was proved with
+    let (proof, public_inputs) = prover
+        .prove_with_transcript::<ScalarTranscript, _, _>(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    verifier
+        .verify_with_transcript::<ScalarTranscript>(&proof, &public_inputs)
+        .expect("Verifying with the same backend should succeed");
+    assert_eq!(
+        verifier.verify(&proof, &public_inputs),
+        Err(Error::ProofVerificationError)
+    );
+    assert_eq!(
+        verifier.verify_with_transcript::<ScalarTranscript>(
+            &proof,
+            &[BlsScalar::from(6)]
+        ),
+        Err(Error::ProofVerificationError)
+    );
+
+    // the default backend is the merlin transcript
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    verifier
+        .verify_with_transcript::<merlin::Transcript>(&proof, &public_inputs)
+        .expect("Verifying with the merlin backend should succeed");
+    assert_eq!(
+        verifier
+            .verify_with_transcript::<ScalarTranscript>(&proof, &public_inputs),
+        Err(Error::ProofVerificationError)
+    );
+}
+
+/// Operations performed against the transcript by [`Verifier::verify`] for
+/// the `TestCircuit`, in order, as (operation, label) pairs
+const TRANSCRIPT_ORDER: &[(&str, &str)] = &[
+    ("new", "transcript_order"),
+    // circuit description
+    ("message", "dom-sep"),
+    ("message", "n"),
+    ("message", "circuit_hash"),
+    ("point", "q_m"),
+    ("point", "q_l"),
+    ("point", "q_r"),
+    ("point", "q_o"),
+    ("point", "q_c"),
+    ("point", "q_f"),
+    ("point", "q_arith"),
+    ("point", "q_range"),
+    ("point", "q_logic"),
+    ("point", "q_variable_group_add"),
+    ("point", "q_fixed_group_add"),
+    ("point", "s_sigma_1"),
+    ("point", "s_sigma_2"),
+    ("point", "s_sigma_3"),
+    ("point", "s_sigma_4"),
+    ("message", "dom-sep"),
+    ("message", "n"),
+    // public inputs
+    ("scalar", "pi"),
+    // round 1
+    ("point", "a_comm"),
+    ("point", "b_comm"),
+    ("point", "c_comm"),
+    ("point", "d_comm"),
+    // round 2
+    ("challenge", "beta"),
+    ("scalar", "beta"),
+    ("challenge", "gamma"),
+    ("point", "z_comm"),
+    // round 3
+    ("challenge", "alpha"),
+    ("challenge", "range separation challenge"),
+    ("challenge", "logic separation challenge"),
+    ("challenge", "fixed base separation challenge"),
+    ("challenge", "variable base separation challenge"),
+    ("point", "t_low_comm"),
+    ("point", "t_mid_comm"),
+    ("point", "t_high_comm"),
+    ("point", "t_fourth_comm"),
+    // round 4
+    ("challenge", "z_challenge"),
+    ("scalar", "a_eval"),
+    ("scalar", "b_eval"),
+    ("scalar", "c_eval"),
+    ("scalar", "d_eval"),
+    ("scalar", "s_sigma_1_eval"),
+    ("scalar", "s_sigma_2_eval"),
+    ("scalar", "s_sigma_3_eval"),
+    ("scalar", "z_eval"),
+    ("scalar", "a_w_eval"),
+    ("scalar", "b_w_eval"),
+    ("scalar", "d_w_eval"),
+    ("scalar", "q_arith_eval"),
+    ("scalar", "q_c_eval"),
+    ("scalar", "q_l_eval"),
+    ("scalar", "q_r_eval"),
+    // round 5
+    ("challenge", "v_challenge"),
+    ("challenge", "v_w_challenge"),
+    ("point", "w_z_chall_comm"),
+    ("point", "w_z_chall_w_comm"),
+    ("challenge", "u_challenge"),
+];
+
+thread_local! {
+    static TRANSCRIPT_LOG: RefCell<Vec<(&'static str, String)>> =
+        RefCell::new(Vec::new());
+}
+
+/// Merlin transcript logging the operations performed against it
+struct RecordingTranscript(merlin::Transcript);
+
+impl RecordingTranscript {
+    fn record(op: &'static str, label: &[u8]) {
+        let label = String::from_utf8(label.to_vec())
+            .expect("Transcript labels should be utf-8");
+        TRANSCRIPT_LOG.with(|log| log.borrow_mut().push((op, label)));
+    }
+
+    fn take() -> Vec<(&'static str, String)> {
+        TRANSCRIPT_LOG.with(|log| log.take())
+    }
+}
+
+/// Operations of a transcript, as logged by [`RecordingTranscript`]
+fn logged(order: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+    order
+        .iter()
+        .map(|&(op, label)| (op, label.to_string()))
+        .collect()
+}
+
+impl TranscriptBackend for RecordingTranscript {
+    fn new(label: &[u8]) -> Self {
+        Self::record("new", label);
+        Self(TranscriptBackend::new(label))
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        Self::record("message", label);
+        TranscriptBackend::append_message(&mut self.0, label, message);
+    }
+
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        Self::record("point", label);
+        self.0.append_point(label, point);
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        Self::record("scalar", label);
+        TranscriptBackend::append_scalar(&mut self.0, label, s);
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        Self::record("challenge", label);
+        self.0.challenge_scalar(label)
+    }
+}
+
+#[test]
+fn transcript_order() {
+    let (prover, verifier, mut rng) = compile(b"transcript_order", 0x0bde);
+
+    let circuit = TestCircuit::valid();
+
+    RecordingTranscript::take();
+    let (proof, public_inputs) = prover
+        .prove_with_transcript::<RecordingTranscript, _, _>(&mut rng, &circuit)
+        .expect("Proving should succeed");
+    let proved = RecordingTranscript::take();
+
+    verifier
+        .verify_with_transcript::<RecordingTranscript>(&proof, &public_inputs)
+        .expect("Verifying should succeed");
+    let verified = RecordingTranscript::take();
+
+    assert_eq!(verified, logged(TRANSCRIPT_ORDER));
+
+    // the prover stops at the opening challenges, as the commitments to the
+    // openings and the `u` challenge only batch the pairing check of the
+    // verifier
+    let (proved_order, _) = TRANSCRIPT_ORDER
+        .iter()
+        .position(|&op| op == ("challenge", "v_w_challenge"))
+        .map(|i| TRANSCRIPT_ORDER.split_at(i + 1))
+        .expect("The opening challenge should be drawn");
+    assert_eq!(proved, logged(proved_order));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn trace_to_json() {
+    // same label as `transcript_order`, to compare to its expected order
+    let label = b"transcript_order";
+    let (_, verifier, proof, public_inputs) = setup(label, 0x75ace);
+
+    let json = proof
+        .trace_to_json(&verifier, &public_inputs)
+        .expect("Tracing the verification should succeed");
+    let trace: serde_json::Value =
+        serde_json::from_str(&json).expect("The trace should be valid JSON");
+
+    // the trace records the operations performed by the verifier
+    let transcript: Vec<(&str, &str)> = trace["transcript"]
+        .as_array()
+        .expect("The transcript should be an array")
+        .iter()
+        .map(|entry| {
+            (
+                entry["op"].as_str().unwrap(),
+                entry["label"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(transcript, TRANSCRIPT_ORDER);
+    assert_eq!(
+        trace["transcript"][21]["value"].as_str(),
+        Some(hex::encode(public_inputs[0].to_bytes()).as_str())
+    );
+
+    let challenges = verifier
+        .derive_challenges(&proof, &public_inputs)
+        .expect("Deriving the challenges should succeed");
+    assert_eq!(
+        trace["challenges"]["z"].as_str(),
+        Some(hex::encode(challenges.z.to_bytes()).as_str())
+    );
+    assert!(trace["pairing"]["left"].is_string());
+    assert_eq!(trace["valid"], true);
+    assert!(trace["failure"].is_null());
+
+    // an invalid proof is traced along with the failed check
+    let wrong = [BlsScalar::from(6)];
+    let json = proof
+        .trace_to_json(&verifier, &wrong)
+        .expect("Tracing the verification should succeed");
+    let trace: serde_json::Value =
+        serde_json::from_str(&json).expect("The trace should be valid JSON");
+    assert_eq!(trace["valid"], false);
+    assert_eq!(trace["failure"], "Pairing");
+
+    assert_eq!(
+        proof.trace_to_json(&verifier, &[]),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 1,
+            provided: 0,
+        })
+    );
+}